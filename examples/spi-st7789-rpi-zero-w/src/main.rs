@@ -22,12 +22,10 @@ use embedded_graphics::{
     prelude::*,
     text::Text,
 };
-use embedded_hal_bus::spi::ExclusiveDevice;
-use mipidsi::interface::SpiInterface;
+use mipidsi::interface::SpidevInterface;
 use mipidsi::{models::ST7789, options::ColorInversion, Builder};
 use rppal::gpio::Gpio;
 use rppal::hal::Delay;
-use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
 use std::process::ExitCode;
 
 // Pins
@@ -66,10 +64,9 @@ fn main() -> ExitCode {
     let button_y = gpio.get(BUTTON_Y).unwrap().into_input_pullup();
 
     // SPI Display
-    let spi = Spi::new(Bus::Spi0, SlaveSelect::Ss1, 60_000_000_u32, Mode::Mode0).unwrap();
-    let spi_device = ExclusiveDevice::new_no_delay(spi, NoCs).unwrap();
-    let mut buffer = [0_u8; 512];
-    let di = SpiInterface::new(spi_device, dc, &mut buffer);
+    // The interface sends the pixel data in transfers of the maximum size of the spidev
+    // driver, see the README for raising the limit. CS is handled by the driver.
+    let di = SpidevInterface::open("/dev/spidev0.1", dc, 60_000_000).unwrap();
     let mut delay = Delay::new();
     let mut display = Builder::new(ST7789, di)
         .display_size(W as u16, H as u16)
@@ -156,23 +153,3 @@ fn main() -> ExitCode {
 
     ExitCode::SUCCESS
 }
-
-/// Noop `OutputPin` implementation.
-///
-/// This is passed to `ExclusiveDevice`, because the CS pin is handle in
-/// hardware.
-struct NoCs;
-
-impl embedded_hal::digital::OutputPin for NoCs {
-    fn set_low(&mut self) -> Result<(), Self::Error> {
-        Ok(())
-    }
-
-    fn set_high(&mut self) -> Result<(), Self::Error> {
-        Ok(())
-    }
-}
-
-impl embedded_hal::digital::ErrorType for NoCs {
-    type Error = core::convert::Infallible;
-}
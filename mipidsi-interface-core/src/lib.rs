@@ -0,0 +1,359 @@
+#![no_std]
+#![warn(missing_docs)]
+
+//! Stable interface traits of the [mipidsi](https://docs.rs/mipidsi) display driver.
+//!
+//! This crate contains the [`Interface`] and [`InterfaceRead`] traits, which connect the
+//! driver to the display controller, the [`Event`]s which the driver sends to the interface
+//! and the [`ErrorKind`] of interface errors. It doesn't contain any pixel format
+//! conversions, drivers or models, so it only changes when the traits themselves change.
+//! `mipidsi` re-exports all items in its `interface` module.
+//!
+//! HAL crates can depend on this crate to ship interface implementations which are optimized
+//! for their hardware, e.g. with DMA transfers, without depending on a specific version
+//! of `mipidsi`.
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi_interface_core::{Error, ErrorKind, Interface, InterfaceKind};
+//!
+//! #[derive(Debug)]
+//! struct DmaError;
+//!
+//! impl Error for DmaError {
+//!     fn kind(&self) -> ErrorKind {
+//!         ErrorKind::Bus
+//!     }
+//! }
+//!
+//! struct DmaInterface;
+//!
+//! impl DmaInterface {
+//!     fn transfer(&mut self, dc: bool, data: &[u8]) -> Result<(), DmaError> {
+//!         // start the DMA transfer and wait until it is complete
+//!         # let _ = (dc, data);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! impl Interface for DmaInterface {
+//!     type Word = u8;
+//!     type Error = DmaError;
+//!
+//!     const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+//!
+//!     fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), DmaError> {
+//!         self.transfer(false, &[command])?;
+//!         self.transfer(true, args)
+//!     }
+//!
+//!     fn send_pixels<const N: usize>(
+//!         &mut self,
+//!         pixels: impl IntoIterator<Item = [u8; N]>,
+//!     ) -> Result<(), DmaError> {
+//!         for pixel in pixels {
+//!             self.transfer(true, &pixel)?;
+//!         }
+//!         Ok(())
+//!     }
+//!
+//!     fn send_repeated_pixel<const N: usize>(
+//!         &mut self,
+//!         pixel: [u8; N],
+//!         count: u32,
+//!     ) -> Result<(), DmaError> {
+//!         self.send_pixels((0..count).map(|_| pixel))
+//!     }
+//!
+//!     fn send_pixels_from_buffer<const N: usize>(&mut self, data: &[u8]) -> Result<(), DmaError> {
+//!         // the whole buffer is sent in a single DMA transfer
+//!         self.transfer(true, &data[..data.len() - data.len() % N])
+//!     }
+//! }
+//! ```
+
+/// Error kind
+///
+/// Describes the cause of an interface error independently of the interface implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Error of the bus which transfers the data, e.g. SPI or a parallel bus
+    Bus,
+    /// Error of a control pin, e.g. the data/command or the chip select pin
+    Pin,
+    /// Other error
+    Other,
+}
+
+/// Interface error
+///
+/// Interface implementations should implement this trait for their error type, so
+/// applications can handle the errors of different interfaces in the same way.
+pub trait Error: core::fmt::Debug {
+    /// Returns the kind of the error
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+/// Interface kind
+///
+/// Specifies the kind of physical connection to the display controller, which is used by
+/// models to check if the selected color format is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InterfaceKind {
+    /// Serial interface with data/command pin (MIPI DBI Type C Option 3)
+    ///
+    /// SPI style interface with 8 bits per word and an additional output pin to distinguish
+    /// between command and data transfers.
+    Serial4Line,
+
+    /// Serial interface without data/command pin (MIPI DBI Type C Option 1)
+    ///
+    /// SPI style interface with 9 bits per word, where the first bit distinguishes between
+    /// command and data transfers.
+    Serial3Line,
+
+    /// 8 bit parallel interface
+    ///
+    /// 8080 style parallel interface with 8 data pins and chip select, write enable,
+    /// and command/data signals.
+    Parallel8Bit,
+
+    /// 16 bit parallel interface
+    ///
+    /// 8080 style parallel interface with 16 data pins and chip select, write enable,
+    /// and command/data signals.
+    Parallel16Bit,
+
+    /// 18 bit parallel interface
+    ///
+    /// 8080 style parallel interface with 18 data pins and chip select, write enable,
+    /// and command/data signals.
+    Parallel18Bit,
+}
+
+/// Speed profile
+///
+/// Some panels only work with low SPI clock frequencies during the init sequence, but
+/// accept much higher frequencies for pixel data. The driver selects the speed profile
+/// with [`Event::SpeedProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedProfile {
+    /// Speed used for the reset and the init sequence.
+    Init,
+    /// Speed used after the init sequence, mostly for pixel data.
+    Pixels,
+}
+
+/// Driver event
+///
+/// Events are passed to [`Interface::handle_event`] to inform the interface about the
+/// state of the driver. New events can be added in minor versions, so interfaces must
+/// ignore events they don't handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// Begin a transaction
+    ///
+    /// Sent before the driver sets the address window and starts a memory write.
+    /// Interfaces can use this to group the following commands and pixel data, e.g. by
+    /// keeping the chip select line asserted until [`EndTransaction`](Self::EndTransaction)
+    /// is sent.
+    BeginTransaction,
+
+    /// End a transaction
+    ///
+    /// Sent after the pixel data of a memory write was sent, even if sending it failed.
+    EndTransaction,
+
+    /// Begin a grouped update
+    ///
+    /// Sent before the drawing operations of a grouped update, which can contain multiple
+    /// memory writes. Interfaces which defer the pixel data and send it in bursts when they
+    /// are flushed, e.g. synchronized with the tearing effect signal, must not flush until
+    /// [`EndUpdate`](Self::EndUpdate) is sent.
+    BeginUpdate,
+
+    /// End a grouped update
+    ///
+    /// Sent after the drawing operations of a grouped update. Interfaces which defer the
+    /// pixel data should flush all memory writes of the update in a single burst.
+    EndUpdate,
+
+    /// Select the speed profile
+    ///
+    /// Sent with [`SpeedProfile::Init`] before the display is reset and with
+    /// [`SpeedProfile::Pixels`] after the init sequence has completed. Interfaces which can
+    /// change the clock frequency at runtime can use this to initialize the display with a
+    /// lower frequency.
+    SpeedProfile(SpeedProfile),
+
+    /// Maximum serial clock frequency of the display controller in Hz
+    ///
+    /// Sent before the display is initialized, if the controller specifies a limit.
+    /// Interfaces which know the clock frequency of the bus can use this to report an
+    /// overclocked bus.
+    ClockLimit(u32),
+
+    /// Mark an area as invalid
+    ///
+    /// Sent with an area in controller address coordinates, with inclusive start and end
+    /// coordinates, when the content of the panel in this area is known to be corrupted.
+    /// Interfaces which keep a copy of the display RAM and send the changed areas when they
+    /// are flushed must add the area to their dirty window, so it is sent with the next
+    /// flush even if no pixels were written.
+    Invalidate {
+        /// Start column
+        sx: u16,
+        /// Start row
+        sy: u16,
+        /// End column
+        ex: u16,
+        /// End row
+        ey: u16,
+    },
+}
+
+/// Command and pixel interface
+pub trait Interface {
+    /// The native width of the interface
+    ///
+    /// In most cases this will be u8, except for larger parallel interfaces such as
+    /// 16 bit or 18 bit (u32). Serial interfaces with 9 bit words use u8 and add the
+    /// data/command bit themselves.
+    type Word: Copy;
+
+    /// Error type
+    type Error: Error;
+
+    /// Kind of the physical connection to the display controller
+    const KIND: InterfaceKind;
+
+    /// Send a command with optional parameters
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error>;
+
+    /// Send a sequence of pixels
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function
+    ///
+    /// The driver passes all pixels of a drawing operation to a single call of this function.
+    /// Interfaces which gather the pixel data in a buffer must fill the buffer with as many
+    /// whole pixels as fit into it before each transfer, so that only the last transfer of a
+    /// call is shorter. Interfaces without a buffer send the pixels as they are produced.
+    /// Interfaces which can only send a limited number of bytes in a single transfer, e.g.
+    /// with DMA, must split the pixels into multiple transfers.
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error>;
+
+    /// Send the same pixel value multiple times
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function
+    ///
+    /// The same buffering rules as for [`send_pixels`](Self::send_pixels) apply.
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Send a buffer of pixel data
+    ///
+    /// `WriteMemoryStart` must be sent before calling this function
+    ///
+    /// The buffer contains whole pixels with `N` words each, already converted to the format
+    /// which is sent to the display controller. An incomplete pixel at the end of the buffer
+    /// is ignored. Interfaces which can transfer a slice directly, e.g. with DMA, should
+    /// override this function. The default implementation passes the pixels to
+    /// [`send_pixels`](Self::send_pixels).
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.send_pixels(
+            data.chunks_exact(N)
+                .map(|pixel| -> [Self::Word; N] { core::array::from_fn(|i| pixel[i]) }),
+        )
+    }
+
+    /// Handle a driver event
+    ///
+    /// See [`Event`] for the events which are sent by the driver. Interfaces which wrap
+    /// another interface must pass all events they don't handle themselves to the wrapped
+    /// interface. The default implementation ignores all events.
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        let _ = event;
+        Ok(())
+    }
+}
+
+impl<T: Interface> Interface for &mut T {
+    type Word = T::Word;
+    type Error = T::Error;
+
+    const KIND: InterfaceKind = T::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        T::send_command(self, command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        T::send_pixels(self, pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        T::send_repeated_pixel(self, pixel, count)
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        T::send_pixels_from_buffer::<N>(self, data)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        T::handle_event(self, event)
+    }
+}
+
+/// Interface which can read data from the display controller
+///
+/// Reads require a connected data output of the controller, e.g. the MISO line of a SPI
+/// bus. The driver uses this trait to read the controller state with
+/// `Display::read_display_id` and `Display::read_status`.
+pub trait InterfaceRead: Interface {
+    /// Send a read command and fill `buffer` with the returned data
+    ///
+    /// The buffer contains the raw data, including the dummy clock cycle or dummy byte
+    /// which controllers send before the data of most read commands.
+    fn read_command(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+impl<T: InterfaceRead> InterfaceRead for &mut T {
+    fn read_command(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        T::read_command(self, command, buffer)
+    }
+}
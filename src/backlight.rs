@@ -0,0 +1,173 @@
+//! Backlight control
+//!
+//! A backlight can be added to a [`Display`](crate::Display) by using
+//! [`Builder::backlight`](crate::Builder::backlight). The display will turn
+//! the backlight off while it is sleeping and restore the previous brightness
+//! after it was woken up.
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi::{Builder, backlight::PwmBacklight, models::ILI9341Rgb565};
+//!
+//! # let di = mipidsi::_mock::MockDisplayInterface;
+//! # let pwm = mipidsi::_mock::MockPwm;
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! let mut display = Builder::new(ILI9341Rgb565, di)
+//!     .backlight(PwmBacklight::new(pwm))
+//!     .init(&mut delay).unwrap();
+//!
+//! display.set_backlight(50).unwrap();
+//! ```
+
+use embedded_hal::{digital::OutputPin, pwm::SetDutyCycle};
+
+/// Backlight
+pub trait Backlight {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Sets the backlight brightness in percent.
+    ///
+    /// Implementations which don't support dimming should turn the backlight
+    /// on for all non zero values.
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error>;
+}
+
+/// Marker type for no backlight.
+pub enum NoBacklight {}
+
+impl Backlight for NoBacklight {
+    type Error = core::convert::Infallible;
+
+    fn set_brightness(&mut self, _percent: u8) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Backlight controlled by an [`OutputPin`].
+///
+/// The backlight is turned on for all non zero brightness values.
+pub struct BacklightPin<P> {
+    pin: P,
+    active_low: bool,
+}
+
+impl<P: OutputPin> BacklightPin<P> {
+    /// Creates a new backlight, which is turned on by setting the pin high.
+    pub fn new(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: false,
+        }
+    }
+
+    /// Creates a new backlight, which is turned on by setting the pin low.
+    pub fn new_active_low(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: true,
+        }
+    }
+
+    /// Releases the pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: OutputPin> Backlight for BacklightPin<P> {
+    type Error = P::Error;
+
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+        if (percent > 0) != self.active_low {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        }
+    }
+}
+
+/// Dimmable backlight controlled by a PWM channel.
+pub struct PwmBacklight<P> {
+    pwm: P,
+}
+
+impl<P: SetDutyCycle> PwmBacklight<P> {
+    /// Creates a new dimmable backlight.
+    pub fn new(pwm: P) -> Self {
+        Self { pwm }
+    }
+
+    /// Releases the PWM channel.
+    pub fn release(self) -> P {
+        self.pwm
+    }
+}
+
+impl<P: SetDutyCycle> Backlight for PwmBacklight<P> {
+    type Error = P::Error;
+
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+        self.pwm.set_duty_cycle_percent(percent.min(100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    use super::*;
+
+    struct RecordingBacklight<'a>(&'a Cell<u8>);
+
+    impl Backlight for RecordingBacklight<'_> {
+        type Error = Infallible;
+
+        fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+            self.0.set(percent);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn backlight_follows_sleep_state() {
+        let brightness = Cell::new(0);
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .backlight(RecordingBacklight(&brightness))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(brightness.get(), 100);
+
+        display.set_backlight(30).unwrap();
+        assert_eq!(brightness.get(), 30);
+
+        display.sleep(&mut MockDelay).unwrap();
+        assert_eq!(brightness.get(), 0);
+
+        display.set_backlight(60).unwrap();
+        assert_eq!(brightness.get(), 0);
+        assert_eq!(display.backlight(), 60);
+
+        display.wake(&mut MockDelay).unwrap();
+        assert_eq!(brightness.get(), 60);
+    }
+
+    #[test]
+    fn brightness_is_limited() {
+        let brightness = Cell::new(0);
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .backlight(RecordingBacklight(&brightness))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.set_backlight(200).unwrap();
+        assert_eq!(brightness.get(), 100);
+    }
+}
@@ -1,11 +1,18 @@
 //! Original code from: [this repo](https://github.com/lupyuen/piet-embedded/blob/master/piet-embedded-graphics/src/batch.rs)
-//! Batch the pixels to be rendered into Pixel Rows and Pixel Blocks (contiguous Pixel Rows).
-//! This enables the pixels to be rendered efficiently as Pixel Blocks, which may be transmitted in a single Non-Blocking SPI request.
+//! Batch the pixels to be rendered into Pixel Runs (contiguous pixels in the same row).
+//! This enables the pixels to be rendered efficiently as Pixel Runs, which only need one address window each.
+//!
+//! The runs are detected lazily while the pixels are sent: the address window of a run
+//! extends to the right edge of the display and the colors are streamed to the display until
+//! a pixel doesn't continue the run. No pixel colors are buffered, so runs aren't limited
+//! in length and no RAM is used for buffers.
 use crate::{
+    backlight::Backlight,
     interface::{Interface, InterfacePixelFormat},
     models::Model,
     Display,
 };
+use core::iter::Peekable;
 use embedded_graphics_core::prelude::*;
 use embedded_hal::digital::OutputPin;
 
@@ -19,325 +26,171 @@ where
     fn draw_batch(&mut self, item_pixels: I) -> Result<(), DI::Error>;
 }
 
-impl<DI, M, RST, I> DrawBatch<DI, M, I> for Display<DI, M, RST>
+impl<DI, M, RST, BL, I> DrawBatch<DI, M, I> for Display<DI, M, RST, BL>
 where
     DI: Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     I: IntoIterator<Item = Pixel<M::ColorFormat>>,
     RST: OutputPin,
+    BL: Backlight,
 {
     fn draw_batch(&mut self, item_pixels: I) -> Result<(), DI::Error> {
-        //  Get the pixels for the item to be rendered.
-        let pixels = item_pixels.into_iter();
-        //  Batch the pixels into Pixel Rows.
-        let rows = to_rows(pixels);
-        //  Batch the Pixel Rows into Pixel Blocks.
-        let blocks = to_blocks(rows);
-        //  For each Pixel Block...
-        for PixelBlock {
-            x_left,
-            x_right,
-            y_top,
-            y_bottom,
-            colors,
-            ..
-        } in blocks
-        {
-            //  Render the Pixel Block.
-            self.set_pixels(x_left, y_top, x_right, y_bottom, colors)?;
+        let last_column = self.options.display_size().0.saturating_sub(1);
 
-            //  Dump out the Pixel Blocks for the square in test_display()
-            /* if x_left >= 60 && x_left <= 150 && x_right >= 60 && x_right <= 150 && y_top >= 60 && y_top <= 150 && y_bottom >= 60 && y_bottom <= 150 {
-                console::print("pixel block ("); console::printint(x_left as i32); console::print(", "); console::printint(y_top as i32); ////
-                console::print("), ("); console::printint(x_right as i32); console::print(", "); console::printint(y_bottom as i32); console::print(")\n"); ////
-            } */
-        }
-        Ok(())
+        draw_runs(item_pixels, last_column, |sx, y, ex, colors| {
+            self.draw_pixels(sx, y, ex, y, colors)
+        })
     }
 }
 
-/// Max number of pixels per Pixel Row
-const MAX_ROW_SIZE: usize = 50;
-/// Max number of pixels per Pixel Block
-const MAX_BLOCK_SIZE: usize = 100;
-
-/// Consecutive color words for a Pixel Row
-type RowColors<C> = heapless::Vec<C, MAX_ROW_SIZE>;
-/// Consecutive color words for a Pixel Block
-type BlockColors<C> = heapless::Vec<C, MAX_BLOCK_SIZE>;
-
-/// Iterator for each Pixel Row in the pixel data. A Pixel Row consists of contiguous pixels on the same row.
-#[derive(Debug, Clone)]
-pub struct RowIterator<C, P>
-where
-    C: PixelColor,
-    P: Iterator<Item = Pixel<C>>,
-{
-    /// Pixels to be batched into rows
+/// Splits the pixels into Pixel Runs and renders each run with `draw`.
+///
+/// `draw` is called with the start column, the row and the end column of the address window
+/// of the run. The window extends to `last_column`, but the run ends as soon as a pixel
+/// doesn't continue it.
+fn draw_runs<C, P, E>(
     pixels: P,
-    /// Start column number
-    x_left: u16,
-    /// End column number
-    x_right: u16,
-    /// Row number
-    y: u16,
-    /// List of pixel colours for the entire row
-    colors: RowColors<C>,
-    /// True if this is the first pixel for the row
-    first_pixel: bool,
-}
-
-/// Iterator for each Pixel Block in the pixel data. A Pixel Block consists of contiguous Pixel Rows with the same start and end column number.
-#[derive(Debug, Clone)]
-pub struct BlockIterator<C, R>
+    last_column: u16,
+    mut draw: impl FnMut(u16, u16, u16, PixelRun<'_, P::IntoIter, C>) -> Result<(), E>,
+) -> Result<(), E>
 where
     C: PixelColor,
-    R: Iterator<Item = PixelRow<C>>,
+    P: IntoIterator<Item = Pixel<C>>,
 {
-    /// Pixel Rows to be batched into blocks
-    rows: R,
-    /// Start column number
-    x_left: u16,
-    /// End column number
-    x_right: u16,
-    /// Start row number
-    y_top: u16,
-    /// End row number
-    y_bottom: u16,
-    /// List of pixel colours for the entire block, row by row
-    colors: BlockColors<C>,
-    /// True if this is the first row for the block
-    first_row: bool,
-}
+    let mut pixels = pixels.into_iter().peekable();
 
-/// A row of contiguous pixels
-pub struct PixelRow<C>
-where
-    C: PixelColor,
-{
-    /// Start column number
-    pub x_left: u16,
-    /// End column number
-    pub x_right: u16,
-    /// Row number
-    pub y: u16,
-    /// List of pixel colours for the entire row
-    pub colors: RowColors<C>,
+    while let Some(Pixel(coord, color)) = pixels.next() {
+        if coord.x < 0 || coord.y < 0 {
+            continue;
+        }
+        let x = coord.x as u16;
+        let y = coord.y as u16;
+        //  Pixels outside of the display are drawn as single pixels.
+        let end = last_column.max(x);
+
+        draw(
+            x,
+            y,
+            end,
+            PixelRun {
+                pixels: &mut pixels,
+                first: Some(color),
+                next: coord + Point::new(1, 0),
+                end: i32::from(end),
+            },
+        )?;
+    }
+
+    Ok(())
 }
 
-/// A block of contiguous pixel rows with the same start and end column number
-pub struct PixelBlock<C>
+/// Colors of contiguous pixels in the same row.
+///
+/// The pixels are taken from the underlying iterator as long as they continue the run,
+/// the first pixel which doesn't continue the run is left in the iterator.
+struct PixelRun<'a, I, C>
 where
     C: PixelColor,
+    I: Iterator<Item = Pixel<C>>,
 {
-    /// Start column number
-    pub x_left: u16,
-    /// End column number
-    pub x_right: u16,
-    /// Start row number
-    pub y_top: u16,
-    /// End row number
-    pub y_bottom: u16,
-    /// List of pixel colours for the entire block, row by row
-    pub colors: BlockColors<C>,
+    /// Remaining pixels
+    pixels: &'a mut Peekable<I>,
+    /// Color of the first pixel, which was already taken from the iterator
+    first: Option<C>,
+    /// Position of the next pixel in the run
+    next: Point,
+    /// End column of the address window
+    end: i32,
 }
 
-/// Batch the pixels into Pixel Rows, which are contiguous pixels on the same row.
-/// P can be any Pixel Iterator (e.g. a rectangle).
-fn to_rows<C, P>(pixels: P) -> RowIterator<C, P>
+impl<I, C> Iterator for PixelRun<'_, I, C>
 where
     C: PixelColor,
-    P: Iterator<Item = Pixel<C>>,
+    I: Iterator<Item = Pixel<C>>,
 {
-    RowIterator::<C, P> {
-        pixels,
-        x_left: 0,
-        x_right: 0,
-        y: 0,
-        colors: RowColors::new(),
-        first_pixel: true,
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if let Some(color) = self.first.take() {
+            return Some(color);
+        }
+
+        if self.next.x > self.end {
+            return None;
+        }
+
+        let Pixel(_, color) = self.pixels.next_if(|Pixel(coord, _)| *coord == self.next)?;
+        self.next.x += 1;
+        Some(color)
     }
 }
 
-/// Batch the Pixel Rows into Pixel Blocks, which are contiguous Pixel Rows with the same start and end column number
-/// R can be any Pixel Row Iterator.
-fn to_blocks<C, R>(rows: R) -> BlockIterator<C, R>
-where
-    C: PixelColor,
-    R: Iterator<Item = PixelRow<C>>,
-{
-    BlockIterator::<C, R> {
-        rows,
-        x_left: 0,
-        x_right: 0,
-        y_top: 0,
-        y_bottom: 0,
-        colors: BlockColors::new(),
-        first_row: true,
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_graphics_core::pixelcolor::Rgb565;
+
+    use super::*;
+
+    /// Rendered run: start column, row, end column of the window and number of pixels.
+    type Drawn = heapless::Vec<(u16, u16, u16, usize), 64>;
+
+    fn batch(pixels: impl IntoIterator<Item = (i32, i32)>) -> Drawn {
+        let mut drawn = Drawn::new();
+        let pixels = pixels
+            .into_iter()
+            .map(|(x, y)| Pixel(Point::new(x, y), Rgb565::RED));
+
+        draw_runs(pixels, 479, |sx, y, ex, colors| -> Result<(), Infallible> {
+            drawn.push((sx, y, ex, colors.count())).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        drawn
     }
-}
 
-/// Implement the Iterator for Pixel Rows.
-/// P can be any Pixel Iterator (e.g. a rectangle).
-impl<C, P> Iterator for RowIterator<C, P>
-where
-    C: PixelColor,
-    P: Iterator<Item = Pixel<C>>,
-{
-    /// This Iterator returns Pixel Rows
-    type Item = PixelRow<C>;
+    #[test]
+    fn row_is_one_run() {
+        let drawn = batch((10..20).map(|x| (x, 5)));
+        assert_eq!(drawn, [(10, 5, 479, 10)]);
+    }
 
-    /// Return the next Pixel Row of contiguous pixels on the same row
-    fn next(&mut self) -> Option<Self::Item> {
-        //  Loop over all pixels until we have composed a Pixel Row, or we have run out of pixels.
-        loop {
-            //  Get the next pixel.
-            let next_pixel = self.pixels.next();
-            match next_pixel {
-                None => {
-                    //  If no more pixels...
-                    if self.first_pixel {
-                        return None; //  No pixels to group
-                    }
-                    //  Else return previous pixels as row.
-                    let row = PixelRow {
-                        x_left: self.x_left,
-                        x_right: self.x_right,
-                        y: self.y,
-                        colors: self.colors.clone(),
-                    };
-                    self.colors.clear();
-                    self.first_pixel = true;
-                    return Some(row);
-                }
-                Some(Pixel(coord, color)) => {
-                    if coord.x < 0 || coord.y < 0 {
-                        continue;
-                    }
-                    //  If there is a pixel...
-                    let x = coord.x as u16;
-                    let y = coord.y as u16;
-                    //  Save the first pixel as the row start and handle next pixel.
-                    if self.first_pixel {
-                        self.first_pixel = false;
-                        self.x_left = x;
-                        self.x_right = x;
-                        self.y = y;
-                        self.colors.clear();
-                        if self.colors.push(color).is_err() {
-                            return None;
-                        }
-                        continue;
-                    }
-                    //  If this pixel is adjacent to the previous pixel, add to the row.
-                    if x == self.x_right.wrapping_add(1)
-                        && y == self.y
-                        && self.colors.push(color).is_ok()
-                    {
-                        // Don't add pixel if too many pixels in the row.
-                        self.x_right = x;
-                        continue;
-                    }
-                    //  Else return previous pixels as row.
-                    let row = PixelRow {
-                        x_left: self.x_left,
-                        x_right: self.x_right,
-                        y: self.y,
-                        colors: self.colors.clone(),
-                    };
-                    self.x_left = x;
-                    self.x_right = x;
-                    self.y = y;
-                    self.colors.clear();
-                    if self.colors.push(color).is_err() {
-                        return None;
-                    }
-                    return Some(row);
-                }
-            }
-        }
+    #[test]
+    fn runs_are_not_limited_in_length() {
+        let drawn = batch((0..480).map(|x| (x, 0)));
+        assert_eq!(drawn, [(0, 0, 479, 480)]);
     }
-}
 
-/// Implement the Iterator for Pixel Blocks.
-/// R can be any Pixel Row Iterator.
-impl<C, R> Iterator for BlockIterator<C, R>
-where
-    C: PixelColor,
-    R: Iterator<Item = PixelRow<C>>,
-{
-    /// This Iterator returns Pixel Blocks
-    type Item = PixelBlock<C>;
+    #[test]
+    fn rows_are_separate_runs() {
+        let drawn = batch((0..3).flat_map(|y| (0..200).map(move |x| (x, y))));
+        assert_eq!(
+            drawn,
+            [(0, 0, 479, 200), (0, 1, 479, 200), (0, 2, 479, 200)]
+        );
+    }
 
-    /// Return the next Pixel Block of contiguous Pixel Rows with the same start and end column number
-    fn next(&mut self) -> Option<Self::Item> {
-        //  Loop over all Pixel Rows until we have composed a Pixel Block, or we have run out of Pixel Rows.
-        loop {
-            //  Get the next Pixel Row.
-            let next_row = self.rows.next();
-            match next_row {
-                None => {
-                    //  If no more Pixel Rows...
-                    if self.first_row {
-                        return None; //  No rows to group
-                    }
-                    //  Else return previous rows as block.
-                    let row = PixelBlock {
-                        x_left: self.x_left,
-                        x_right: self.x_right,
-                        y_top: self.y_top,
-                        y_bottom: self.y_bottom,
-                        colors: self.colors.clone(),
-                    };
-                    self.colors.clear();
-                    self.first_row = true;
-                    return Some(row);
-                }
-                Some(PixelRow {
-                    x_left,
-                    x_right,
-                    y,
-                    colors,
-                    ..
-                }) => {
-                    //  If there is a Pixel Row...
-                    //  Save the first row as the block start and handle next block.
-                    if self.first_row {
-                        self.first_row = false;
-                        self.x_left = x_left;
-                        self.x_right = x_right;
-                        self.y_top = y;
-                        self.y_bottom = y;
-                        self.colors.clear();
-                        self.colors.extend_from_slice(&colors).expect("never");
-                        continue;
-                    }
-                    //  If this row is adjacent to the previous row and same size, add to the block.
-                    if y == self.y_bottom + 1 && x_left == self.x_left && x_right == self.x_right {
-                        //  Don't add row if too many pixels in the block.
-                        if self.colors.extend_from_slice(&colors).is_ok() {
-                            self.y_bottom = y;
-                            continue;
-                        }
-                    }
-                    //  Else return previous rows as block.
-                    let row = PixelBlock {
-                        x_left: self.x_left,
-                        x_right: self.x_right,
-                        y_top: self.y_top,
-                        y_bottom: self.y_bottom,
-                        colors: self.colors.clone(),
-                    };
-                    self.x_left = x_left;
-                    self.x_right = x_right;
-                    self.y_top = y;
-                    self.y_bottom = y;
-                    self.colors.clear();
-                    self.colors.extend_from_slice(&colors).expect("never");
-                    return Some(row);
-                }
-            }
-        }
+    #[test]
+    fn gap_starts_new_run() {
+        let drawn = batch([(0, 0), (1, 0), (2, 0), (4, 0), (5, 0), (5, 5)]);
+        assert_eq!(drawn, [(0, 0, 479, 3), (4, 0, 479, 2), (5, 5, 479, 1)]);
+    }
+
+    #[test]
+    fn run_ends_at_last_column() {
+        let drawn = batch((478..482).map(|x| (x, 0)));
+        assert_eq!(
+            drawn,
+            [(478, 0, 479, 2), (480, 0, 480, 1), (481, 0, 481, 1)]
+        );
+    }
+
+    #[test]
+    fn negative_coordinates_are_skipped() {
+        let drawn = batch([(-1, 0), (0, 0), (1, 0), (0, -1)]);
+        assert_eq!(drawn, [(0, 0, 479, 2)]);
     }
 }
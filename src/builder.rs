@@ -3,10 +3,20 @@
 use embedded_hal::digital;
 use embedded_hal::{delay::DelayNs, digital::OutputPin};
 
-use crate::interface::{Interface, InterfacePixelFormat};
-use crate::{dcs::InterfaceExt, models::Model, Display};
-
-use crate::options::{ColorInversion, ColorOrder, ModelOptions, Orientation, RefreshOrder};
+use crate::backlight::{Backlight, NoBacklight};
+use crate::interface::{Event, Interface, InterfacePixelFormat, SpeedProfile};
+use crate::{
+    dcs::{InterfaceExt, SetAddressMode, SetDisplayOn},
+    models::{ConfigurationError, Model, ModelInitError},
+    Display,
+};
+
+use crate::options::{
+    BrightnessTable, ColorInversion, ColorOrder, Endianness, ModelOptions, Mounting, Orientation,
+    RefreshOrder,
+};
+use crate::timing::{CheckedDelay, CheckedInterface, TimingChecker};
+use crate::trace;
 
 /// Builder for [Display] instances.
 ///
@@ -26,7 +36,7 @@ use crate::options::{ColorInversion, ColorOrder, ModelOptions, Orientation, Refr
 ///     .display_size(320, 240)
 ///     .init(&mut delay).unwrap();
 /// ```
-pub struct Builder<DI, MODEL, RST>
+pub struct Builder<DI, MODEL, RST, BL = NoBacklight>
 where
     DI: Interface,
     MODEL: Model,
@@ -35,9 +45,16 @@ where
     di: DI,
     model: MODEL,
     rst: Option<RST>,
+    bl: Option<BL>,
     options: ModelOptions,
+    display_on_after_first_draw: bool,
+    init_hook: Option<InitHook<DI>>,
+    warning_hook: Option<fn(ConfigurationWarning)>,
 }
 
+/// Function which is called by [`Builder::init_hook`] after the model init sequence.
+pub type InitHook<DI> = fn(&mut DI, &ModelOptions) -> Result<(), <DI as Interface>::Error>;
+
 impl<DI, MODEL> Builder<DI, MODEL, NoResetPin>
 where
     DI: Interface,
@@ -53,17 +70,22 @@ where
             di,
             model,
             rst: None,
+            bl: None,
             options: ModelOptions::full_size::<MODEL>(),
+            display_on_after_first_draw: false,
+            init_hook: None,
+            warning_hook: None,
         }
     }
 }
 
-impl<DI, MODEL, RST> Builder<DI, MODEL, RST>
+impl<DI, MODEL, RST, BL> Builder<DI, MODEL, RST, BL>
 where
     DI: Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
 {
     ///
     /// Sets the invert color flag
@@ -83,6 +105,15 @@ where
         self
     }
 
+    ///
+    /// Sets the [Endianness] of 16-bit pixel data
+    ///
+    #[must_use]
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.options.endianness = endianness;
+        self
+    }
+
     ///
     /// Sets the [Orientation]
     ///
@@ -92,6 +123,16 @@ where
         self
     }
 
+    /// Sets the mounting of the panel.
+    ///
+    /// The [`Mounting`] is applied in addition to the [orientation](Self::orientation), see
+    /// its documentation for more information.
+    #[must_use]
+    pub fn mounting(mut self, mounting: Mounting) -> Self {
+        self.options.mounting = mounting;
+        self
+    }
+
     ///
     /// Sets refresh order
     ///
@@ -123,6 +164,73 @@ where
         self
     }
 
+    /// Sets the vendor brightness and gamma table.
+    ///
+    /// The table is only used by models which support it, currently the
+    /// [`RM67162`](crate::models::RM67162).
+    #[must_use]
+    pub fn brightness_table(mut self, table: BrightnessTable) -> Self {
+        self.options.brightness_table = Some(table);
+        self
+    }
+
+    /// Defers turning on the display until the first frame has been drawn.
+    ///
+    /// By default the display is turned on at the end of [`init`](Self::init), which can
+    /// briefly show the uninitialized framebuffer content. If this option is set, the display
+    /// stays turned off until the first frame is drawn with [`Display::first_draw`], which
+    /// turns on the display and waits for the
+    /// [`DISPLAY_ON_DELAY_US`](crate::models::Model::DISPLAY_ON_DELAY_US) of the model.
+    #[must_use]
+    pub fn display_on_after_first_draw(mut self) -> Self {
+        self.display_on_after_first_draw = true;
+        self
+    }
+
+    /// Sets a function which sends additional commands after the model init sequence.
+    ///
+    /// Many panels need vendor specific register writes, e.g. for the gamma curves, the
+    /// VCOM voltage or the porch settings, which differ from the values that are used by
+    /// the model. The hook is called with the display interface and the options after the
+    /// model init sequence and before the display is turned on, which makes it possible to
+    /// change these registers without creating a new model. The hook must not change the
+    /// address mode or the pixel format, because the driver isn't aware of these changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{dcs::InterfaceExt, interface::Interface, models::ST7789, Builder};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let display = Builder::new(ST7789, di)
+    ///     .init_hook(|di, _options| {
+    ///         // VCOM setting
+    ///         di.write_raw(0xBB, &[0x2B])
+    ///     })
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn init_hook(mut self, hook: InitHook<DI>) -> Self {
+        self.init_hook = Some(hook);
+        self
+    }
+
+    /// Sets a function which is called for suspicious configurations.
+    ///
+    /// During [`init`](Self::init) the options are checked for configurations which are
+    /// valid, but often caused by a mistake. Each problem is reported to the hook as a
+    /// [`ConfigurationWarning`] and the initialization continues normally. This can be used
+    /// to log these problems during development. The warnings are reported before the
+    /// display size and offset are checked, so the hook is also called for configurations
+    /// which make [`init`](Self::init) panic.
+    #[must_use]
+    pub fn warning_hook(mut self, hook: fn(ConfigurationWarning)) -> Self {
+        self.warning_hook = Some(hook);
+        self
+    }
+
     /// Sets the reset pin.
     ///
     /// ### WARNING
@@ -130,12 +238,35 @@ where
     /// If it wasn't provided the user needs to ensure this is the case.
     ///
     #[must_use]
-    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2> {
+    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2, BL> {
         Builder {
             di: self.di,
             model: self.model,
             rst: Some(rst),
+            bl: self.bl,
             options: self.options,
+            display_on_after_first_draw: self.display_on_after_first_draw,
+            init_hook: self.init_hook,
+            warning_hook: self.warning_hook,
+        }
+    }
+
+    /// Sets the backlight.
+    ///
+    /// The backlight is turned on to full brightness by [`init`](Self::init)
+    /// and can be controlled by using [`Display::set_backlight`].
+    /// See the [`backlight`](crate::backlight) module for the available implementations.
+    #[must_use]
+    pub fn backlight<BL2: Backlight>(self, bl: BL2) -> Builder<DI, MODEL, RST, BL2> {
+        Builder {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            bl: Some(bl),
+            options: self.options,
+            display_on_after_first_draw: self.display_on_after_first_draw,
+            init_hook: self.init_hook,
+            warning_hook: self.warning_hook,
         }
     }
 
@@ -150,53 +281,262 @@ where
     /// and [`display_offset`](Self::display_offset) settings is (partially)
     /// outside the framebuffer.
     pub fn init(
+        self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<Display<DI, MODEL, RST, BL>, InitError<DI::Error, RST::Error, BL::Error>> {
+        self.init_display(0, delay_source, false, |_| Ok(()))
+    }
+
+    ///
+    /// Consumes the builder to create a new [Display], retrying the initialization if it fails.
+    ///
+    /// This works like [`init`](Self::init), but if the display interface reports an error
+    /// during the initialization the display is reset and the initialization sequence is
+    /// repeated up to `retries` times. This can help with marginal wiring or startup issues at
+    /// low temperatures. Errors caused by the reset pin are returned immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the area defined by the [`display_size`](Self::display_size)
+    /// and [`display_offset`](Self::display_offset) settings is (partially)
+    /// outside the framebuffer.
+    pub fn init_with_retries(
+        self,
+        retries: u8,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<Display<DI, MODEL, RST, BL>, InitError<DI::Error, RST::Error, BL::Error>> {
+        self.init_display(retries, delay_source, false, |_| Ok(()))
+    }
+
+    ///
+    /// Consumes the builder to create a new [Display], drawing a splash screen before the
+    /// display is turned on.
+    ///
+    /// This works like [`init`](Self::init), but calls `splash` after the display was
+    /// initialized and before it is turned on. This can be used to clear the framebuffer or
+    /// to draw an image, which prevents the uninitialized framebuffer content from being
+    /// visible after power on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .init_with_splash(&mut delay, |display| display.clear(Rgb565::BLACK))
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the area defined by the [`display_size`](Self::display_size)
+    /// and [`display_offset`](Self::display_offset) settings is (partially)
+    /// outside the framebuffer.
+    pub fn init_with_splash<F>(
+        self,
+        delay_source: &mut impl DelayNs,
+        splash: F,
+    ) -> Result<Display<DI, MODEL, RST, BL>, InitError<DI::Error, RST::Error, BL::Error>>
+    where
+        F: FnOnce(&mut Display<DI, MODEL, RST, BL>) -> Result<(), DI::Error>,
+    {
+        self.init_display(0, delay_source, true, splash)
+    }
+
+    fn init_display<F>(
         mut self,
+        retries: u8,
         delay_source: &mut impl DelayNs,
-    ) -> Result<Display<DI, MODEL, RST>, InitError<DI::Error, RST::Error>> {
+        has_splash: bool,
+        splash: F,
+    ) -> Result<Display<DI, MODEL, RST, BL>, InitError<DI::Error, RST::Error, BL::Error>>
+    where
+        F: FnOnce(&mut Display<DI, MODEL, RST, BL>) -> Result<(), DI::Error>,
+    {
+        // The warnings are reported first, because they can explain the following panics
+        if let Some(hook) = self.warning_hook {
+            self.check_options().for_each(hook);
+        }
+
         let to_u32 = |(a, b)| (u32::from(a), u32::from(b));
         let (width, height) = to_u32(self.options.display_size);
         let (offset_x, offset_y) = to_u32(self.options.display_offset);
         let (max_width, max_height) = to_u32(MODEL::FRAMEBUFFER_SIZE);
         assert!(width + offset_x <= max_width);
         assert!(height + offset_y <= max_height);
-
-        match self.rst {
-            Some(ref mut rst) => {
-                rst.set_low().map_err(InitError::ResetPin)?;
-                delay_source.delay_us(10);
-                rst.set_high().map_err(InitError::ResetPin)?;
-            }
-            None => self
-                .di
-                .write_command(crate::dcs::SoftReset)
-                .map_err(InitError::Interface)?,
+        let address_offset = self.options.address_offset(MODEL::FRAMEBUFFER_SIZE).ok_or(
+            InitError::InvalidConfiguration(ConfigurationError::InvalidOffset),
+        )?;
+
+        if let Some(max_hz) = MODEL::MAX_SERIAL_CLOCK_HZ {
+            self.di
+                .handle_event(Event::ClockLimit(max_hz))
+                .map_err(InitError::Interface)?;
         }
 
-        let madctl = self
-            .model
-            .init(&mut self.di, delay_source, &self.options)
+        // The model turns on the display, unless something needs to be done before
+        let display_on_after_init = has_splash || self.init_hook.is_some();
+        self.options.display_on = !display_on_after_init && !self.display_on_after_first_draw;
+
+        let mut attempts_left = retries;
+        let madctl = loop {
+            match self.reset_and_init(delay_source) {
+                Ok(madctl) => break madctl,
+                Err(InitError::Interface(_)) if attempts_left > 0 => {
+                    trace::init("interface error, retrying");
+                    attempts_left -= 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        self.di
+            .handle_event(Event::SpeedProfile(SpeedProfile::Pixels))
             .map_err(InitError::Interface)?;
 
-        let display = Display {
+        let mut display = Display {
             di: self.di,
             model: self.model,
             rst: self.rst,
+            bl: self.bl,
+            backlight_brightness: 100,
             options: self.options,
             madctl,
             sleeping: false, // TODO: init should lock state
+            display_on_pending: self.display_on_after_first_draw,
+            persistent_window: None,
+            in_update: false,
+            address_window: None,
+            scroll_offset: 0,
+            scroll_area: (0, MODEL::FRAMEBUFFER_SIZE.1),
+            address_offset,
         };
 
+        splash(&mut display).map_err(InitError::Interface)?;
+
+        if display_on_after_init && !display.display_on_pending {
+            trace::init("display on");
+            display
+                .di
+                .write_command(SetDisplayOn)
+                .map_err(InitError::Interface)?;
+            delay_source.delay_us(MODEL::DISPLAY_ON_DELAY_US);
+        }
+
+        if let Some(bl) = display.bl.as_mut() {
+            bl.set_brightness(display.backlight_brightness)
+                .map_err(InitError::Backlight)?;
+        }
+
         Ok(display)
     }
+
+    // Returns the warnings for the current options.
+    fn check_options(&self) -> impl Iterator<Item = ConfigurationWarning> {
+        let size = self.options.display_size;
+        let offset = self.options.display_offset;
+        let correction = self.options.mounting.offset;
+        let full_size = MODEL::FRAMEBUFFER_SIZE;
+
+        let offset_with_full_size = (size.0 == full_size.0 && (offset.0 != 0 || correction.0 != 0))
+            || (size.1 == full_size.1 && (offset.1 != 0 || correction.1 != 0));
+        let inversion_overridden = MODEL::DEFAULT_COLOR_INVERSION == ColorInversion::Inverted
+            && self.options.invert_colors != ColorInversion::Inverted;
+
+        [
+            offset_with_full_size.then_some(ConfigurationWarning::OffsetWithFullSize),
+            inversion_overridden.then_some(ConfigurationWarning::InversionOverridden),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    // Resets the display and runs the model init sequence.
+    //
+    // The required delays between commands are checked in debug builds.
+    fn reset_and_init(
+        &mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<SetAddressMode, InitError<DI::Error, RST::Error, BL::Error>> {
+        self.di
+            .handle_event(Event::SpeedProfile(SpeedProfile::Init))
+            .map_err(InitError::Interface)?;
+
+        let checker = TimingChecker::default();
+        let mut di = CheckedInterface::new(&mut self.di, &checker);
+        let mut delay = CheckedDelay::new(delay_source, &checker);
+
+        match self.rst {
+            Some(ref mut rst) => {
+                trace::init("hardware reset");
+                rst.set_low().map_err(InitError::ResetPin)?;
+                delay.delay_us(10);
+                rst.set_high().map_err(InitError::ResetPin)?;
+                checker.reset();
+            }
+            None => {
+                trace::init("software reset");
+                di.write_command(crate::dcs::SoftReset)
+                    .map_err(InitError::Interface)?
+            }
+        }
+
+        trace::init("model init sequence");
+        let madctl = self
+            .model
+            .init(&mut di, &mut delay, &self.options)
+            .map_err(InitError::from)?;
+
+        if let Some(hook) = self.init_hook {
+            trace::init("init hook");
+            hook(&mut self.di, &self.options).map_err(InitError::Interface)?;
+        }
+
+        Ok(madctl)
+    }
+}
+
+/// Suspicious configuration which is reported to [`Builder::warning_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigurationWarning {
+    /// The display size equals the framebuffer size along an axis, but an offset is set
+    /// for this axis.
+    ///
+    /// The offset, usually a [`Mounting`] offset, moves a part of the display area outside
+    /// of the framebuffer. A [`display_offset`](Builder::display_offset) along this axis
+    /// makes [`init`](Builder::init) panic after this warning was reported.
+    OffsetWithFullSize,
+    /// The color inversion was disabled for a model which enables it by default.
+    ///
+    /// Panels for these models usually show inverted colors without color inversion.
+    InversionOverridden,
 }
 
 /// Error returned by [`Builder::init`].
 #[derive(Debug)]
-pub enum InitError<DI, P> {
+pub enum InitError<DI, P, BL = core::convert::Infallible> {
     /// Error caused by the display interface.
     Interface(DI),
     /// Error caused by the reset pin's [`OutputPin`](embedded_hal::digital::OutputPin) implementation.
     ResetPin(P),
+    /// Error caused by the [`Backlight`] implementation.
+    Backlight(BL),
+    /// Invalid configuration error.
+    ///
+    /// This error is returned if the model doesn't support the display interface.
+    InvalidConfiguration(ConfigurationError),
+}
+
+impl<DI, P, BL> From<ModelInitError<DI>> for InitError<DI, P, BL> {
+    fn from(value: ModelInitError<DI>) -> Self {
+        match value {
+            ModelInitError::Interface(e) => Self::Interface(e),
+            ModelInitError::InvalidConfiguration(ce) => Self::InvalidConfiguration(ce),
+        }
+    }
 }
 
 /// Marker type for no reset pin.
@@ -218,11 +558,17 @@ impl digital::ErrorType for NoResetPin {
 
 #[cfg(test)]
 mod tests {
+    use core::cell::Cell;
+
     use crate::{
         _mock::{MockDelay, MockDisplayInterface, MockOutputPin},
-        models::ILI9341Rgb565,
+        _test::{FlakyInterface, RecordingInterface},
+        interface::ErrorKind,
+        models::{ILI9341Rgb565, ST7789},
     };
 
+    use embedded_graphics_core::{draw_target::DrawTarget, pixelcolor::RgbColor};
+
     use super::*;
 
     #[test]
@@ -282,6 +628,230 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn mounting_offset_outside_address_space_is_rejected() {
+        let result = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .mounting(Mounting::new().offset(-1, 0))
+            .init(&mut MockDelay);
+        assert!(matches!(
+            result,
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::InvalidOffset
+            ))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "OffsetWithFullSize")]
+    fn offset_warning_is_reported_before_panic() {
+        let _ = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .warning_hook(|warning| panic!("{warning:?}"))
+            .display_size(240, 320)
+            .display_offset(1, 0)
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+
+    /// Model with non standard default options.
+    struct BgrInvertedModel;
+
+    impl Model for BgrInvertedModel {
+        type ColorFormat = embedded_graphics_core::pixelcolor::Rgb565;
+        const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+        const DEFAULT_COLOR_ORDER: ColorOrder = ColorOrder::Bgr;
+        const DEFAULT_COLOR_INVERSION: ColorInversion = ColorInversion::Inverted;
+
+        fn init<DELAY, DI>(
+            &mut self,
+            _di: &mut DI,
+            _delay: &mut DELAY,
+            options: &ModelOptions,
+        ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+        where
+            DELAY: DelayNs,
+            DI: Interface,
+        {
+            Ok(SetAddressMode::from(options))
+        }
+    }
+
+    #[test]
+    fn model_default_options() {
+        let builder = Builder::new(BgrInvertedModel, MockDisplayInterface);
+        assert_eq!(builder.options.color_order, ColorOrder::Bgr);
+        assert_eq!(builder.options.invert_colors, ColorInversion::Inverted);
+
+        let builder = builder
+            .color_order(ColorOrder::Rgb)
+            .invert_colors(ColorInversion::Normal);
+        assert_eq!(builder.options.color_order, ColorOrder::Rgb);
+        assert_eq!(builder.options.invert_colors, ColorInversion::Normal);
+    }
+
+    /// Delay which records the last requested delay.
+    struct LastDelay<'a>(&'a Cell<u32>);
+
+    impl DelayNs for LastDelay<'_> {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0.set(ns / 1000);
+        }
+    }
+
+    #[test]
+    fn display_on_delay_is_model_specific() {
+        let last_delay = Cell::new(u32::MAX);
+
+        Builder::new(ST7789, MockDisplayInterface)
+            .init(&mut LastDelay(&last_delay))
+            .unwrap();
+        assert_eq!(last_delay.get(), 120_000);
+
+        Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .init(&mut LastDelay(&last_delay))
+            .unwrap();
+        assert_eq!(last_delay.get(), 0);
+    }
+
+    #[test]
+    fn display_is_turned_on_after_splash() {
+        let last_delay = Cell::new(u32::MAX);
+
+        let mut display = Builder::new(ST7789, RecordingInterface::default())
+            .init_with_splash(&mut LastDelay(&last_delay), |display| {
+                // The model init sequence leaves the display turned off
+                assert_ne!(unsafe { display.dcs() }.last_command, 0x29);
+                display.set_pixel(0, 0, RgbColor::BLACK).unwrap();
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.last_command, 0x29);
+        assert_eq!(last_delay.get(), 120_000);
+
+        let mut display = Builder::new(ST7789, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.last_command, 0x29);
+    }
+
+    #[test]
+    fn init_hook_is_called_after_model_init() {
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::default())
+            .display_on_after_first_draw()
+            .init_hook(|di, _| di.send_command(0xC5, &[0x3E, 0x28]))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(unsafe { display.dcs() }.last_command, 0xC5);
+    }
+
+    #[test]
+    fn warnings_are_reported() {
+        use core::sync::atomic::{AtomicU32, Ordering};
+
+        static INVERSION_WARNINGS: AtomicU32 = AtomicU32::new(0);
+        static OFFSET_WARNINGS: AtomicU32 = AtomicU32::new(0);
+
+        fn hook(warning: ConfigurationWarning) {
+            match warning {
+                ConfigurationWarning::InversionOverridden => &INVERSION_WARNINGS,
+                ConfigurationWarning::OffsetWithFullSize => &OFFSET_WARNINGS,
+            }
+            .fetch_add(1, Ordering::Relaxed);
+        }
+
+        Builder::new(BgrInvertedModel, MockDisplayInterface)
+            .warning_hook(hook)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(INVERSION_WARNINGS.load(Ordering::Relaxed), 0);
+        assert_eq!(OFFSET_WARNINGS.load(Ordering::Relaxed), 0);
+
+        Builder::new(BgrInvertedModel, MockDisplayInterface)
+            .warning_hook(hook)
+            .invert_colors(ColorInversion::Normal)
+            .display_size(240, 240)
+            .mounting(Mounting::new().offset(0, 80))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(INVERSION_WARNINGS.load(Ordering::Relaxed), 1);
+        // The offset is only set for the smaller axis
+        assert_eq!(OFFSET_WARNINGS.load(Ordering::Relaxed), 0);
+
+        Builder::new(BgrInvertedModel, MockDisplayInterface)
+            .warning_hook(hook)
+            .mounting(Mounting::new().offset(0, 80))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(OFFSET_WARNINGS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn display_on_after_first_draw() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .display_on_after_first_draw()
+            .init(&mut MockDelay)
+            .unwrap();
+        assert!(display.display_on_pending);
+
+        // Other drawing operations don't turn on the display
+        display.set_pixel(0, 0, RgbColor::BLACK).unwrap();
+        assert!(display.display_on_pending);
+
+        display
+            .first_draw(&mut MockDelay, |display| display.clear(RgbColor::BLACK))
+            .unwrap();
+        assert!(!display.display_on_pending);
+    }
+
+    #[test]
+    fn deferred_display_on_is_followed_by_delay() {
+        let last_delay = Cell::new(u32::MAX);
+
+        let mut display = Builder::new(ST7789, RecordingInterface::default())
+            .display_on_after_first_draw()
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_ne!(unsafe { display.dcs() }.last_command, 0x29);
+
+        display
+            .first_draw(&mut LastDelay(&last_delay), |display| {
+                display.clear(RgbColor::BLACK)
+            })
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.last_command, 0x29);
+        assert_eq!(last_delay.get(), 120_000);
+
+        // The display is only turned on once
+        last_delay.set(u32::MAX);
+        display
+            .first_draw(&mut LastDelay(&last_delay), |display| {
+                display.clear(RgbColor::BLACK)
+            })
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.last_command, 0x2C);
+        assert_eq!(last_delay.get(), u32::MAX);
+    }
+
+    #[test]
+    fn init_with_retries() {
+        let di = FlakyInterface {
+            failures: 2,
+            ..FlakyInterface::default()
+        };
+        assert!(matches!(
+            Builder::new(ILI9341Rgb565, di).init_with_retries(1, &mut MockDelay),
+            Err(InitError::Interface(ErrorKind::Bus))
+        ));
+
+        let di = FlakyInterface {
+            failures: 2,
+            ..FlakyInterface::default()
+        };
+        let _ = Builder::new(ILI9341Rgb565, di)
+            .init_with_retries(2, &mut MockDelay)
+            .unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "assertion failed: width != 0 && height != 0")]
     fn panic_zero_size() {
@@ -0,0 +1,235 @@
+//! On-device calibration of the display options.
+//!
+//! The [`Calibration`] state machine automates the steps described in the
+//! [troubleshooting guide](https://github.com/almindor/mipidsi/blob/master/docs/TROUBLESHOOTING.md).
+//! It steps through all possible orientations, color orders and color
+//! inversion settings, drawing the [`TestImage`] after each change, and
+//! returns the [`ModelOptions`] selected by the user.
+//!
+//! User input, e.g. from two buttons, is passed to the state machine as
+//! [`Input`] values:
+//!
+//! - [`Input::Next`] selects the next setting for the current step,
+//! - [`Input::Confirm`] accepts the current setting and continues with the next step.
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi::calibration::{Calibration, Input};
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! # let mut inputs = [Input::Next, Input::Confirm, Input::Confirm, Input::Confirm].into_iter();
+//! # let mut wait_for_button = || inputs.next().unwrap();
+//! let mut calibration = Calibration::new();
+//! calibration.start(&mut display).unwrap();
+//!
+//! let options = loop {
+//!     let input = wait_for_button();
+//!     if let Some(options) = calibration.handle(&mut display, input).unwrap() {
+//!         break options;
+//!     }
+//! };
+//! ```
+
+use embedded_graphics_core::Drawable;
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::{ColorInversion, ColorOrder, ModelOptions, Orientation, Rotation},
+    Display, SetOrientationError, TestImage,
+};
+
+/// All orientations in the order they are shown during calibration.
+const ORIENTATIONS: [Orientation; 8] = [
+    orientation(Rotation::Deg0, false),
+    orientation(Rotation::Deg0, true),
+    orientation(Rotation::Deg90, false),
+    orientation(Rotation::Deg90, true),
+    orientation(Rotation::Deg180, false),
+    orientation(Rotation::Deg180, true),
+    orientation(Rotation::Deg270, false),
+    orientation(Rotation::Deg270, true),
+];
+
+/// Abbreviated constructor for orientations.
+const fn orientation(rotation: Rotation, mirrored: bool) -> Orientation {
+    Orientation { rotation, mirrored }
+}
+
+/// Calibration step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Selecting the orientation.
+    Orientation,
+    /// Selecting the subpixel order.
+    ColorOrder,
+    /// Selecting the color inversion.
+    ColorInversion,
+    /// Calibration is finished.
+    Done,
+}
+
+/// User input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// Try the next setting for the current step.
+    Next,
+    /// Accept the current setting and continue with the next step.
+    Confirm,
+}
+
+/// Calibration state machine.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    step: Step,
+}
+
+impl Calibration {
+    /// Creates a new calibration state machine.
+    pub const fn new() -> Self {
+        Self {
+            step: Step::Orientation,
+        }
+    }
+
+    /// Returns the current calibration step.
+    pub fn step(&self) -> Step {
+        self.step
+    }
+
+    /// Starts the calibration by drawing the test image using the current display settings.
+    pub fn start<DI, M, RST, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        self.step = Step::Orientation;
+
+        TestImage::new().draw(display)
+    }
+
+    /// Handles user input.
+    ///
+    /// Returns the selected options after the last step was confirmed.
+    pub fn handle<DI, M, RST, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL>,
+        input: Input,
+    ) -> Result<Option<ModelOptions>, DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        match (self.step, input) {
+            (Step::Done, _) => return Ok(Some(display.options.clone())),
+            (Step::Orientation, Input::Next) => {
+                let current = ORIENTATIONS
+                    .iter()
+                    .position(|o| *o == display.orientation());
+
+                // Orientations in which the display offset doesn't fit are skipped
+                for step in 1..=ORIENTATIONS.len() {
+                    let index =
+                        current.map_or(step - 1, |index| (index + step) % ORIENTATIONS.len());
+                    match display.set_orientation(ORIENTATIONS[index]) {
+                        Ok(()) => break,
+                        Err(SetOrientationError::Interface(e)) => return Err(e),
+                        Err(SetOrientationError::InvalidOffset) => {}
+                    }
+                }
+            }
+            (Step::ColorOrder, Input::Next) => {
+                let color_order = match display.color_order() {
+                    ColorOrder::Rgb => ColorOrder::Bgr,
+                    ColorOrder::Bgr => ColorOrder::Rgb,
+                };
+
+                display.set_color_order(color_order)?;
+            }
+            (Step::ColorInversion, Input::Next) => {
+                let color_inversion = match display.invert_colors() {
+                    ColorInversion::Normal => ColorInversion::Inverted,
+                    ColorInversion::Inverted => ColorInversion::Normal,
+                };
+
+                display.set_invert_colors(color_inversion)?;
+            }
+            (Step::Orientation, Input::Confirm) => self.step = Step::ColorOrder,
+            (Step::ColorOrder, Input::Confirm) => self.step = Step::ColorInversion,
+            (Step::ColorInversion, Input::Confirm) => {
+                self.step = Step::Done;
+                return Ok(Some(display.options.clone()));
+            }
+        }
+
+        // Orientation changes can change the display size, so the whole test
+        // image is redrawn after every change.
+        TestImage::new().draw(display)?;
+
+        Ok(None)
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::_mock::new_mock_display;
+
+    use super::*;
+
+    #[test]
+    fn orientations_are_unique() {
+        for (i, a) in ORIENTATIONS.iter().enumerate() {
+            for b in &ORIENTATIONS[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn calibration_steps() {
+        let mut display = new_mock_display();
+        let mut calibration = Calibration::new();
+        calibration.start(&mut display).unwrap();
+
+        for input in [Input::Next, Input::Next, Input::Next, Input::Confirm] {
+            assert_eq!(calibration.step(), Step::Orientation);
+            assert!(calibration.handle(&mut display, input).unwrap().is_none());
+        }
+
+        for input in [Input::Next, Input::Confirm] {
+            assert_eq!(calibration.step(), Step::ColorOrder);
+            assert!(calibration.handle(&mut display, input).unwrap().is_none());
+        }
+
+        assert_eq!(calibration.step(), Step::ColorInversion);
+        let options = calibration
+            .handle(&mut display, Input::Confirm)
+            .unwrap()
+            .unwrap();
+        assert_eq!(calibration.step(), Step::Done);
+
+        assert_eq!(options.orientation, ORIENTATIONS[3]);
+        assert_eq!(options.color_order, ColorOrder::Bgr);
+        assert_eq!(options.invert_colors, ColorInversion::Normal);
+    }
+}
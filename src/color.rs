@@ -0,0 +1,152 @@
+//! Additional color types.
+//!
+//! [`Rgb111`] is the 3-bit color format of the DCS 3 bits per pixel mode. It only has 8
+//! colors, but sends 8 pixels in 3 bytes, which makes it useful for simple status displays
+//! on slow links. Colors from other formats can be converted with [`From`], each channel
+//! is rounded to on or off.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb888, prelude::*};
+//! use mipidsi::color::Rgb111;
+//!
+//! assert_eq!(Rgb111::from(Rgb888::new(255, 200, 10)), Rgb111::YELLOW);
+//! ```
+
+use embedded_graphics_core::pixelcolor::{
+    raw::{RawData, RawU4},
+    BinaryColor, PixelColor, Rgb565, Rgb666, Rgb888, RgbColor,
+};
+
+/// 3-bit RGB color with one bit per channel.
+///
+/// The raw data is stored as `0b0RGB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Rgb111(u8);
+
+impl Rgb111 {
+    /// Creates a new color.
+    ///
+    /// Only the lowest bit of each channel is used.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self((r & 1) << 2 | (g & 1) << 1 | (b & 1))
+    }
+
+    /// Returns the color packed into the lowest 3 bits.
+    pub(crate) const fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl PixelColor for Rgb111 {
+    type Raw = RawU4;
+}
+
+impl From<RawU4> for Rgb111 {
+    fn from(raw: RawU4) -> Self {
+        Self(raw.into_inner() & 0b111)
+    }
+}
+
+impl From<Rgb111> for RawU4 {
+    fn from(color: Rgb111) -> Self {
+        RawU4::new(color.0)
+    }
+}
+
+impl RgbColor for Rgb111 {
+    fn r(&self) -> u8 {
+        self.0 >> 2
+    }
+
+    fn g(&self) -> u8 {
+        (self.0 >> 1) & 1
+    }
+
+    fn b(&self) -> u8 {
+        self.0 & 1
+    }
+
+    const MAX_R: u8 = 1;
+    const MAX_G: u8 = 1;
+    const MAX_B: u8 = 1;
+
+    const BLACK: Self = Self::new(0, 0, 0);
+    const RED: Self = Self::new(1, 0, 0);
+    const GREEN: Self = Self::new(0, 1, 0);
+    const BLUE: Self = Self::new(0, 0, 1);
+    const YELLOW: Self = Self::new(1, 1, 0);
+    const MAGENTA: Self = Self::new(1, 0, 1);
+    const CYAN: Self = Self::new(0, 1, 1);
+    const WHITE: Self = Self::new(1, 1, 1);
+}
+
+// Rounds every channel to on or off.
+fn from_rgb<C: RgbColor>(color: C) -> Rgb111 {
+    let bit = |value: u8, max: u8| u8::from(value > max / 2);
+    Rgb111::new(
+        bit(color.r(), C::MAX_R),
+        bit(color.g(), C::MAX_G),
+        bit(color.b(), C::MAX_B),
+    )
+}
+
+macro_rules! impl_conversions {
+    ($($color:ty),*) => {
+        $(
+            impl From<$color> for Rgb111 {
+                fn from(color: $color) -> Self {
+                    from_rgb(color)
+                }
+            }
+
+            impl From<Rgb111> for $color {
+                fn from(color: Rgb111) -> Self {
+                    <$color>::new(
+                        color.r() * <$color>::MAX_R,
+                        color.g() * <$color>::MAX_G,
+                        color.b() * <$color>::MAX_B,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_conversions!(Rgb565, Rgb666, Rgb888);
+
+impl From<BinaryColor> for Rgb111 {
+    fn from(color: BinaryColor) -> Self {
+        if color.is_on() {
+            Self::WHITE
+        } else {
+            Self::BLACK
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels() {
+        let color = Rgb111::new(1, 0, 1);
+        assert_eq!((color.r(), color.g(), color.b()), (1, 0, 1));
+        assert_eq!(color, Rgb111::MAGENTA);
+        assert_eq!(RawU4::from(color).into_inner(), 0b101);
+    }
+
+    #[test]
+    fn conversions_round_channels() {
+        assert_eq!(
+            Rgb111::from(Rgb888::new(0x80, 0x7F, 0xFF)),
+            Rgb111::new(1, 0, 1)
+        );
+        assert_eq!(Rgb111::from(Rgb565::new(16, 31, 15)), Rgb111::new(1, 0, 0));
+        assert_eq!(Rgb111::from(BinaryColor::On), Rgb111::WHITE);
+        assert_eq!(Rgb888::from(Rgb111::CYAN), Rgb888::CYAN);
+        assert_eq!(Rgb666::from(Rgb111::RED), Rgb666::RED);
+    }
+}
@@ -0,0 +1,232 @@
+//! Compatibility shims for code written for `mipidsi` 0.7.
+//!
+//! This module adds deprecated versions of the old model specific constructors,
+//! the `with_*` builder methods and the old [`Orientation`] variants, which
+//! are implemented on top of the current API. They are intended to make it
+//! easier to update projects step by step: the old code keeps compiling and the
+//! deprecation warnings point to the replacement for each use.
+//!
+//! These shims can't cover all changes. In particular, the reset pin is no
+//! longer passed to `init`, but set with [`Builder::reset_pin`], and the old
+//! orientation variants can only be constructed, not matched on.
+//! See the [migration guide](https://github.com/almindor/mipidsi/blob/master/docs/MIGRATION.md)
+//! for all required changes.
+//!
+//! # Examples
+//!
+//! ```
+//! # #![allow(deprecated)]
+//! use mipidsi::{Builder, options::Orientation};
+//!
+//! # let di = mipidsi::_mock::MockDisplayInterface;
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! let display = Builder::st7789(di)
+//!     .with_display_size(240, 240)
+//!     .with_orientation(Orientation::LandscapeInverted(false))
+//!     .init(&mut delay)
+//!     .unwrap();
+//! ```
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::{
+        ILI9341Rgb565, ILI9341Rgb666, ILI9342CRgb565, ILI9342CRgb666, ILI9486Rgb565, ILI9486Rgb666,
+        Model, ST7735s, ST7789,
+    },
+    options::{ColorInversion, ColorOrder, Orientation, Rotation},
+    Builder, NoResetPin,
+};
+
+macro_rules! compat_constructor {
+    ($name:ident, $model:ident, $note:literal) => {
+        impl<DI> Builder<DI, $model, NoResetPin>
+        where
+            DI: Interface,
+            <$model as Model>::ColorFormat: InterfacePixelFormat<DI::Word>,
+        {
+            #[doc = concat!("Creates a new builder for the [`", stringify!($model), "`](crate::models::", stringify!($model), ") model.")]
+            #[deprecated(
+                since = "0.10.0",
+                note = $note
+            )]
+            #[must_use]
+            pub fn $name(di: DI) -> Self {
+                Self::new($model, di)
+            }
+        }
+    };
+}
+
+compat_constructor!(
+    st7789,
+    ST7789,
+    "use `Builder::new(models::ST7789, di)` instead"
+);
+compat_constructor!(
+    st7735s,
+    ST7735s,
+    "use `Builder::new(models::ST7735s, di)` instead"
+);
+compat_constructor!(
+    ili9341_rgb565,
+    ILI9341Rgb565,
+    "use `Builder::new(models::ILI9341Rgb565, di)` instead"
+);
+compat_constructor!(
+    ili9341_rgb666,
+    ILI9341Rgb666,
+    "use `Builder::new(models::ILI9341Rgb666, di)` instead"
+);
+compat_constructor!(
+    ili9342c_rgb565,
+    ILI9342CRgb565,
+    "use `Builder::new(models::ILI9342CRgb565, di)` instead"
+);
+compat_constructor!(
+    ili9342c_rgb666,
+    ILI9342CRgb666,
+    "use `Builder::new(models::ILI9342CRgb666, di)` instead"
+);
+compat_constructor!(
+    ili9486_rgb565,
+    ILI9486Rgb565,
+    "use `Builder::new(models::ILI9486Rgb565, di)` instead"
+);
+compat_constructor!(
+    ili9486_rgb666,
+    ILI9486Rgb666,
+    "use `Builder::new(models::ILI9486Rgb666, di)` instead"
+);
+
+impl<DI, MODEL, RST, BL> Builder<DI, MODEL, RST, BL>
+where
+    DI: Interface,
+    MODEL: Model,
+    MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Sets the display size.
+    #[deprecated(since = "0.10.0", note = "use `Builder::display_size` instead")]
+    #[must_use]
+    pub fn with_display_size(self, width: u16, height: u16) -> Self {
+        self.display_size(width, height)
+    }
+
+    /// Sets the [`Orientation`].
+    #[deprecated(since = "0.10.0", note = "use `Builder::orientation` instead")]
+    #[must_use]
+    pub fn with_orientation(self, orientation: Orientation) -> Self {
+        self.orientation(orientation)
+    }
+
+    /// Sets the invert color flag.
+    #[deprecated(since = "0.10.0", note = "use `Builder::invert_colors` instead")]
+    #[must_use]
+    pub fn with_invert_colors(self, color_inversion: ColorInversion) -> Self {
+        self.invert_colors(color_inversion)
+    }
+
+    /// Sets the [`ColorOrder`].
+    #[deprecated(since = "0.10.0", note = "use `Builder::color_order` instead")]
+    #[must_use]
+    pub fn with_color_order(self, color_order: ColorOrder) -> Self {
+        self.color_order(color_order)
+    }
+}
+
+/// Old orientation variants.
+#[allow(non_snake_case)]
+impl Orientation {
+    /// Portrait orientation, with an optional mirror image.
+    #[deprecated(
+        since = "0.10.0",
+        note = "use `Orientation { rotation: Rotation::Deg0, mirrored }` instead"
+    )]
+    pub const fn Portrait(mirrored: bool) -> Self {
+        compat_orientation(Rotation::Deg0, mirrored)
+    }
+
+    /// Landscape orientation, with an optional mirror image.
+    #[deprecated(
+        since = "0.10.0",
+        note = "use `Orientation { rotation: Rotation::Deg90, mirrored }` instead"
+    )]
+    pub const fn Landscape(mirrored: bool) -> Self {
+        compat_orientation(Rotation::Deg90, mirrored)
+    }
+
+    /// Inverted portrait orientation, with an optional mirror image.
+    #[deprecated(
+        since = "0.10.0",
+        note = "use `Orientation { rotation: Rotation::Deg180, mirrored }` instead"
+    )]
+    pub const fn PortraitInverted(mirrored: bool) -> Self {
+        compat_orientation(Rotation::Deg180, mirrored)
+    }
+
+    /// Inverted landscape orientation, with an optional mirror image.
+    #[deprecated(
+        since = "0.10.0",
+        note = "use `Orientation { rotation: Rotation::Deg270, mirrored }` instead"
+    )]
+    pub const fn LandscapeInverted(mirrored: bool) -> Self {
+        compat_orientation(Rotation::Deg270, mirrored)
+    }
+}
+
+const fn compat_orientation(rotation: Rotation, mirrored: bool) -> Orientation {
+    Orientation { rotation, mirrored }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use crate::_mock::{MockDelay, MockDisplayInterface};
+
+    use super::*;
+
+    #[test]
+    fn old_orientations() {
+        assert_eq!(Orientation::Portrait(false), Orientation::new());
+        assert_eq!(
+            Orientation::Landscape(false),
+            Orientation::new().rotate(Rotation::Deg90)
+        );
+        assert_eq!(
+            Orientation::PortraitInverted(true),
+            Orientation {
+                rotation: Rotation::Deg180,
+                mirrored: true
+            }
+        );
+        assert_eq!(
+            Orientation::LandscapeInverted(true),
+            Orientation {
+                rotation: Rotation::Deg270,
+                mirrored: true
+            }
+        );
+    }
+
+    #[test]
+    fn old_builder_methods() {
+        let display = Builder::ili9342c_rgb565(MockDisplayInterface)
+            .with_display_size(200, 100)
+            .with_orientation(Orientation::Landscape(false))
+            .with_color_order(ColorOrder::Bgr)
+            .with_invert_colors(ColorInversion::Inverted)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(
+            display.orientation(),
+            Orientation::new().rotate(Rotation::Deg90)
+        );
+        assert_eq!(display.color_order(), ColorOrder::Bgr);
+        assert_eq!(display.invert_colors(), ColorInversion::Inverted);
+    }
+}
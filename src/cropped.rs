@@ -0,0 +1,241 @@
+//! Cropped sub-displays.
+//!
+//! [`CroppedDisplay`] is a draw target for a rectangular area of the display. All drawing
+//! operations are translated by the top left corner of the area and clipped to it, so
+//! widget code can be drawn at different positions of the screen, e.g. in a split-screen
+//! UI, without recomputing the coordinates.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     primitives::{Circle, PrimitiveStyle, Rectangle},
+//! };
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! fn draw_widget<D: DrawTarget<Color = Rgb565>>(target: &mut D) -> Result<(), D::Error> {
+//!     target.clear(Rgb565::BLACK)?;
+//!     Circle::new(Point::new(10, 10), 100)
+//!         .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+//!         .draw(target)
+//! }
+//!
+//! // Draw the same widget in the left and in the right half of the display.
+//! draw_widget(&mut display.cropped(Rectangle::new(Point::zero(), Size::new(120, 320)))).unwrap();
+//! draw_widget(&mut display.cropped(Rectangle::new(Point::new(120, 0), Size::new(120, 320)))).unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Returns a draw target for a part of the display.
+    ///
+    /// The origin of the returned draw target is the top left corner of `area`, which is
+    /// given in display coordinates. Drawing outside of `area` is clipped, and a
+    /// [persistent window](Self::set_persistent_window) still restricts the drawing.
+    ///
+    /// See the [module documentation](crate::cropped) for more information.
+    pub fn cropped(&mut self, area: Rectangle) -> CroppedDisplay<'_, DI, M, RST, BL> {
+        CroppedDisplay {
+            display: self,
+            area,
+        }
+    }
+}
+
+/// Draw target for a part of the display.
+///
+/// See the [module documentation](self) for more information.
+pub struct CroppedDisplay<'a, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut Display<DI, M, RST, BL>,
+    area: Rectangle,
+}
+
+impl<DI, M, RST, BL> CroppedDisplay<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Returns the cropped area in display coordinates.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    // Runs a drawing operation on the display with the persistent window restricted to
+    // the cropped area, which reuses the clipping of the display.
+    fn clipped<R>(&mut self, f: impl FnOnce(&mut Display<DI, M, RST, BL>) -> R) -> R {
+        let window = self.display.persistent_window;
+        self.display.persistent_window = Some(match window {
+            Some(window) => window.intersection(&self.area),
+            None => self.area,
+        });
+
+        let result = f(self.display);
+
+        self.display.persistent_window = window;
+        result
+    }
+
+    // Converts a rectangle in cropped coordinates into display coordinates.
+    fn to_display(&self, area: &Rectangle) -> Rectangle {
+        Rectangle::new(area.top_left + self.area.top_left, area.size)
+    }
+}
+
+impl<DI, M, RST, BL> DrawTarget for CroppedDisplay<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let offset = self.area.top_left;
+        self.clipped(|display| {
+            display.draw_iter(
+                pixels
+                    .into_iter()
+                    .map(|Pixel(point, color)| Pixel(point + offset, color)),
+            )
+        })
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let area = self.to_display(area);
+        self.clipped(|display| display.fill_contiguous(&area, colors))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.to_display(area);
+        self.clipped(|display| display.fill_solid(&area, color))
+    }
+}
+
+impl<DI, M, RST, BL> OriginDimensions for CroppedDisplay<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{
+        geometry::Point,
+        pixelcolor::{Rgb565, RgbColor},
+    };
+
+    use crate::{_test::display, options::Orientation};
+
+    use super::*;
+
+    #[test]
+    fn drawing_is_translated() {
+        let mut display = display(Orientation::new());
+        let mut cropped = display.cropped(Rectangle::new(Point::new(100, 50), Size::new(40, 30)));
+        assert_eq!(cropped.size(), Size::new(40, 30));
+
+        cropped.clear(Rgb565::RED).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 100, 0, 139]);
+        assert_eq!(di.pages, [0, 50, 0, 79]);
+
+        display
+            .cropped(Rectangle::new(Point::new(100, 50), Size::new(40, 30)))
+            .draw_iter([Pixel(Point::new(5, 6), Rgb565::RED)])
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns[..2], [0, 105]);
+        assert_eq!(di.pages, [0, 56, 0, 56]);
+    }
+
+    #[test]
+    fn drawing_is_clipped() {
+        let mut display = display(Orientation::new());
+        let mut cropped = display.cropped(Rectangle::new(Point::new(100, 50), Size::new(40, 30)));
+
+        cropped
+            .fill_solid(
+                &Rectangle::new(Point::new(-10, 20), Size::new(100, 100)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 100, 0, 139]);
+        assert_eq!(di.pages, [0, 70, 0, 79]);
+
+        unsafe { display.dcs() }.windows = 0;
+        display
+            .cropped(Rectangle::new(Point::new(100, 50), Size::new(40, 30)))
+            .draw_iter([
+                Pixel(Point::new(-1, 0), Rgb565::RED),
+                Pixel(Point::new(40, 0), Rgb565::RED),
+                Pixel(Point::new(0, 30), Rgb565::RED),
+            ])
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.windows, 0);
+    }
+
+    #[test]
+    fn persistent_window_is_kept() {
+        let mut display = display(Orientation::new());
+        let window = Rectangle::new(Point::new(0, 0), Size::new(120, 240));
+        display.set_persistent_window(window);
+
+        display
+            .cropped(Rectangle::new(Point::new(100, 50), Size::new(40, 30)))
+            .clear(Rgb565::RED)
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 100, 0, 119]);
+        assert_eq!(display.persistent_window(), Some(window));
+    }
+}
@@ -21,6 +21,12 @@ mod set_tearing_effect;
 pub use set_tearing_effect::*;
 mod set_invert_mode;
 pub use set_invert_mode::*;
+mod set_partial_area;
+pub use set_partial_area::*;
+mod set_display_brightness;
+pub use set_display_brightness::*;
+mod write_ctrl_display;
+pub use write_ctrl_display::*;
 
 /// Common trait for DCS commands.
 ///
@@ -59,6 +65,7 @@ pub trait InterfaceExt: Interface {
     /// user command set. Use [`write_command`](Self::write_command) for commands in the user
     /// command set.
     fn write_raw(&mut self, instruction: u8, param_bytes: &[u8]) -> Result<(), Self::Error> {
+        crate::trace::command(instruction, param_bytes);
         self.send_command(instruction, param_bytes)
     }
 }
@@ -88,7 +88,7 @@ impl From<&ModelOptions> for SetAddressMode {
     fn from(options: &ModelOptions) -> Self {
         Self::default()
             .with_color_order(options.color_order)
-            .with_orientation(options.orientation)
+            .with_orientation(options.hardware_orientation())
             .with_refresh_order(options.refresh_order)
     }
 }
@@ -0,0 +1,38 @@
+use super::DcsCommand;
+
+/// Set Display Brightness (WRDISBV)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetDisplayBrightness(u8);
+
+impl SetDisplayBrightness {
+    /// Construct a new SetDisplayBrightness DCS with the given value
+    pub fn new(brightness: u8) -> Self {
+        SetDisplayBrightness(brightness)
+    }
+}
+
+impl DcsCommand for SetDisplayBrightness {
+    fn instruction(&self) -> u8 {
+        0x51
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0;
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_display_brightness_fills_param_properly() {
+        let sdb = SetDisplayBrightness(0x80);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(sdb.instruction(), 0x51);
+        assert_eq!(sdb.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x80]);
+    }
+}
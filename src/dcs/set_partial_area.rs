@@ -0,0 +1,51 @@
+//! Module for the PTLAR partial area instruction constructors
+
+use super::DcsCommand;
+
+/// Set Partial Area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetPartialArea {
+    start_row: u16,
+    end_row: u16,
+}
+
+impl SetPartialArea {
+    /// Creates a new Set Partial Area command.
+    ///
+    /// The start and end rows are inclusive.
+    pub const fn new(start_row: u16, end_row: u16) -> Self {
+        Self { start_row, end_row }
+    }
+}
+
+impl DcsCommand for SetPartialArea {
+    fn instruction(&self) -> u8 {
+        0x30
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let start_row = self.start_row.to_be_bytes();
+        let end_row = self.end_row.to_be_bytes();
+        buffer[0] = start_row[0];
+        buffer[1] = start_row[1];
+        buffer[2] = end_row[0];
+        buffer[3] = end_row[1];
+
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ptlar_fills_rows_properly() {
+        let ptlar = SetPartialArea::new(16, 300);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(ptlar.instruction(), 0x30);
+        assert_eq!(ptlar.fill_params_buf(&mut buffer), 4);
+        assert_eq!(buffer, [0x0, 0x10, 0x1, 0x2C]);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::options::BrightnessControl;
+
+use super::DcsCommand;
+
+/// Write CTRL Display (WRCTRLD)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteCtrlDisplay(BrightnessControl);
+
+impl WriteCtrlDisplay {
+    /// Construct a new WriteCtrlDisplay DCS with the given value
+    pub fn new(brightness_control: BrightnessControl) -> Self {
+        WriteCtrlDisplay(brightness_control)
+    }
+}
+
+impl DcsCommand for WriteCtrlDisplay {
+    fn instruction(&self) -> u8 {
+        0x53
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let BrightnessControl {
+            brightness_control,
+            dimming,
+            backlight,
+        } = self.0;
+
+        buffer[0] = (u8::from(brightness_control) << 5)
+            | (u8::from(dimming) << 3)
+            | (u8::from(backlight) << 2);
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_ctrl_display_fills_param_properly() {
+        let wcd = WriteCtrlDisplay(BrightnessControl {
+            brightness_control: true,
+            dimming: false,
+            backlight: true,
+        });
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wcd.instruction(), 0x53);
+        assert_eq!(wcd.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x24]);
+    }
+}
@@ -0,0 +1,349 @@
+//! Framebuffer with dirty rectangle tracking.
+//!
+//! [`Framebuffer`] keeps a copy of the display content in a user provided buffer. Drawing
+//! operations only change the buffer and record the changed areas, and
+//! [`Framebuffer::flush`] sends only the changed areas to the display. This greatly reduces
+//! the amount of data which is sent for user interfaces, where small widgets are redrawn
+//! over a static background, and makes it possible to draw overlapping elements without
+//! flicker.
+//!
+//! Up to `N` dirty rectangles are tracked. Rectangles which overlap or touch are merged,
+//! and if all slots are used a new rectangle is merged with the rectangle which grows the
+//! least. Pixels which are drawn with their current color aren't marked as dirty.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+//! use mipidsi::framebuffer::Framebuffer;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! let mut buffer = [Rgb565::BLACK; 240 * 320];
+//! let mut framebuffer = Framebuffer::<_, 8>::new(&mut buffer, Size::new(240, 320));
+//!
+//! let widget = Rectangle::new(Point::new(10, 10), Size::new(20, 10));
+//! framebuffer.fill_solid(&widget, Rgb565::RED).unwrap();
+//!
+//! // Only the 20x10 pixels of the widget are sent to the display
+//! framebuffer.flush(&mut display).unwrap();
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Rectangle with inclusive corner coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bounds {
+    min: (u32, u32),
+    max: (u32, u32),
+}
+
+impl Bounds {
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    fn area(self) -> u64 {
+        u64::from(self.max.0 - self.min.0 + 1) * u64::from(self.max.1 - self.min.1 + 1)
+    }
+
+    fn contains(self, other: Self) -> bool {
+        self.union(other) == self
+    }
+
+    // Returns `true` if the rectangles overlap or share an edge.
+    fn touches(self, other: Self) -> bool {
+        self.min.0 <= other.max.0 + 1
+            && other.min.0 <= self.max.0 + 1
+            && self.min.1 <= other.max.1 + 1
+            && other.min.1 <= self.max.1 + 1
+    }
+
+    fn rectangle(self) -> Rectangle {
+        Rectangle::with_corners(
+            Point::new(self.min.0 as i32, self.min.1 as i32),
+            Point::new(self.max.0 as i32, self.max.1 as i32),
+        )
+    }
+}
+
+/// Framebuffer which tracks up to `N` dirty rectangles.
+///
+/// See the [module documentation](self) for more information.
+pub struct Framebuffer<'a, C, const N: usize> {
+    buffer: &'a mut [C],
+    size: Size,
+    dirty: [Option<Bounds>; N],
+}
+
+impl<'a, C: PixelColor, const N: usize> Framebuffer<'a, C, N> {
+    /// Creates a new framebuffer with the given size, which stores the pixels in `buffer`.
+    ///
+    /// The size should be the size of the display. The initial content of the buffer is
+    /// assumed to be already shown on the display, use [`invalidate`](Self::invalidate) to
+    /// send the whole buffer with the next flush.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is smaller than `size.width * size.height` pixels or if `N` is
+    /// zero.
+    pub fn new(buffer: &'a mut [C], size: Size) -> Self {
+        assert!(N > 0);
+        assert!(buffer.len() >= size.width as usize * size.height as usize);
+
+        Self {
+            buffer,
+            size,
+            dirty: [None; N],
+        }
+    }
+
+    /// Returns the pixels of the framebuffer, in rows from top to bottom.
+    pub fn pixels(&self) -> &[C] {
+        &self.buffer[..self.size.width as usize * self.size.height as usize]
+    }
+
+    /// Returns `true` if the framebuffer contains changes which weren't flushed yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.iter().any(Option::is_some)
+    }
+
+    /// Returns the dirty rectangles.
+    pub fn dirty_rectangles(&self) -> impl Iterator<Item = Rectangle> + '_ {
+        self.dirty.iter().flatten().map(|bounds| bounds.rectangle())
+    }
+
+    /// Marks the whole framebuffer as dirty.
+    pub fn invalidate(&mut self) {
+        self.mark_dirty(Rectangle::new(Point::zero(), self.size));
+    }
+
+    /// Sends all dirty rectangles to the display.
+    ///
+    /// The dirty rectangles are only cleared if all of them were sent successfully.
+    pub fn flush<DI, M, RST, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model<ColorFormat = C>,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let width = self.size.width as usize;
+        for bounds in self.dirty.iter().flatten() {
+            let (x0, x1) = (bounds.min.0 as usize, bounds.max.0 as usize);
+            let rows = bounds.min.1 as usize..=bounds.max.1 as usize;
+            let pixels = rows.flat_map(|y| self.buffer[y * width + x0..=y * width + x1].iter());
+            display.fill_contiguous(&bounds.rectangle(), pixels.copied())?;
+        }
+
+        self.dirty = [None; N];
+        Ok(())
+    }
+
+    /// Adds an area to the dirty rectangles.
+    fn mark_dirty(&mut self, area: Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+
+        let mut bounds = Bounds {
+            min: (area.top_left.x as u32, area.top_left.y as u32),
+            max: (bottom_right.x as u32, bottom_right.y as u32),
+        };
+        if self
+            .dirty
+            .iter()
+            .flatten()
+            .any(|dirty| dirty.contains(bounds))
+        {
+            return;
+        }
+
+        // Merge all touching rectangles into the new rectangle
+        for slot in &mut self.dirty {
+            if let Some(dirty) = slot.filter(|dirty| dirty.touches(bounds)) {
+                bounds = bounds.union(dirty);
+                *slot = None;
+            }
+        }
+
+        if let Some(slot) = self.dirty.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(bounds);
+            return;
+        }
+
+        // All slots are used, merge with the rectangle which grows the least
+        let slot = self
+            .dirty
+            .iter_mut()
+            .flatten()
+            .min_by_key(|dirty| dirty.union(bounds).area() - dirty.area())
+            .unwrap();
+        *slot = slot.union(bounds);
+    }
+}
+
+impl<C: PixelColor, const N: usize> DrawTarget for Framebuffer<'_, C, N> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) {
+                if x < self.size.width && y < self.size.height {
+                    let pixel = &mut self.buffer[(y * self.size.width + x) as usize];
+                    if *pixel != color {
+                        *pixel = color;
+                        self.mark_dirty(Rectangle::new(point, Size::new(1, 1)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let width = self.size.width as usize;
+        let (x0, x1) = (area.top_left.x as usize, bottom_right.x as usize);
+        let mut changed = false;
+        for y in area.top_left.y as usize..=bottom_right.y as usize {
+            for pixel in &mut self.buffer[y * width + x0..=y * width + x1] {
+                changed |= *pixel != color;
+                *pixel = color;
+            }
+        }
+
+        if changed {
+            self.mark_dirty(area);
+        }
+        Ok(())
+    }
+}
+
+impl<C, const N: usize> OriginDimensions for Framebuffer<'_, C, N> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    fn touching_rectangles_are_merged() {
+        let mut buffer = [Rgb565::BLACK; 100];
+        let mut framebuffer = Framebuffer::<_, 4>::new(&mut buffer, Size::new(10, 10));
+        assert!(!framebuffer.is_dirty());
+
+        framebuffer
+            .fill_solid(&rect(0, 0, 2, 2), Rgb565::RED)
+            .unwrap();
+        framebuffer
+            .fill_solid(&rect(2, 0, 2, 2), Rgb565::RED)
+            .unwrap();
+        framebuffer
+            .fill_solid(&rect(6, 6, 8, 8), Rgb565::RED)
+            .unwrap();
+
+        let mut dirty = framebuffer.dirty_rectangles();
+        assert_eq!(dirty.next(), Some(rect(0, 0, 4, 2)));
+        assert_eq!(dirty.next(), Some(rect(6, 6, 4, 4)));
+        assert_eq!(dirty.next(), None);
+    }
+
+    #[test]
+    fn unchanged_pixels_are_not_dirty() {
+        let mut buffer = [Rgb565::BLACK; 100];
+        let mut framebuffer = Framebuffer::<_, 4>::new(&mut buffer, Size::new(10, 10));
+
+        framebuffer.clear(Rgb565::BLACK).unwrap();
+        framebuffer
+            .draw_iter([Pixel(Point::new(3, 4), Rgb565::BLACK)])
+            .unwrap();
+        assert!(!framebuffer.is_dirty());
+
+        framebuffer
+            .draw_iter([Pixel(Point::new(3, 4), Rgb565::WHITE)])
+            .unwrap();
+        assert_eq!(
+            framebuffer.dirty_rectangles().next(),
+            Some(rect(3, 4, 1, 1))
+        );
+    }
+
+    #[test]
+    fn rectangles_are_merged_if_all_slots_are_used() {
+        let mut buffer = [Rgb565::BLACK; 100];
+        let mut framebuffer = Framebuffer::<_, 2>::new(&mut buffer, Size::new(10, 10));
+
+        framebuffer
+            .fill_solid(&rect(0, 0, 1, 1), Rgb565::RED)
+            .unwrap();
+        framebuffer
+            .fill_solid(&rect(8, 8, 1, 1), Rgb565::RED)
+            .unwrap();
+        framebuffer
+            .fill_solid(&rect(0, 3, 1, 1), Rgb565::RED)
+            .unwrap();
+
+        let mut dirty = framebuffer.dirty_rectangles();
+        assert_eq!(dirty.next(), Some(rect(0, 0, 1, 4)));
+        assert_eq!(dirty.next(), Some(rect(8, 8, 1, 1)));
+        assert_eq!(dirty.next(), None);
+    }
+
+    #[test]
+    fn flush_clears_dirty_rectangles() {
+        let mut display = crate::_mock::new_mock_display();
+        let mut buffer = [Rgb565::BLACK; 240 * 320];
+        let mut framebuffer = Framebuffer::<_, 4>::new(&mut buffer, Size::new(240, 320));
+
+        framebuffer.invalidate();
+        assert_eq!(
+            framebuffer.dirty_rectangles().next(),
+            Some(rect(0, 0, 240, 320))
+        );
+
+        framebuffer.flush(&mut display).unwrap();
+        assert!(!framebuffer.is_dirty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Cache for rendered glyphs and other small images.
+//!
+//! Text rendered by `embedded-graphics` is drawn pixel by pixel, which is slow
+//! for most interfaces. [`GlyphCache`] stores the rendered pixels of each glyph
+//! in a user provided buffer once and afterwards draws them as a single
+//! contiguous block, using the same fast path as
+//! [`fill_contiguous`](embedded_graphics_core::draw_target::DrawTarget::fill_contiguous).
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     mono_font::{ascii::FONT_6X10, MonoTextStyle},
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     text::{Baseline, Text},
+//! };
+//! use mipidsi::glyph_cache::GlyphCache;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+//! let mut buffer = [Rgb565::BLACK; 6 * 10 * 16];
+//! let mut cache = GlyphCache::<_, char, 16>::new(&mut buffer);
+//!
+//! let mut position = Point::new(10, 10);
+//! for c in ['4', '2', '4'] {
+//!     if !cache.contains(c) {
+//!         cache
+//!             .insert(c, Size::new(6, 10), Rgb565::BLACK, |canvas| {
+//!                 let mut text = [0; 4];
+//!                 let text = c.encode_utf8(&mut text);
+//!                 Text::with_baseline(text, Point::zero(), style, Baseline::Top)
+//!                     .draw(canvas)
+//!                     .unwrap();
+//!             })
+//!             .unwrap();
+//!     }
+//!
+//!     cache.draw(&mut display, c, position).unwrap();
+//!     position.x += 6;
+//! }
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Error returned by [`GlyphCache::insert`] if the cache is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphCacheFull;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry<K> {
+    id: K,
+    offset: usize,
+    size: Size,
+}
+
+/// Cache for up to `N` rendered glyphs, keyed by glyph ids of type `K`.
+///
+/// See the [module documentation](self) for more information.
+pub struct GlyphCache<'a, C, K, const N: usize> {
+    buffer: &'a mut [C],
+    entries: [Option<Entry<K>>; N],
+    used: usize,
+}
+
+impl<'a, C, K, const N: usize> GlyphCache<'a, C, K, N>
+where
+    C: PixelColor,
+    K: Copy + PartialEq,
+{
+    /// Creates a new empty cache, which stores the glyph pixels in `buffer`.
+    pub fn new(buffer: &'a mut [C]) -> Self {
+        Self {
+            buffer,
+            entries: [None; N],
+            used: 0,
+        }
+    }
+
+    /// Returns `true` if the glyph with the given id is cached.
+    pub fn contains(&self, id: K) -> bool {
+        self.entry(id).is_some()
+    }
+
+    /// Renders a glyph and adds it to the cache.
+    ///
+    /// The glyph area is filled with `background` before `render` is called with a
+    /// [`GlyphCanvas`] of the given `size`. If a glyph with the same id is already
+    /// cached it is kept and the new glyph is stored in addition, but only the first
+    /// glyph will be drawn. Use [`contains`](Self::contains) to check for cached glyphs
+    /// and [`clear`](Self::clear) to remove all glyphs.
+    pub fn insert<F>(
+        &mut self,
+        id: K,
+        size: Size,
+        background: C,
+        render: F,
+    ) -> Result<(), GlyphCacheFull>
+    where
+        F: FnOnce(&mut GlyphCanvas<'_, C>),
+    {
+        let len = size.width as usize * size.height as usize;
+        let slot = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.is_none())
+            .ok_or(GlyphCacheFull)?;
+        let pixels = self
+            .buffer
+            .get_mut(self.used..self.used + len)
+            .ok_or(GlyphCacheFull)?;
+
+        pixels.fill(background);
+        render(&mut GlyphCanvas { pixels, size });
+
+        *slot = Some(Entry {
+            id,
+            offset: self.used,
+            size,
+        });
+        self.used += len;
+
+        Ok(())
+    }
+
+    /// Draws a cached glyph with its top left corner at `position`.
+    ///
+    /// Returns `false` if the glyph isn't cached.
+    pub fn draw<DI, M, RST, BL>(
+        &self,
+        display: &mut Display<DI, M, RST, BL>,
+        id: K,
+        position: Point,
+    ) -> Result<bool, DI::Error>
+    where
+        DI: Interface,
+        M: Model<ColorFormat = C>,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let Some(entry) = self.entry(id) else {
+            return Ok(false);
+        };
+
+        let len = entry.size.width as usize * entry.size.height as usize;
+        let pixels = &self.buffer[entry.offset..entry.offset + len];
+        display.fill_contiguous(
+            &Rectangle::new(position, entry.size),
+            pixels.iter().copied(),
+        )?;
+
+        Ok(true)
+    }
+
+    /// Removes all glyphs from the cache.
+    pub fn clear(&mut self) {
+        self.entries = [None; N];
+        self.used = 0;
+    }
+
+    fn entry(&self, id: K) -> Option<&Entry<K>> {
+        self.entries.iter().flatten().find(|entry| entry.id == id)
+    }
+}
+
+/// Draw target used to render glyphs into a [`GlyphCache`].
+pub struct GlyphCanvas<'a, C> {
+    pixels: &'a mut [C],
+    size: Size,
+}
+
+impl<C: PixelColor> DrawTarget for GlyphCanvas<'_, C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) {
+                if x < self.size.width && y < self.size.height {
+                    self.pixels[(y * self.size.width + x) as usize] = color;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> OriginDimensions for GlyphCanvas<'_, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use super::*;
+
+    fn render_diagonal(canvas: &mut GlyphCanvas<'_, Rgb565>) {
+        canvas
+            .draw_iter((-1..4).map(|i| Pixel(Point::new(i, i), Rgb565::WHITE)))
+            .unwrap();
+    }
+
+    #[test]
+    fn insert_and_draw() {
+        let mut display = crate::_mock::new_mock_display();
+        let mut buffer = [Rgb565::RED; 9];
+        let mut cache = GlyphCache::<_, u8, 2>::new(&mut buffer);
+
+        assert!(!cache.contains(1));
+        assert!(!cache.draw(&mut display, 1, Point::zero()).unwrap());
+
+        cache
+            .insert(1, Size::new(3, 3), Rgb565::BLACK, render_diagonal)
+            .unwrap();
+        assert!(cache.contains(1));
+        assert!(cache.draw(&mut display, 1, Point::new(10, 10)).unwrap());
+
+        assert_eq!(
+            buffer,
+            [
+                Rgb565::WHITE,
+                Rgb565::BLACK,
+                Rgb565::BLACK,
+                Rgb565::BLACK,
+                Rgb565::WHITE,
+                Rgb565::BLACK,
+                Rgb565::BLACK,
+                Rgb565::BLACK,
+                Rgb565::WHITE,
+            ]
+        );
+    }
+
+    #[test]
+    fn cache_full() {
+        let mut buffer = [Rgb565::BLACK; 10];
+        let mut cache = GlyphCache::<_, u8, 2>::new(&mut buffer);
+
+        let render = |_: &mut GlyphCanvas<'_, Rgb565>| {};
+        assert_eq!(
+            cache.insert(1, Size::new(2, 4), Rgb565::BLACK, render),
+            Ok(())
+        );
+        assert_eq!(
+            cache.insert(2, Size::new(2, 2), Rgb565::BLACK, render),
+            Err(GlyphCacheFull)
+        );
+        assert_eq!(
+            cache.insert(2, Size::new(2, 1), Rgb565::BLACK, render),
+            Ok(())
+        );
+        assert_eq!(
+            cache.insert(3, Size::new(1, 0), Rgb565::BLACK, render),
+            Err(GlyphCacheFull)
+        );
+
+        cache.clear();
+        assert!(!cache.contains(1));
+        assert_eq!(
+            cache.insert(3, Size::new(2, 5), Rgb565::BLACK, render),
+            Ok(())
+        );
+    }
+}
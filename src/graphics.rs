@@ -7,17 +7,18 @@ use embedded_graphics_core::{
 };
 use embedded_hal::digital::OutputPin;
 
-use crate::dcs::InterfaceExt;
+use crate::backlight::Backlight;
+use crate::models::Model;
 use crate::{dcs::BitsPerPixel, interface::Interface};
-use crate::{dcs::WriteMemoryStart, models::Model};
 use crate::{interface::InterfacePixelFormat, Display};
 
-impl<DI, M, RST> DrawTarget for Display<DI, M, RST>
+impl<DI, M, RST, BL> DrawTarget for Display<DI, M, RST, BL>
 where
     DI: Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
 {
     type Error = DI::Error;
     type Color = M::ColorFormat;
@@ -27,11 +28,16 @@ where
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
+        let window = self.persistent_window;
+        let pixels = pixels
+            .into_iter()
+            .filter(|Pixel(point, _)| window.map_or(true, |window| window.contains(*point)));
+
         for pixel in pixels {
             let x = pixel.0.x as u16;
             let y = pixel.0.y as u16;
 
-            self.set_pixel(x, y, pixel.1)?;
+            self.draw_pixels(x, y, x, y, core::iter::once(pixel.1))?;
         }
 
         Ok(())
@@ -44,14 +50,18 @@ where
     {
         use crate::batch::DrawBatch;
 
-        self.draw_batch(item)
+        let window = self.persistent_window;
+        self.draw_batch(
+            item.into_iter()
+                .filter(|Pixel(point, _)| window.map_or(true, |window| window.contains(*point))),
+        )
     }
 
     fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Self::Color>,
     {
-        let intersection = area.intersection(&self.bounding_box());
+        let intersection = area.intersection(&self.drawable_area());
         let Some(bottom_right) = intersection.bottom_right() else {
             // No intersection -> nothing to draw
             return Ok(());
@@ -70,7 +80,7 @@ where
 
         if &intersection == area {
             // Draw the original iterator if no edge overlaps the framebuffer
-            self.set_pixels(sx, sy, ex, ey, take_u32(colors, count))
+            self.draw_pixels(sx, sy, ex, ey, take_u32(colors, count))
         } else {
             // Skip pixels above and to the left of the intersection
             let mut initial_skip = 0;
@@ -87,7 +97,7 @@ where
             // Draw only the pixels which don't overlap the edges of the framebuffer
             let take_per_row = intersection.size.width;
             let skip_per_row = area.size.width - intersection.size.width;
-            self.set_pixels(
+            self.draw_pixels(
                 sx,
                 sy,
                 ex,
@@ -98,31 +108,50 @@ where
     }
 
     fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-        let area = area.intersection(&self.bounding_box());
+        let area = area.intersection(&self.drawable_area());
         let Some(bottom_right) = area.bottom_right() else {
             // No intersection -> nothing to draw
             return Ok(());
         };
 
-        let count = area.size.width * area.size.height;
+        let Some((sx, sy, ex, ey)) = self.offset_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        ) else {
+            return Ok(());
+        };
 
-        let sx = area.top_left.x as u16;
-        let sy = area.top_left.y as u16;
-        let ex = bottom_right.x as u16;
-        let ey = bottom_right.y as u16;
+        self.fill_raw(sx, sy, ex, ey, color)
+    }
+}
 
-        self.set_address_window(sx, sy, ex, ey)?;
-        self.di.write_command(WriteMemoryStart)?;
-        M::ColorFormat::send_repeated_pixel(&mut self.di, color, count)
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    // Returns the area drawing operations are restricted to.
+    pub(crate) fn drawable_area(&self) -> Rectangle {
+        let bounding_box = self.bounding_box();
+        match self.persistent_window {
+            Some(window) => window.intersection(&bounding_box),
+            None => bounding_box,
+        }
     }
 }
 
-impl<DI, MODEL, RST> OriginDimensions for Display<DI, MODEL, RST>
+impl<DI, MODEL, RST, BL> OriginDimensions for Display<DI, MODEL, RST, BL>
 where
     DI: Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
 {
     fn size(&self) -> Size {
         let ds = self.options.display_size();
@@ -212,11 +241,281 @@ fn nth_u32<I: Iterator>(mut iter: I, n: u32) -> Option<I::Item> {
 
 #[cfg(test)]
 mod test {
-    use crate::dcs::BitsPerPixel;
-    use embedded_graphics_core::pixelcolor::*;
+    use core::convert::Infallible;
+
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface},
+        _test::RecordingInterface,
+        dcs::BitsPerPixel,
+        interface::{Event, Interface, InterfaceKind, TracingInterface},
+        models::{ILI9341Rgb565, ILI9488Rgb111},
+        options::{ColorInversion, Mounting, Orientation, Rotation},
+        Builder, SetOrientationError, SetPixelsError,
+    };
+    use embedded_graphics_core::{
+        draw_target::DrawTarget,
+        geometry::{Point, Size},
+        pixelcolor::*,
+        primitives::Rectangle,
+        Pixel,
+    };
 
     use super::TakeSkip;
 
+    #[test]
+    fn set_pixels_checked_detects_count_mismatch() {
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+
+        let colors = core::iter::repeat(Rgb565::RED);
+        display
+            .set_pixels_checked(0, 0, 9, 1, colors.clone().take(20))
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.pixels, 20);
+
+        assert!(matches!(
+            display.set_pixels_checked(0, 0, 9, 1, colors.clone().take(19)),
+            Err(SetPixelsError::PixelCountMismatch)
+        ));
+        assert_eq!(unsafe { display.dcs() }.pixels, 20 + 19);
+
+        // Surplus colors aren't drawn
+        assert!(matches!(
+            display.set_pixels_checked(0, 0, 9, 1, colors.take(21)),
+            Err(SetPixelsError::PixelCountMismatch)
+        ));
+        assert_eq!(unsafe { display.dcs() }.pixels, 20 + 19 + 20);
+    }
+
+    #[test]
+    fn orientation_with_invalid_offset_is_rejected() {
+        // The correction moves the area back to the first column, which only works if the
+        // columns aren't reversed.
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::default())
+            .display_size(230, 320)
+            .display_offset(10, 0)
+            .mounting(Mounting::new().offset(-10, 0))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert!(matches!(
+            display.set_orientation(Orientation::new().flip_horizontal()),
+            Err(SetOrientationError::InvalidOffset)
+        ));
+        assert_eq!(display.orientation(), Orientation::new());
+
+        display.set_pixel(0, 0, Rgb565::RED).unwrap();
+        assert_eq!(unsafe { display.dcs() }.columns, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn invert_colors_can_be_changed() {
+        let mut last_command = 0;
+        let di = TracingInterface::new(MockDisplayInterface, |command, _| last_command = command);
+        let mut display = Builder::new(ILI9341Rgb565, di)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.invert_colors(), ColorInversion::Normal);
+
+        display.set_invert_colors(ColorInversion::Inverted).unwrap();
+        assert_eq!(display.invert_colors(), ColorInversion::Inverted);
+
+        let _ = display.release();
+        assert_eq!(last_command, 0x21);
+    }
+
+    #[test]
+    fn window_outside_address_space_is_rejected() {
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::default())
+            .display_size(240, 240)
+            .display_offset(0, 80)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert!(matches!(
+            display.set_pixel(0, u16::MAX - 10, Rgb565::RED),
+            Err(SetPixelsError::InvalidWindow)
+        ));
+        assert!(matches!(
+            display.set_pixels_from_buffer(0, 0, 0, u16::MAX - 79, &[0; 2]),
+            Err(SetPixelsError::InvalidWindow)
+        ));
+        assert_eq!(unsafe { display.dcs() }.pixels, 0);
+
+        display.set_pixel(0, 239, Rgb565::RED).unwrap();
+        assert_eq!(unsafe { display.dcs() }.pages, [1, 63, 1, 63]);
+    }
+
+    #[test]
+    fn persistent_window_clips_drawing() {
+        let mut display = Builder::new(ILI9341Rgb565, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_persistent_window(Rectangle::new(Point::new(10, 20), Size::new(20, 5)));
+
+        display.clear(Rgb565::BLACK).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pixels, 100);
+        assert_eq!(di.columns, [0, 10, 0, 29]);
+        assert_eq!(di.pages, [0, 20, 0, 24]);
+        di.pixels = 0;
+
+        let area = Rectangle::new(Point::new(0, 0), Size::new(15, 22));
+        let colors = core::iter::repeat(Rgb565::RED).take(15 * 22);
+        display.fill_contiguous(&area, colors).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pixels, 5 * 2);
+        assert_eq!(di.columns, [0, 10, 0, 14]);
+        assert_eq!(di.pages, [0, 20, 0, 21]);
+        di.pixels = 0;
+
+        display
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb565::RED),
+                Pixel(Point::new(10, 20), Rgb565::RED),
+            ])
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.pixels, 1);
+
+        display.clear_persistent_window();
+        display.clear(Rgb565::BLACK).unwrap();
+        assert_eq!(unsafe { display.dcs() }.pixels, 1 + 240 * 320);
+    }
+
+    /// Interface which records the grouped updates and memory writes.
+    #[derive(Default)]
+    struct UpdateInterface {
+        events: heapless::Vec<&'static str, 16>,
+    }
+
+    impl Interface for UpdateInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            if command == 0x2C {
+                self.events.push("write").unwrap();
+            }
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            pixels.into_iter().for_each(drop);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+            match event {
+                Event::BeginUpdate => self.events.push("begin").unwrap(),
+                Event::EndUpdate => self.events.push("end").unwrap(),
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transaction_groups_draws() {
+        let mut display = Builder::new(ILI9341Rgb565, UpdateInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        let area = Rectangle::new(Point::new(0, 0), Size::new(10, 10));
+
+        display
+            .transaction(|display| {
+                display.fill_solid(&area, Rgb565::RED)?;
+                // Nested transactions are part of the outer update.
+                display.transaction(|display| display.fill_solid(&area, Rgb565::BLUE))
+            })
+            .unwrap();
+        display.fill_solid(&area, Rgb565::RED).unwrap();
+
+        assert_eq!(
+            unsafe { display.dcs() }.events,
+            ["begin", "write", "write", "end", "write"]
+        );
+    }
+
+    #[test]
+    fn clear_raw_fills_framebuffer() {
+        let mut display = crate::_test::display(Orientation::new().rotate(Rotation::Deg90));
+
+        display.clear_raw(Rgb565::BLACK).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!((di.windows, di.pixels), (1, 320 * 240));
+        assert_eq!(di.columns, [0, 0, 0x01, 0x3F]);
+        assert_eq!(di.pages, [0, 0, 0, 239]);
+
+        // The previous address window is restored
+        display.set_pixel(10, 20, Rgb565::RED).unwrap();
+        display.clear_raw(Rgb565::BLACK).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, 4);
+        assert_eq!(di.columns, [0, 10, 0, 10]);
+        assert_eq!(di.pages, [0, 20, 0, 20]);
+    }
+
+    #[test]
+    fn write_raw_window_checks_length() {
+        let mut display = crate::_test::display(Orientation::new());
+
+        display
+            .write_raw_window(10, 20, 12, 21, &[0; 6 * 2])
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, 1);
+        assert_eq!(di.columns, [0, 10, 0, 12]);
+        assert_eq!(di.pages, [0, 20, 0, 21]);
+
+        // Nothing is sent if the length doesn't match
+        for len in [11, 14] {
+            assert!(matches!(
+                display.write_raw_window(0, 0, 2, 1, &[0; 14][..len]),
+                Err(SetPixelsError::PixelCountMismatch)
+            ));
+        }
+        assert_eq!(unsafe { display.dcs() }.windows, 1);
+
+        // Nothing is sent if the end coordinates are smaller than the start coordinates
+        for (ex, ey) in [(1, 2), (3, 1)] {
+            assert!(matches!(
+                display.write_raw_window(2, 2, ex, ey, &[]),
+                Err(SetPixelsError::InvalidWindow)
+            ));
+        }
+        assert_eq!(unsafe { display.dcs() }.windows, 1);
+    }
+
+    #[test]
+    fn write_raw_window_sends_incomplete_rgb111_group() {
+        let mut display = Builder::new(ILI9488Rgb111, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+
+        // 5 pixels are packed into 2 bytes, which don't form a whole group of 8 pixels
+        display.write_raw_window(0, 0, 4, 0, &[0xE0, 0xA7]).unwrap();
+        assert_eq!(unsafe { display.dcs() }.data, [0xE0, 0xA7]);
+
+        display
+            .write_raw_window(0, 0, 9, 0, &[0xE0, 0xA7, 0x2E, 0xFF])
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.data, [0xE0, 0xA7, 0x2E, 0xFF]);
+    }
+
     #[test]
     fn bpp_from_rgb_color_works() {
         assert_eq!(
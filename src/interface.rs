@@ -1,80 +1,130 @@
 //! Interface traits and implementations
 
 mod spi;
-use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666, RgbColor};
+use embedded_graphics_core::pixelcolor::{IntoStorage, Rgb565, Rgb666, RgbColor};
 pub use spi::*;
 
+use crate::{color::Rgb111, options::Endianness};
+
+mod i2c;
+pub use i2c::*;
+
 mod parallel;
 pub use parallel::*;
 
-/// Command and pixel interface
-pub trait Interface {
-    /// The native width of the interface
-    ///
-    /// In most cases this will be u8, except for larger parallel interfaces such as
-    /// 16 bit (currently supported)
-    /// or 9 or 18 bit (currently unsupported)
-    type Word: Copy;
-
-    /// Error type
-    type Error: core::fmt::Debug;
-
-    /// Send a command with optional parameters
-    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error>;
-
-    /// Send a sequence of pixels
-    ///
-    /// `WriteMemoryStart` must be sent before calling this function
-    fn send_pixels<const N: usize>(
-        &mut self,
-        pixels: impl IntoIterator<Item = [Self::Word; N]>,
-    ) -> Result<(), Self::Error>;
-
-    /// Send the same pixel value multiple times
-    ///
-    /// `WriteMemoryStart` must be sent before calling this function
-    fn send_repeated_pixel<const N: usize>(
-        &mut self,
-        pixel: [Self::Word; N],
-        count: u32,
-    ) -> Result<(), Self::Error>;
-}
+mod shift_register;
+pub use shift_register::*;
 
-impl<T: Interface> Interface for &mut T {
-    type Word = T::Word;
-    type Error = T::Error;
+mod trace;
+pub use trace::*;
 
-    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
-        T::send_command(self, command, args)
-    }
+mod dual;
+pub use dual::*;
 
-    fn send_pixels<const N: usize>(
-        &mut self,
-        pixels: impl IntoIterator<Item = [Self::Word; N]>,
-    ) -> Result<(), Self::Error> {
-        T::send_pixels(self, pixels)
-    }
+mod reclock;
+pub use reclock::*;
 
-    fn send_repeated_pixel<const N: usize>(
-        &mut self,
-        pixel: [Self::Word; N],
-        count: u32,
-    ) -> Result<(), Self::Error> {
-        T::send_repeated_pixel(self, pixel, count)
-    }
-}
+mod tee;
+pub use tee::*;
+
+mod retry;
+pub use retry::*;
+
+mod throttle;
+pub use throttle::*;
+
+mod limit;
+pub use limit::*;
+
+#[cfg(feature = "std")]
+mod capture;
+#[cfg(feature = "std")]
+pub use capture::*;
+
+#[cfg(feature = "std")]
+mod serial_bridge;
+#[cfg(feature = "std")]
+pub use serial_bridge::*;
 
-fn rgb565_to_bytes(pixel: Rgb565) -> [u8; 2] {
-    embedded_graphics_core::pixelcolor::raw::ToBytes::to_be_bytes(pixel)
+#[cfg(all(feature = "spidev", target_os = "linux"))]
+mod spidev;
+#[cfg(all(feature = "spidev", target_os = "linux"))]
+pub use self::spidev::*;
+
+#[cfg(feature = "display-interface")]
+mod display_interface;
+#[cfg(feature = "display-interface")]
+pub use self::display_interface::*;
+
+pub use mipidsi_interface_core::{
+    Error, ErrorKind, Event, Interface, InterfaceKind, InterfaceRead, SpeedProfile,
+};
+
+fn rgb565_to_bytes(pixel: Rgb565, endianness: Endianness) -> [u8; 2] {
+    match endianness {
+        Endianness::Big => embedded_graphics_core::pixelcolor::raw::ToBytes::to_be_bytes(pixel),
+        Endianness::Little => embedded_graphics_core::pixelcolor::raw::ToBytes::to_le_bytes(pixel),
+    }
 }
-fn rgb565_to_u16(pixel: Rgb565) -> [u16; 1] {
-    [u16::from_ne_bytes(
-        embedded_graphics_core::pixelcolor::raw::ToBytes::to_ne_bytes(pixel),
-    )]
+fn rgb565_to_u16(pixel: Rgb565, endianness: Endianness) -> [u16; 1] {
+    match endianness {
+        Endianness::Big => [pixel.into_storage()],
+        Endianness::Little => [pixel.into_storage().swap_bytes()],
+    }
 }
 fn rgb666_to_bytes(pixel: Rgb666) -> [u8; 3] {
     [pixel.r(), pixel.g(), pixel.b()].map(|x| x << 2)
 }
+// 16-bit bus with two transfers per pixel: R and G in the first transfer, B in the
+// second transfer, with each channel in the upper 6 bits of a byte.
+fn rgb666_to_u16(pixel: Rgb666) -> [u16; 2] {
+    [
+        (u16::from(pixel.r()) << 10) | (u16::from(pixel.g()) << 2),
+        u16::from(pixel.b()) << 10,
+    ]
+}
+fn rgb666_to_u32(pixel: Rgb666) -> [u32; 1] {
+    [(u32::from(pixel.r()) << 12) | (u32::from(pixel.g()) << 6) | u32::from(pixel.b())]
+}
+
+// 3-bit pixels are packed MSB first, with 8 pixels in 3 bytes. If the number of pixels isn't
+// a multiple of 8, the last byte is padded with zero bits.
+fn rgb111_to_bytes(pixels: impl IntoIterator<Item = Rgb111>) -> impl Iterator<Item = [u8; 1]> {
+    let mut pixels = pixels.into_iter();
+    let mut bits = 0u16;
+    let mut len = 0;
+
+    core::iter::from_fn(move || {
+        while len < 8 {
+            let Some(pixel) = pixels.next() else {
+                break;
+            };
+            bits = (bits << 3) | u16::from(pixel.bits());
+            len += 3;
+        }
+
+        match len {
+            0 => None,
+            1..=7 => {
+                let byte = (bits << (8 - len)) as u8;
+                len = 0;
+                Some([byte])
+            }
+            _ => {
+                len -= 8;
+                let byte = (bits >> len) as u8;
+                bits &= (1 << len) - 1;
+                Some([byte])
+            }
+        }
+    })
+}
+// A group of 8 pixels with the same color.
+fn rgb111_to_group(pixel: Rgb111) -> [u8; 3] {
+    let bits = (0..8).fold(0u32, |bits, _| (bits << 3) | u32::from(pixel.bits()));
+    let [_, bytes @ ..] = bits.to_be_bytes();
+    bytes
+}
 
 /// This is an implementation detail, it should not be implemented or used outside this crate
 pub trait InterfacePixelFormat<Word> {
@@ -83,10 +133,13 @@ pub trait InterfacePixelFormat<Word> {
     // fn convert(self) -> [Word; Self::N];
     // but that doesn't work yet
 
+    // The byte order option of the display only affects 16-bit pixels.
+
     #[doc(hidden)]
     fn send_pixels<DI: Interface<Word = Word>>(
         di: &mut DI,
         pixels: impl IntoIterator<Item = Self>,
+        endianness: Endianness,
     ) -> Result<(), DI::Error>;
 
     #[doc(hidden)]
@@ -94,6 +147,34 @@ pub trait InterfacePixelFormat<Word> {
         di: &mut DI,
         pixel: Self,
         count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>;
+
+    #[doc(hidden)]
+    fn send_pixels_from_buffer<DI: Interface<Word = Word>>(
+        di: &mut DI,
+        data: &[Word],
+    ) -> Result<(), DI::Error>;
+
+    // Used by models which expand the colors to 18 bits on the interface.
+
+    #[doc(hidden)]
+    fn send_pixels_as_rgb666<DI: Interface<Word = Word>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error>;
+
+    #[doc(hidden)]
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = Word>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error>;
+
+    #[doc(hidden)]
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = Word>>(
+        di: &mut DI,
+        data: &[Word],
     ) -> Result<(), DI::Error>;
 }
 
@@ -101,16 +182,55 @@ impl InterfacePixelFormat<u8> for Rgb565 {
     fn send_pixels<DI: Interface<Word = u8>>(
         di: &mut DI,
         pixels: impl IntoIterator<Item = Self>,
+        endianness: Endianness,
     ) -> Result<(), DI::Error> {
-        di.send_pixels(pixels.into_iter().map(rgb565_to_bytes))
+        di.send_pixels(
+            pixels
+                .into_iter()
+                .map(|pixel| rgb565_to_bytes(pixel, endianness)),
+        )
     }
 
     fn send_repeated_pixel<DI: Interface<Word = u8>>(
         di: &mut DI,
         pixel: Self,
         count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb565_to_bytes(pixel, endianness), count)
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<2>(data)
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(
+            pixels
+                .into_iter()
+                .map(|pixel| rgb666_to_bytes(pixel.into())),
+        )
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
     ) -> Result<(), DI::Error> {
-        di.send_repeated_pixel(rgb565_to_bytes(pixel), count)
+        di.send_repeated_pixel(rgb666_to_bytes(pixel.into()), count)
+    }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<3>(data)
     }
 }
 
@@ -118,6 +238,7 @@ impl InterfacePixelFormat<u8> for Rgb666 {
     fn send_pixels<DI: Interface<Word = u8>>(
         di: &mut DI,
         pixels: impl IntoIterator<Item = Self>,
+        _endianness: Endianness,
     ) -> Result<(), DI::Error> {
         di.send_pixels(pixels.into_iter().map(rgb666_to_bytes))
     }
@@ -126,24 +247,300 @@ impl InterfacePixelFormat<u8> for Rgb666 {
         di: &mut DI,
         pixel: Self,
         count: u32,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_bytes(pixel), count)
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<3>(data)
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(rgb666_to_bytes))
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
     ) -> Result<(), DI::Error> {
         di.send_repeated_pixel(rgb666_to_bytes(pixel), count)
     }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<3>(data)
+    }
 }
 
 impl InterfacePixelFormat<u16> for Rgb565 {
     fn send_pixels<DI: Interface<Word = u16>>(
         di: &mut DI,
         pixels: impl IntoIterator<Item = Self>,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(
+            pixels
+                .into_iter()
+                .map(|pixel| rgb565_to_u16(pixel, endianness)),
+        )
+    }
+
+    fn send_repeated_pixel<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb565_to_u16(pixel, endianness), count)
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        data: &[u16],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<1>(data)
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(|pixel| rgb666_to_u16(pixel.into())))
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_u16(pixel.into()), count)
+    }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        data: &[u16],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<2>(data)
+    }
+}
+
+impl InterfacePixelFormat<u16> for Rgb666 {
+    fn send_pixels<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+        _endianness: Endianness,
     ) -> Result<(), DI::Error> {
-        di.send_pixels(pixels.into_iter().map(rgb565_to_u16))
+        di.send_pixels(pixels.into_iter().map(rgb666_to_u16))
     }
 
     fn send_repeated_pixel<DI: Interface<Word = u16>>(
         di: &mut DI,
         pixel: Self,
         count: u32,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_u16(pixel), count)
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        data: &[u16],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<2>(data)
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(rgb666_to_u16))
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_u16(pixel), count)
+    }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u16>>(
+        di: &mut DI,
+        data: &[u16],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<2>(data)
+    }
+}
+
+impl InterfacePixelFormat<u32> for Rgb666 {
+    fn send_pixels<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(rgb666_to_u32))
+    }
+
+    fn send_repeated_pixel<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_u32(pixel), count)
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        data: &[u32],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<1>(data)
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(pixels.into_iter().map(rgb666_to_u32))
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_u32(pixel), count)
+    }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u32>>(
+        di: &mut DI,
+        data: &[u32],
+    ) -> Result<(), DI::Error> {
+        di.send_pixels_from_buffer::<1>(data)
+    }
+}
+
+impl InterfacePixelFormat<u8> for Rgb111 {
+    fn send_pixels<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(rgb111_to_bytes(pixels))
+    }
+
+    fn send_repeated_pixel<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+        _endianness: Endianness,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb111_to_group(pixel), count / 8)?;
+        let rest = (count % 8) as usize;
+        if rest > 0 {
+            di.send_pixels(rgb111_to_bytes(core::iter::repeat(pixel).take(rest)))?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels_from_buffer<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
+    ) -> Result<(), DI::Error> {
+        // Whole groups of 8 pixels are sent from the buffer, the bytes of the last
+        // incomplete group are sent separately like in `send_repeated_pixel`.
+        let len = data.len() - data.len() % 3;
+        di.send_pixels_from_buffer::<3>(&data[..len])?;
+        if len < data.len() {
+            di.send_pixels(data[len..].iter().map(|&byte| [byte]))?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self>,
+    ) -> Result<(), DI::Error> {
+        di.send_pixels(
+            pixels
+                .into_iter()
+                .map(|pixel| rgb666_to_bytes(pixel.into())),
+        )
+    }
+
+    fn send_repeated_pixel_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        pixel: Self,
+        count: u32,
+    ) -> Result<(), DI::Error> {
+        di.send_repeated_pixel(rgb666_to_bytes(pixel.into()), count)
+    }
+
+    fn send_pixels_from_buffer_as_rgb666<DI: Interface<Word = u8>>(
+        di: &mut DI,
+        data: &[u8],
     ) -> Result<(), DI::Error> {
-        di.send_repeated_pixel(rgb565_to_u16(pixel), count)
+        di.send_pixels_from_buffer::<3>(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::Rgb666;
+
+    use super::*;
+
+    #[test]
+    fn little_endian_rgb565_is_swapped() {
+        let pixel = Rgb565::new(0x1F, 0, 0);
+        assert_eq!(rgb565_to_bytes(pixel, Endianness::Little), [0x00, 0xF8]);
+        assert_eq!(rgb565_to_u16(pixel, Endianness::Little), [0x00F8]);
+
+        assert_eq!(rgb565_to_bytes(pixel, Endianness::Big), [0xF8, 0x00]);
+        assert_eq!(rgb565_to_u16(pixel, Endianness::Big), [0xF800]);
+    }
+
+    #[test]
+    fn rgb666_to_16bit_words() {
+        assert_eq!(rgb666_to_u16(Rgb666::new(0x3F, 0, 0)), [0xFC00, 0x0000]);
+        assert_eq!(rgb666_to_u16(Rgb666::new(0, 0x3F, 0)), [0x00FC, 0x0000]);
+        assert_eq!(rgb666_to_u16(Rgb666::new(0, 0, 0x3F)), [0x0000, 0xFC00]);
+        assert_eq!(rgb666_to_u16(Rgb666::WHITE), [0xFCFC, 0xFC00]);
+    }
+
+    #[test]
+    fn rgb111_is_packed() {
+        let bytes = |pixels: &[Rgb111]| {
+            rgb111_to_bytes(pixels.iter().copied())
+                .map(|[byte]| byte)
+                .collect::<heapless::Vec<u8, 8>>()
+        };
+
+        // 0b111_000_001_010_011_100_101_110
+        let pixels =
+            [0b111, 0, 1, 2, 3, 4, 5, 6].map(|bits| Rgb111::new(bits >> 2, bits >> 1, bits));
+        assert_eq!(bytes(&pixels), [0xE0, 0xA7, 0x2E]);
+        assert_eq!(bytes(&pixels[..3]), [0xE0, 0x80]);
+        assert_eq!(bytes(&[]), []);
+        assert_eq!(rgb111_to_group(Rgb111::WHITE), [0xFF; 3]);
+        assert_eq!(rgb111_to_group(Rgb111::RED), [0x92, 0x49, 0x24]);
+    }
+
+    #[test]
+    fn rgb666_to_18bit_word() {
+        assert_eq!(rgb666_to_u32(Rgb666::new(0x3F, 0, 0)), [0x3F000]);
+        assert_eq!(rgb666_to_u32(Rgb666::new(0, 0x3F, 0)), [0x00FC0]);
+        assert_eq!(rgb666_to_u32(Rgb666::new(0, 0, 0x3F)), [0x0003F]);
+        assert_eq!(rgb666_to_u32(Rgb666::WHITE), [0x3FFFF]);
     }
 }
@@ -0,0 +1,467 @@
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+use std::{io, path::Path, vec, vec::Vec};
+
+use super::{Interface, InterfaceKind};
+
+/// Interface which reconstructs the framebuffer from the command stream
+///
+/// Instead of sending the commands to a display controller, this interface keeps a copy of
+/// the controller memory, which is updated by the address window, memory write and address
+/// mode commands. The content can be saved as a PNG image, which makes it possible to
+/// generate screenshots for documentation and regression tests from the real drawing code.
+///
+/// The image is stored in the native orientation of the controller, with the size of the
+/// framebuffer of the model. Pixel data is decoded according to the last `SetPixelFormat`
+/// command, 16 (RGB565) and 18 (RGB666) bits per pixel are supported. Color inversion and the
+/// color order bit aren't applied, because they only affect the physical panel.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::{interface::CaptureToImage, models::ST7789, Builder};
+///
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let di = CaptureToImage::new(240, 320);
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// display.clear(Rgb565::RED).unwrap();
+///
+/// let (di, _model, _rst, _bl) = display.release();
+/// di.save_png("screenshot.png").unwrap();
+/// ```
+pub struct CaptureToImage {
+    width: u16,
+    height: u16,
+    pixels: Vec<[u8; 3]>,
+    madctl: u8,
+    bytes_per_pixel: usize,
+    columns: (u16, u16),
+    pages: (u16, u16),
+    // Address of the next pixel of the current memory write
+    position: Option<(u16, u16)>,
+    // Bytes of an incomplete pixel
+    partial: [u8; 3],
+    partial_len: usize,
+}
+
+impl CaptureToImage {
+    /// Creates a new capture interface for a framebuffer with the given size.
+    ///
+    /// The size should be the `FRAMEBUFFER_SIZE` of the model. All pixels are initially black.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 3]; usize::from(width) * usize::from(height)],
+            madctl: 0,
+            bytes_per_pixel: 2,
+            columns: (0, width.saturating_sub(1)),
+            pages: (0, height.saturating_sub(1)),
+            position: None,
+            partial: [0; 3],
+            partial_len: 0,
+        }
+    }
+
+    /// Returns the size of the framebuffer.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the color of a pixel in native framebuffer coordinates as 8-bit RGB values.
+    ///
+    /// Returns `None` if the coordinates are outside the framebuffer.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<[u8; 3]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[usize::from(y) * usize::from(self.width) + usize::from(x)])
+    }
+
+    /// Encodes the framebuffer as a PNG image.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.pixels.len() * 3 + usize::from(self.height));
+        for row in self.pixels.chunks(usize::from(self.width).max(1)) {
+            raw.push(0); // no filter
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(u32::from(self.width).to_be_bytes());
+        header.extend(u32::from(self.height).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]); // 8-bit RGB, no interlacing
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Saves the framebuffer as a PNG image.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_png())
+    }
+
+    fn write_pixel(&mut self, bytes: [u8; 3]) {
+        let Some((column, page)) = self.position else {
+            return;
+        };
+
+        // Map the address to the native framebuffer coordinates
+        let (mut x, mut y) = if self.madctl & 0x20 != 0 {
+            (page, column)
+        } else {
+            (column, page)
+        };
+        if self.madctl & 0x40 != 0 {
+            x = self.width.saturating_sub(1).wrapping_sub(x);
+        }
+        if self.madctl & 0x80 != 0 {
+            y = self.height.saturating_sub(1).wrapping_sub(y);
+        }
+
+        if x < self.width && y < self.height {
+            let color = if self.bytes_per_pixel == 2 {
+                let value = u16::from_be_bytes([bytes[0], bytes[1]]);
+                [
+                    expand((value >> 11) as u8, 5),
+                    expand((value >> 5) as u8 & 0x3F, 6),
+                    expand(value as u8 & 0x1F, 5),
+                ]
+            } else {
+                bytes.map(|byte| expand(byte >> 2, 6))
+            };
+            self.pixels[usize::from(y) * usize::from(self.width) + usize::from(x)] = color;
+        }
+
+        // Advance to the next address, wrapping around at the end of the window
+        self.position = Some(if column < self.columns.1 {
+            (column + 1, page)
+        } else if page < self.pages.1 {
+            (self.columns.0, page + 1)
+        } else {
+            (self.columns.0, self.pages.0)
+        });
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        self.partial[self.partial_len] = byte;
+        self.partial_len += 1;
+        if self.partial_len == self.bytes_per_pixel {
+            self.partial_len = 0;
+            self.write_pixel(self.partial);
+        }
+    }
+}
+
+impl Interface for CaptureToImage {
+    type Word = u8;
+    type Error = core::convert::Infallible;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        let address = |args: &[u8]| match *args {
+            [s0, s1, e0, e1] => Some((u16::from_be_bytes([s0, s1]), u16::from_be_bytes([e0, e1]))),
+            _ => None,
+        };
+
+        self.position = None;
+        match (command, args) {
+            (0x2A, _) => self.columns = address(args).unwrap_or(self.columns),
+            (0x2B, _) => self.pages = address(args).unwrap_or(self.pages),
+            (0x2C, _) => {
+                self.position = Some((self.columns.0, self.pages.0));
+                self.partial_len = 0;
+            }
+            (0x36, &[madctl, ..]) => self.madctl = madctl,
+            (0x3A, &[format, ..]) => {
+                self.bytes_per_pixel = if format & 0x07 == 0b110 { 3 } else { 2 }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        for pixel in pixels {
+            for byte in pixel {
+                self.write_byte(byte);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels((0..count).map(|_| pixel))
+    }
+}
+
+/// Returns the regions which differ between two captured frames.
+///
+/// Compares the frames pixel by pixel and returns rectangles in native framebuffer
+/// coordinates, which together cover exactly the changed pixels. Changed pixels in the same
+/// columns of consecutive rows are merged into a single rectangle. An empty result means
+/// that the frames are identical, which makes this function useful for pixel-regression
+/// tests of drawing code against reference frames, e.g. decoded from a saved capture.
+///
+/// If the frames have different sizes, a single rectangle covering both frames is returned.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+/// use mipidsi::{interface::{diff_frames, CaptureToImage}, models::ST7789, Builder};
+///
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let reference = CaptureToImage::new(240, 320);
+///
+/// let mut display = Builder::new(ST7789, CaptureToImage::new(240, 320))
+///     .init(&mut delay)
+///     .unwrap();
+/// display.set_pixel(10, 20, Rgb565::RED).unwrap();
+/// let (frame, _model, _rst, _bl) = display.release();
+///
+/// assert_eq!(
+///     diff_frames(&reference, &frame),
+///     [Rectangle::new(Point::new(10, 20), Size::new(1, 1))]
+/// );
+/// ```
+pub fn diff_frames(a: &CaptureToImage, b: &CaptureToImage) -> Vec<Rectangle> {
+    if a.size() != b.size() {
+        let width = a.width.max(b.width);
+        let height = a.height.max(b.height);
+        return vec![Rectangle::new(
+            Point::zero(),
+            Size::new(width.into(), height.into()),
+        )];
+    }
+
+    let width = usize::from(a.width).max(1);
+    let mut regions = Vec::new();
+    // Regions which end in the previous row and can be extended by the current row
+    let mut open: Vec<Rectangle> = Vec::new();
+
+    let rows = a.pixels.chunks(width).zip(b.pixels.chunks(width));
+    for (y, (row_a, row_b)) in rows.enumerate() {
+        let mut next = Vec::new();
+        let mut x = 0;
+
+        while x < width {
+            if row_a[x] == row_b[x] {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < width && row_a[x] != row_b[x] {
+                x += 1;
+            }
+
+            let top_left = Point::new(start as i32, y as i32);
+            let run_width = (x - start) as u32;
+            let region = match open.iter().position(|region| {
+                region.top_left.x == top_left.x && region.size.width == run_width
+            }) {
+                Some(i) => {
+                    let mut region = open.swap_remove(i);
+                    region.size.height += 1;
+                    region
+                }
+                None => Rectangle::new(top_left, Size::new(run_width, 1)),
+            };
+            next.push(region);
+        }
+
+        regions.append(&mut open);
+        open = next;
+    }
+    regions.append(&mut open);
+
+    regions.sort_by_key(|region| (region.top_left.y, region.top_left.x));
+    regions
+}
+
+/// Expands a color channel with `bits` bits to 8 bits.
+fn expand(value: u8, bits: u32) -> u8 {
+    let value = u16::from(value);
+    ((value * 255 + (1 << (bits - 1))) / ((1 << bits) - 1)) as u8
+}
+
+/// Writes a PNG chunk.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps the data in a zlib stream with uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(u8::from(last));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{
+        pixelcolor::{Rgb565, Rgb666, RgbColor},
+        prelude::*,
+        primitives::Rectangle,
+    };
+
+    use crate::{
+        _mock::MockDelay,
+        models::{ILI9341Rgb666, ST7789},
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn pixels_are_stored_in_native_coordinates() {
+        let mut display = Builder::new(ST7789, CaptureToImage::new(240, 320))
+            .orientation(Orientation::new().rotate(Rotation::Deg90))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        let area = Rectangle::new(Point::new(0, 0), Size::new(2, 1));
+        display
+            .fill_contiguous(&area, [Rgb565::RED, Rgb565::BLUE])
+            .unwrap();
+
+        // Deg90 reverses the columns and swaps rows and columns
+        let (di, _, _, _) = display.release();
+        assert_eq!(di.pixel(239, 0), Some([255, 0, 0]));
+        assert_eq!(di.pixel(239, 1), Some([0, 0, 255]));
+        assert_eq!(di.pixel(238, 0), Some([0, 0, 0]));
+    }
+
+    #[test]
+    fn rgb666_pixels() {
+        let mut display = Builder::new(ILI9341Rgb666, CaptureToImage::new(240, 320))
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_pixel(10, 20, Rgb666::GREEN).unwrap();
+
+        let (di, _, _, _) = display.release();
+        assert_eq!(di.pixel(10, 20), Some([0, 255, 0]));
+    }
+
+    #[test]
+    fn pixels_outside_empty_framebuffer_are_ignored() {
+        let mut di = CaptureToImage::new(0, 0);
+        di.send_command(0x36, &[0xC0]).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[0xFF, 0xFF]]).unwrap();
+
+        assert_eq!(di.pixel(0, 0), None);
+    }
+
+    #[test]
+    fn changed_regions_are_merged() {
+        let reference = CaptureToImage::new(240, 320);
+        let mut display = Builder::new(ST7789, CaptureToImage::new(240, 320))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 20), Size::new(5, 3)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        display.set_pixel(100, 21, Rgb565::BLUE).unwrap();
+        display.set_pixel(12, 23, Rgb565::BLUE).unwrap();
+
+        let (frame, _, _, _) = display.release();
+        assert_eq!(
+            diff_frames(&reference, &frame),
+            [
+                Rectangle::new(Point::new(10, 20), Size::new(5, 3)),
+                Rectangle::new(Point::new(100, 21), Size::new(1, 1)),
+                Rectangle::new(Point::new(12, 23), Size::new(1, 1)),
+            ]
+        );
+        assert_eq!(diff_frames(&frame, &frame), []);
+        assert_eq!(
+            diff_frames(&reference, &CaptureToImage::new(10, 400)),
+            [Rectangle::new(Point::zero(), Size::new(240, 400))]
+        );
+    }
+
+    #[test]
+    fn png_encoding() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+
+        let png = CaptureToImage::new(2, 2).to_png();
+        assert_eq!(
+            png[..8],
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n']
+        );
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use core::marker::PhantomData;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+use super::{Error, ErrorKind, Interface, InterfaceKind};
+
+/// Adapter error
+///
+/// Wraps the `DisplayError` of the `display-interface` implementation.
+#[derive(Clone, Debug)]
+pub struct CompatError(pub DisplayError);
+
+impl Error for CompatError {
+    fn kind(&self) -> ErrorKind {
+        match self.0 {
+            DisplayError::BusWriteError => ErrorKind::Bus,
+            DisplayError::DCError | DisplayError::CSError | DisplayError::RSError => ErrorKind::Pin,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl From<DisplayError> for CompatError {
+    fn from(error: DisplayError) -> Self {
+        Self(error)
+    }
+}
+
+/// Adapter for `display-interface` implementations
+///
+/// Wraps any implementation of the `WriteOnlyDataCommand` trait of the `display-interface`
+/// 0.5 crate, e.g. `display-interface-spi` or `display-interface-parallel-gpio`, which makes
+/// it possible to update to the current `mipidsi` version without replacing the interface
+/// first. Commands and parameters are sent as `u8` slices and pixel data as `u8` iterators,
+/// in the same way as `mipidsi` 0.8 did.
+///
+/// The builtin interfaces are faster, because they don't pass every pixel through a
+/// `dyn Iterator`, so this adapter is only intended as a temporary solution. See the
+/// [migration guide](https://github.com/almindor/mipidsi/blob/master/docs/MIGRATION.md)
+/// for the replacements of the `display-interface` crates.
+///
+/// The kind of the wrapped interface isn't known, so it is set by the `K` type parameter,
+/// which defaults to [`InterfaceKind::Serial4Line`]. Models which check the kind, like the
+/// [`ST7365PRgb565`](crate::models::ST7365PRgb565), need the kind of the actual bus, e.g.
+/// [`compat_kind::Parallel8Bit`] for `display-interface-parallel-gpio`.
+///
+/// Requires the `display-interface` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// use display_interface_spi::SPIInterface;
+/// use mipidsi::{interface::CompatInterface, models::ST7789, Builder};
+///
+/// let di = CompatInterface::new(SPIInterface::new(spi, dc));
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+///
+/// ```ignore
+/// use display_interface_parallel_gpio::{Generic8BitBus, PGPIO8BitInterface};
+/// use mipidsi::interface::{compat_kind, CompatInterface};
+///
+/// let bus = Generic8BitBus::new(pins);
+/// let di = CompatInterface::<_, compat_kind::Parallel8Bit>::with_kind(
+///     PGPIO8BitInterface::new(bus, dc, wr),
+/// );
+/// ```
+pub struct CompatInterface<T, K = compat_kind::Serial4Line> {
+    di: T,
+    kind: PhantomData<K>,
+}
+
+/// Interface kind of a [`CompatInterface`]
+///
+/// Implemented by the marker types in [`compat_kind`].
+pub trait CompatKind {
+    /// The kind of the wrapped interface.
+    const KIND: InterfaceKind;
+}
+
+/// Marker types for the kind of a [`CompatInterface`]
+pub mod compat_kind {
+    use super::{CompatKind, InterfaceKind};
+
+    /// Serial interface with data/command pin, e.g. `display-interface-spi`
+    pub struct Serial4Line;
+
+    impl CompatKind for Serial4Line {
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+    }
+
+    /// 8 bit parallel interface, e.g. `display-interface-parallel-gpio`
+    pub struct Parallel8Bit;
+
+    impl CompatKind for Parallel8Bit {
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+    }
+}
+
+impl<T: WriteOnlyDataCommand> CompatInterface<T> {
+    /// Creates a new adapter for a `display-interface` implementation.
+    ///
+    /// The adapter reports [`InterfaceKind::Serial4Line`], use [`with_kind`](Self::with_kind)
+    /// for other interface kinds.
+    pub fn new(di: T) -> Self {
+        Self::with_kind(di)
+    }
+}
+
+impl<T: WriteOnlyDataCommand, K: CompatKind> CompatInterface<T, K> {
+    /// Creates a new adapter for a `display-interface` implementation of the kind `K`.
+    pub fn with_kind(di: T) -> Self {
+        Self {
+            di,
+            kind: PhantomData,
+        }
+    }
+
+    /// Consumes the adapter and returns the wrapped interface.
+    pub fn release(self) -> T {
+        self.di
+    }
+}
+
+impl<T: WriteOnlyDataCommand, K: CompatKind> Interface for CompatInterface<T, K> {
+    type Word = u8;
+    type Error = CompatError;
+
+    const KIND: InterfaceKind = K::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_commands(DataFormat::U8(&[command]))?;
+        if !args.is_empty() {
+            self.di.send_data(DataFormat::U8(args))?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut bytes = pixels.into_iter().flatten();
+        self.di.send_data(DataFormat::U8Iter(&mut bytes))?;
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.send_pixels(core::iter::repeat(pixel).take(count as usize))
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        let len = data.len() - data.len() % N;
+        if len > 0 {
+            self.di.send_data(DataFormat::U8(&data[..len]))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `display-interface` implementation which counts the sent bytes.
+    #[derive(Default)]
+    struct CountingInterface {
+        commands: usize,
+        data: usize,
+    }
+
+    impl WriteOnlyDataCommand for CountingInterface {
+        fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            match cmd {
+                DataFormat::U8(bytes) => self.commands += bytes.len(),
+                _ => return Err(DisplayError::DataFormatNotImplemented),
+            }
+            Ok(())
+        }
+
+        fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            match buf {
+                DataFormat::U8(bytes) => self.data += bytes.len(),
+                DataFormat::U8Iter(bytes) => self.data += bytes.count(),
+                _ => return Err(DisplayError::DataFormatNotImplemented),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn commands_and_pixels_are_forwarded() {
+        let mut di = CompatInterface::new(CountingInterface::default());
+
+        di.send_command(0x2A, &[0, 0, 0, 9]).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 10).unwrap();
+        di.send_pixels_from_buffer::<2>(&[0; 5]).unwrap();
+
+        let di = di.release();
+        assert_eq!(di.commands, 2);
+        assert_eq!(di.data, 4 + 20 + 4);
+    }
+
+    #[test]
+    fn kind_is_set_by_type_parameter() {
+        fn kind<DI: Interface>(_di: &DI) -> InterfaceKind {
+            DI::KIND
+        }
+
+        let di = CompatInterface::new(CountingInterface::default());
+        assert_eq!(kind(&di), InterfaceKind::Serial4Line);
+
+        let di = CompatInterface::<_, compat_kind::Parallel8Bit>::with_kind(
+            CountingInterface::default(),
+        );
+        assert_eq!(kind(&di), InterfaceKind::Parallel8Bit);
+    }
+}
@@ -0,0 +1,369 @@
+use super::{Event, Interface, InterfaceKind};
+
+/// Interface for panels which are driven by two controllers
+///
+/// Some long panels, e.g. 320x960 bar displays, are driven by two identical controllers,
+/// which each drive one half of the panel. The controllers are stacked along the rows of
+/// the default orientation: the first interface drives the first `rows` rows and the second
+/// interface the remaining rows.
+///
+/// This interface is used together with the [`Dual`](crate::models::Dual) model. Commands
+/// are sent to both controllers, except for the address window, which is split at the
+/// border between the controllers. The pixel data is routed to the controller which
+/// contains the current pixel. The vertical scrolling commands affect both controllers
+/// separately and can't be used to scroll the whole panel.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::DualInterface,
+///     models::{Dual, ST7796},
+///     Builder,
+/// };
+///
+/// # let top = mipidsi::_mock::MockDisplayInterface;
+/// # let bottom = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// // Two ST7796 controllers with 480 rows each drive a 320x960 panel.
+/// let di = DualInterface::new(top, bottom, 480);
+/// let display = Builder::new(Dual(ST7796), di).init(&mut delay).unwrap();
+/// ```
+pub struct DualInterface<DI> {
+    interfaces: [DI; 2],
+    rows: u16,
+    madctl: u8,
+    columns: (u16, u16),
+    pages: (u16, u16),
+    position: u32,
+}
+
+impl<DI: Interface> DualInterface<DI> {
+    /// Creates a new dual controller interface.
+    ///
+    /// `rows` is the number of rows of the first controller in the default orientation,
+    /// which is the height of the framebuffer of a single controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is zero or if the rows of both controllers don't fit into the `u16`
+    /// address space.
+    pub fn new(first: DI, second: DI, rows: u16) -> Self {
+        assert!(rows != 0 && rows <= u16::MAX / 2);
+
+        Self {
+            interfaces: [first, second],
+            rows,
+            madctl: 0,
+            columns: (0, 0),
+            pages: (0, 0),
+            position: 0,
+        }
+    }
+
+    /// Consumes the interface and returns the interfaces of both controllers.
+    pub fn release(self) -> (DI, DI) {
+        let [first, second] = self.interfaces;
+        (first, second)
+    }
+
+    fn reverse_rows(&self) -> bool {
+        self.madctl & 0x80 != 0
+    }
+
+    fn swap_rows_and_columns(&self) -> bool {
+        self.madctl & 0x20 != 0
+    }
+
+    // Returns the controller which contains the row with the given address.
+    fn controller(&self, row: u16) -> usize {
+        usize::from((row >= self.rows) != self.reverse_rows())
+    }
+
+    // Sets the address windows of both controllers and starts the memory write.
+    fn write_memory_start(&mut self, args: &[u8]) -> Result<(), DI::Error> {
+        self.position = 0;
+
+        // The row axis of the controllers is the column address if rows and columns are swapped.
+        let ((start, end), other, [row_cmd, other_cmd]) = if self.swap_rows_and_columns() {
+            (self.columns, self.pages, [0x2A, 0x2B])
+        } else {
+            (self.pages, self.columns, [0x2B, 0x2A])
+        };
+        let other = address_args(other);
+
+        for half in 0..2 {
+            let first = half * self.rows;
+            let last = first + (self.rows - 1);
+            if start > last || end < first {
+                continue;
+            }
+
+            let rows = address_args((start.max(first) - first, end.min(last) - first));
+            let di = &mut self.interfaces[self.controller(first)];
+            di.send_command(row_cmd, &rows)?;
+            di.send_command(other_cmd, &other)?;
+            di.send_command(0x2C, args)?;
+        }
+
+        Ok(())
+    }
+
+    // Returns the controller for the next pixel and the number of following pixels which
+    // are sent to the same controller.
+    fn next_run(&self) -> (usize, u32) {
+        let width = u32::from(self.columns.1.saturating_sub(self.columns.0)) + 1;
+        let column = self.columns.0 as u32 + self.position % width;
+        let page = self.pages.0 as u32 + self.position / width;
+        let rows = u32::from(self.rows);
+
+        if self.swap_rows_and_columns() {
+            let border = (column / rows + 1) * rows;
+            let end = border.min(u32::from(self.columns.1) + 1);
+            (self.controller(column as u16), end - column)
+        } else {
+            let border = (page / rows + 1) * rows;
+            // Pixels after the end of the window are sent to the last controller.
+            let end = border.min(u32::from(self.pages.1) + 1).max(page + 1);
+            (
+                self.controller(page as u16),
+                (end - page) * width - self.position % width,
+            )
+        }
+    }
+
+    // Invalidates an area, which is split at the border between the controllers like the
+    // address window.
+    fn invalidate(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DI::Error> {
+        let swap = self.swap_rows_and_columns();
+        let ((start, end), (other_start, other_end)) = if swap {
+            ((sx, ex), (sy, ey))
+        } else {
+            ((sy, ey), (sx, ex))
+        };
+
+        for half in 0..2 {
+            let first = half * self.rows;
+            let last = first + (self.rows - 1);
+            if start > last || end < first {
+                continue;
+            }
+
+            let (row_start, row_end) = (start.max(first) - first, end.min(last) - first);
+            let di = &mut self.interfaces[self.controller(first)];
+            let (sx, sy, ex, ey) = if swap {
+                (row_start, other_start, row_end, other_end)
+            } else {
+                (other_start, row_start, other_end, row_end)
+            };
+            di.handle_event(Event::Invalidate { sx, sy, ex, ey })?;
+        }
+
+        Ok(())
+    }
+}
+
+fn address_args((start, end): (u16, u16)) -> [u8; 4] {
+    let [s0, s1] = start.to_be_bytes();
+    let [e0, e1] = end.to_be_bytes();
+    [s0, s1, e0, e1]
+}
+
+fn parse_address_args(args: &[u8]) -> Option<(u16, u16)> {
+    match *args {
+        [s0, s1, e0, e1] => Some((u16::from_be_bytes([s0, s1]), u16::from_be_bytes([e0, e1]))),
+        _ => None,
+    }
+}
+
+impl<DI: Interface> Interface for DualInterface<DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        // Some vendor specific commands reuse the instruction codes of the address commands
+        // with a different number of parameters, those are sent to both controllers.
+        match (command, parse_address_args(args)) {
+            (0x2A, Some(columns)) => self.columns = columns,
+            (0x2B, Some(pages)) => self.pages = pages,
+            (0x2C, _) => return self.write_memory_start(args),
+            (0x36, _) => {
+                if let Some(&madctl) = args.first() {
+                    self.madctl = madctl;
+                }
+            }
+            _ => {}
+        }
+
+        for di in &mut self.interfaces {
+            di.send_command(command, args)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter().peekable();
+        while pixels.peek().is_some() {
+            let (controller, run) = self.next_run();
+            self.interfaces[controller].send_pixels(pixels.by_ref().take(run as usize))?;
+            self.position += run;
+        }
+
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        mut count: u32,
+    ) -> Result<(), Self::Error> {
+        while count > 0 {
+            let (controller, run) = self.next_run();
+            let run = run.min(count);
+            self.interfaces[controller].send_repeated_pixel(pixel, run)?;
+            self.position += run;
+            count -= run;
+        }
+
+        Ok(())
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        let mut data = &data[..data.len() - data.len() % N];
+        while !data.is_empty() {
+            let (controller, run) = self.next_run();
+            let (head, tail) = data.split_at((run as usize).saturating_mul(N).min(data.len()));
+            self.interfaces[controller].send_pixels_from_buffer::<N>(head)?;
+            self.position += (head.len() / N) as u32;
+            data = tail;
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        match event {
+            Event::Invalidate { sx, sy, ex, ey } => self.invalidate(sx, sy, ex, ey),
+            event => {
+                for di in &mut self.interfaces {
+                    di.handle_event(event)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        _mock::MockDelay,
+        _test::RecordingInterface,
+        models::{Dual, ST7789},
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    use embedded_graphics_core::{
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::*,
+        primitives::Rectangle,
+    };
+
+    use super::*;
+
+    /// Fills `area` with each of the three memory writes and invalidates it.
+    fn draw(orientation: Orientation, area: Rectangle) -> (RecordingInterface, RecordingInterface) {
+        let di = DualInterface::new(
+            RecordingInterface::default(),
+            RecordingInterface::default(),
+            320,
+        );
+        let mut display = Builder::new(Dual(ST7789), di)
+            .orientation(orientation)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.fill_solid(&area, Rgb565::RED).unwrap();
+        let colors = core::iter::repeat(Rgb565::BLUE)
+            .take(area.size.width as usize * area.size.height as usize);
+        display.fill_contiguous(&area, colors).unwrap();
+
+        let end = area.bottom_right().unwrap();
+        let data = [0x12; 20 * 30 * 2];
+        display
+            .set_pixels_from_buffer(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                end.x as u16,
+                end.y as u16,
+                &data,
+            )
+            .unwrap();
+        display.invalidate(area).unwrap();
+
+        display.release().0.release()
+    }
+
+    #[test]
+    fn window_is_split_between_controllers() {
+        let area = Rectangle::new(Point::new(10, 300), Size::new(20, 30));
+        let (first, second) = draw(Orientation::new(), area);
+
+        assert_eq!(first.columns, [0, 10, 0, 29]);
+        assert_eq!(first.pages, [0x01, 0x2C, 0x01, 0x3F]);
+        assert_eq!(first.pixels, 3 * 20 * 20);
+        assert_eq!(second.columns, [0, 10, 0, 29]);
+        assert_eq!(second.pages, [0, 0, 0, 9]);
+        assert_eq!(second.pixels, 3 * 20 * 10);
+        assert_eq!(first.invalidated, Some((10, 300, 29, 319)));
+        assert_eq!(second.invalidated, Some((10, 0, 29, 9)));
+    }
+
+    #[test]
+    fn swapped_window_is_split_between_controllers() {
+        // Rows are the x axis and reversed for this orientation, which means that the
+        // first rows are drawn by the second controller.
+        let area = Rectangle::new(Point::new(300, 10), Size::new(30, 20));
+        let (first, second) = draw(Orientation::new().rotate(Rotation::Deg270), area);
+
+        assert_eq!(first.columns, [0, 0, 0, 9]);
+        assert_eq!(first.pages, [0, 10, 0, 29]);
+        assert_eq!(first.pixels, 3 * 10 * 20);
+        assert_eq!(second.columns, [0x01, 0x2C, 0x01, 0x3F]);
+        assert_eq!(second.pages, [0, 10, 0, 29]);
+        assert_eq!(second.pixels, 3 * 20 * 20);
+        assert_eq!(first.invalidated, Some((0, 10, 9, 29)));
+        assert_eq!(second.invalidated, Some((300, 10, 319, 29)));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: rows != 0 && rows <= u16::MAX / 2")]
+    fn panic_zero_rows() {
+        let _ = DualInterface::new(
+            RecordingInterface::default(),
+            RecordingInterface::default(),
+            0,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: rows != 0 && rows <= u16::MAX / 2")]
+    fn panic_too_many_rows() {
+        let _ = DualInterface::new(
+            RecordingInterface::default(),
+            RecordingInterface::default(),
+            u16::MAX / 2 + 1,
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use embedded_hal::{
+    digital::{self, OutputPin},
+    i2c::{I2c, Operation},
+};
+
+use super::{write_pixels, write_repeated_pixel, Error, ErrorKind, Interface, InterfaceKind};
+
+/// I2c interface error
+#[derive(Clone, Copy, Debug)]
+pub enum I2cError<I2C, DC> {
+    /// I2C bus error
+    I2c(I2C),
+    /// Data/command pin error
+    Dc(DC),
+}
+
+impl<I2C: core::fmt::Debug, DC: core::fmt::Debug> Error for I2cError<I2C, DC> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::I2c(_) => ErrorKind::Bus,
+            Self::Dc(_) => ErrorKind::Pin,
+        }
+    }
+}
+
+/// I2c interface, including a buffer
+///
+/// Some low pin count designs connect the display controller over I2C, either directly to
+/// a controller with an I2C interface or through an I2C-to-SPI bridge like the SC18IS602.
+/// Every I2C write starts with a control byte, which is followed by a command or by
+/// parameters and pixel data. By default the control byte is `0x00` for commands and `0x40`
+/// for data, which is used by most controllers with an I2C interface. The control bytes can
+/// be changed with [`control_bytes`](Self::control_bytes), e.g. an SC18IS602 expects the
+/// function ID of the slave select output as the first byte of every write.
+///
+/// If the data/command line of the display isn't driven by the control byte, a data/command
+/// pin can be added with [`dc_pin`](Self::dc_pin). It is set low while a command is sent.
+///
+/// The buffer is used to gather batches of pixel data, which are sent in a single write.
+/// Larger buffers reduce the overhead of the address and control bytes, but should not
+/// exceed the maximum write length of a bridge, which is 200 bytes for the SC18IS602.
+///
+/// The interface reports [`InterfaceKind::Serial4Line`], because commands and data are
+/// separated like on a 4-wire serial interface.
+pub struct I2cInterface<'a, I2C, DC = NoDcPin> {
+    i2c: I2C,
+    address: u8,
+    dc: Option<DC>,
+    buffer: &'a mut [u8],
+    command_control: u8,
+    data_control: u8,
+}
+
+impl<'a, I2C: I2c> I2cInterface<'a, I2C> {
+    /// Create new interface for the device with the 7-bit `address`
+    pub fn new(i2c: I2C, address: u8, buffer: &'a mut [u8]) -> Self {
+        Self {
+            i2c,
+            address,
+            dc: None,
+            buffer,
+            command_control: 0x00,
+            data_control: 0x40,
+        }
+    }
+
+    /// Adds a data/command pin, which is set low while a command is sent.
+    pub fn dc_pin<DC: OutputPin>(self, dc: DC) -> I2cInterface<'a, I2C, DC> {
+        I2cInterface {
+            i2c: self.i2c,
+            address: self.address,
+            dc: Some(dc),
+            buffer: self.buffer,
+            command_control: self.command_control,
+            data_control: self.data_control,
+        }
+    }
+}
+
+impl<I2C: I2c, DC: OutputPin> I2cInterface<'_, I2C, DC> {
+    /// Sets the control bytes which are sent before commands and before data.
+    #[must_use]
+    pub fn control_bytes(mut self, command: u8, data: u8) -> Self {
+        self.command_control = command;
+        self.data_control = data;
+        self
+    }
+
+    /// Release the I2C bus and the data/command pin
+    pub fn release(self) -> (I2C, Option<DC>) {
+        (self.i2c, self.dc)
+    }
+
+    /// Sets the data/command pin, if there is one.
+    fn set_dc(&mut self, data: bool) -> Result<(), I2cError<I2C::Error, DC::Error>> {
+        match &mut self.dc {
+            Some(dc) if data => dc.set_high().map_err(I2cError::Dc),
+            Some(dc) => dc.set_low().map_err(I2cError::Dc),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the control byte and the data in a single I2C write.
+fn write_data<I2C: I2c>(
+    i2c: &mut I2C,
+    address: u8,
+    control: u8,
+    data: &[u8],
+) -> Result<(), I2C::Error> {
+    i2c.transaction(
+        address,
+        &mut [Operation::Write(&[control]), Operation::Write(data)],
+    )
+}
+
+impl<I2C: I2c, DC: OutputPin> Interface for I2cInterface<'_, I2C, DC> {
+    type Word = u8;
+    type Error = I2cError<I2C::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.set_dc(false)?;
+        write_data(
+            &mut self.i2c,
+            self.address,
+            self.command_control,
+            &[command],
+        )
+        .map_err(I2cError::I2c)?;
+        self.set_dc(true)?;
+        if !args.is_empty() {
+            write_data(&mut self.i2c, self.address, self.data_control, args)
+                .map_err(I2cError::I2c)?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let (i2c, address, control) = (&mut self.i2c, self.address, self.data_control);
+        write_pixels(self.buffer, pixels, |data| {
+            write_data(i2c, address, control, data).map_err(I2cError::I2c)
+        })
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let (i2c, address, control) = (&mut self.i2c, self.address, self.data_control);
+        write_repeated_pixel(self.buffer, pixel, count, |data| {
+            write_data(i2c, address, control, data).map_err(I2cError::I2c)
+        })
+    }
+}
+
+/// Marker type for no data/command pin.
+pub enum NoDcPin {}
+
+impl OutputPin for NoDcPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl digital::ErrorType for NoDcPin {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::i2c;
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    /// I2C bus which records the writes.
+    #[derive(Default)]
+    struct RecordingI2c {
+        data: heapless::Vec<heapless::Vec<u8, 16>, 8>,
+    }
+
+    impl i2c::ErrorType for RecordingI2c {
+        type Error = Infallible;
+    }
+
+    impl I2c for RecordingI2c {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(address, 0x3C);
+            let mut write = heapless::Vec::new();
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    write.extend_from_slice(data).unwrap();
+                }
+            }
+            self.data.push(write).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn commands_are_framed() {
+        let mut buffer = [0; 4];
+        let mut di = I2cInterface::new(RecordingI2c::default(), 0x3C, &mut buffer);
+
+        di.send_command(0x2A, &[0x00, 0x01]).unwrap();
+        di.send_command(0x29, &[]).unwrap();
+        assert_eq!(
+            di.i2c.data,
+            [&[0x00, 0x2A][..], &[0x40, 0x00, 0x01], &[0x00, 0x29]]
+        );
+    }
+
+    #[test]
+    fn pixels_are_chunked() {
+        let mut buffer = [0; 4];
+        let mut di = I2cInterface::new(RecordingI2c::default(), 0x3C, &mut buffer)
+            .dc_pin(MockOutputPin)
+            .control_bytes(0x01, 0x01);
+
+        di.send_repeated_pixel([0x12, 0x34], 3).unwrap();
+        assert_eq!(
+            di.i2c.data,
+            [&[0x01, 0x12, 0x34, 0x12, 0x34][..], &[0x01, 0x12, 0x34]]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use super::{Event, Interface, InterfaceKind};
+
+/// Interface wrapper which limits the size of the pixel transfers
+///
+/// The driver passes all pixels of a drawing operation to a single call of the interface.
+/// Interfaces which send all pixels of a call in a single transfer, e.g. with DMA, can be
+/// wrapped in this interface to split the pixel data into multiple calls, which contain as
+/// many whole pixels as fit into `max_transfer_size` bytes. Interfaces with a buffer, like
+/// [`SpiInterface`](crate::interface::SpiInterface), are already limited by the size of the
+/// buffer.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::TransferLimitInterface, models::ST7789, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let di = TransferLimitInterface::new(di, 65535);
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct TransferLimitInterface<DI> {
+    di: DI,
+    max_transfer_size: usize,
+}
+
+impl<DI: Interface> TransferLimitInterface<DI> {
+    /// Creates a new interface, which sends at most `max_transfer_size` bytes per transfer.
+    ///
+    /// Pixels which are larger than the limit are sent in separate transfers.
+    pub fn new(di: DI, max_transfer_size: usize) -> Self {
+        Self {
+            di,
+            max_transfer_size,
+        }
+    }
+
+    /// Consumes the interface and returns the wrapped interface.
+    pub fn release(self) -> DI {
+        self.di
+    }
+
+    // Returns the maximum number of pixels with `N` words per transfer.
+    fn max_pixels<const N: usize>(&self) -> usize {
+        let pixel_size = N * core::mem::size_of::<DI::Word>();
+        (self.max_transfer_size / pixel_size).max(1)
+    }
+}
+
+impl<DI: Interface> Interface for TransferLimitInterface<DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_command(command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let max_pixels = self.max_pixels::<N>();
+        let mut pixels = pixels.into_iter().peekable();
+        while pixels.peek().is_some() {
+            self.di.send_pixels((&mut pixels).take(max_pixels))?;
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let max_pixels = u32::try_from(self.max_pixels::<N>()).unwrap_or(u32::MAX);
+        let mut count = count;
+        while count > 0 {
+            let chunk = count.min(max_pixels);
+            self.di.send_repeated_pixel(pixel, chunk)?;
+            count -= chunk;
+        }
+        Ok(())
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        let len = data.len() - data.len() % N;
+        for chunk in data[..len].chunks(self.max_pixels::<N>() * N) {
+            self.di.send_pixels_from_buffer::<N>(chunk)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.di.handle_event(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+    use crate::{_mock::MockDelay, models::ST7789, Builder};
+
+    use super::*;
+
+    // Interface with a transfer limit of 100 bytes, which records the pixel counts of the
+    // last transfers.
+    #[derive(Default)]
+    struct LimitedInterface {
+        transfers: [u32; 4],
+        count: usize,
+    }
+
+    impl LimitedInterface {
+        fn record(&mut self, pixels: u32) {
+            assert!(pixels * 2 <= 100);
+            self.transfers[self.count] = pixels;
+            self.count += 1;
+        }
+    }
+
+    impl Interface for LimitedInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            self.count = 0;
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            self.record(pixels.into_iter().count() as u32);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.record(count);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pixels_are_split_into_limited_transfers() {
+        let di = TransferLimitInterface::new(LimitedInterface::default(), 100);
+        let mut display = Builder::new(ST7789, di).init(&mut MockDelay).unwrap();
+
+        let area = Rectangle::new(Point::zero(), Size::new(12, 10));
+        display.fill_solid(&area, Rgb565::RED).unwrap();
+        let di = unsafe { &display.dcs().di };
+        assert_eq!(di.transfers[..di.count], [50, 50, 20]);
+
+        display
+            .fill_contiguous(&area, core::iter::repeat(Rgb565::BLUE))
+            .unwrap();
+        let di = unsafe { &display.dcs().di };
+        assert_eq!(di.transfers[..di.count], [50, 50, 20]);
+
+        display
+            .set_pixels_from_buffer(0, 0, 11, 9, &[0; 240])
+            .unwrap();
+        let di = unsafe { &display.dcs().di };
+        assert_eq!(di.transfers[..di.count], [50, 50, 20]);
+    }
+}
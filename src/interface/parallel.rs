@@ -1,27 +1,57 @@
 use embedded_hal::digital::OutputPin;
 
-use super::Interface;
+use super::{Error, ErrorKind, Interface, InterfaceKind};
 
 /// This trait represents the data pins of a parallel bus.
 ///
-/// See [Generic8BitBus] and [Generic16BitBus] for generic implementations.
+/// See [Generic8BitBus], [Generic16BitBus] and [Generic18BitBus] for generic implementations
+/// and [`ShiftRegisterBus`](super::ShiftRegisterBus) for buses driven by shift registers.
 pub trait OutputBus {
-    /// [u8] for 8-bit buses, [u16] for 16-bit buses, etc.
+    /// [u8] for 8-bit buses, [u16] for 16-bit buses, [u32] for 18-bit buses, etc.
     type Word: Copy;
 
     /// Error type
     type Error: core::fmt::Debug;
 
+    /// Interface kind of a [`ParallelInterface`] using this bus
+    const KIND: InterfaceKind;
+
     /// Set the output bus to a specific value
     fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error>;
 }
 
+/// Order of the bytes in a 16-bit word
+///
+/// Some boards connect the lower byte of the MCU data pins to the upper byte of the display
+/// data bus and vice versa. With [`WordOrder::SwappedBytes`] the bytes are swapped before
+/// they are written to the pins, which corrects the scrambled colors and commands without
+/// changing the wiring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WordOrder {
+    /// The first pin is the least significant bit.
+    #[default]
+    Normal,
+    /// The first pin is the least significant bit of the upper byte.
+    SwappedBytes,
+}
+
+impl WordOrder {
+    /// Returns the value which needs to be written to the pins.
+    const fn apply(self, value: u16) -> u16 {
+        match self {
+            Self::Normal => value,
+            Self::SwappedBytes => value.swap_bytes(),
+        }
+    }
+}
+
 macro_rules! generic_bus {
-    ($GenericxBitBus:ident { type Word = $Word:ident; Pins {$($PX:ident => $x:tt,)*}}) => {
+    ($GenericxBitBus:ident { type Word = $Word:ident; const KIND = $kind:ident; $(fn $word_order:ident;)? Pins {$($PX:ident => $x:tt,)*}}) => {
         /// A generic implementation of [OutputBus] using [OutputPin]s
         pub struct $GenericxBitBus<$($PX, )*> {
             pins: ($($PX, )*),
             last: Option<$Word>,
+            $($word_order: WordOrder,)?
         }
 
         impl<$($PX, )*> $GenericxBitBus<$($PX, )*>
@@ -32,8 +62,23 @@ macro_rules! generic_bus {
             ///
             /// The first pin in the tuple is the least significant bit.
             pub fn new(pins: ($($PX, )*)) -> Self {
-                Self { pins, last: None }
+                Self {
+                    pins,
+                    last: None,
+                    $($word_order: WordOrder::Normal,)?
+                }
+            }
+
+            $(
+            /// Sets the order of the bytes in a word.
+            ///
+            /// See [`WordOrder`] for details.
+            #[must_use]
+            pub fn $word_order(mut self, $word_order: WordOrder) -> Self {
+                self.$word_order = $word_order;
+                self
             }
+            )?
 
             /// Consumes the bus and returns the pins. This does not change the state of the pins.
             pub fn release(self) -> ($($PX, )*) {
@@ -50,7 +95,11 @@ macro_rules! generic_bus {
             type Word = $Word;
             type Error = E;
 
+            const KIND: InterfaceKind = InterfaceKind::$kind;
+
             fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+                $(let value = self.$word_order.apply(value);)?
+
                 if self.last == Some(value) {
                     // It's quite common for multiple consecutive values to be identical, e.g. when filling or
                     // clearing the screen, so let's optimize for that case
@@ -98,6 +147,7 @@ macro_rules! generic_bus {
 generic_bus! {
     Generic8BitBus {
         type Word = u8;
+        const KIND = Parallel8Bit;
         Pins {
             P0 => 0,
             P1 => 1,
@@ -114,6 +164,8 @@ generic_bus! {
 generic_bus! {
     Generic16BitBus {
         type Word = u16;
+        const KIND = Parallel16Bit;
+        fn word_order;
         Pins {
             P0 => 0,
             P1 => 1,
@@ -135,6 +187,33 @@ generic_bus! {
     }
 }
 
+generic_bus! {
+    Generic18BitBus {
+        type Word = u32;
+        const KIND = Parallel18Bit;
+        Pins {
+            P0 => 0,
+            P1 => 1,
+            P2 => 2,
+            P3 => 3,
+            P4 => 4,
+            P5 => 5,
+            P6 => 6,
+            P7 => 7,
+            P8 => 8,
+            P9 => 9,
+            P10 => 10,
+            P11 => 11,
+            P12 => 12,
+            P13 => 13,
+            P14 => 14,
+            P15 => 15,
+            P16 => 16,
+            P17 => 17,
+        }
+    }
+}
+
 /// Parallel interface error
 #[derive(Clone, Copy, Debug)]
 pub enum ParallelError<BUS, DC, WR> {
@@ -146,6 +225,17 @@ pub enum ParallelError<BUS, DC, WR> {
     Wr(WR),
 }
 
+impl<BUS: core::fmt::Debug, DC: core::fmt::Debug, WR: core::fmt::Debug> Error
+    for ParallelError<BUS, DC, WR>
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(_) => ErrorKind::Bus,
+            Self::Dc(_) | Self::Wr(_) => ErrorKind::Pin,
+        }
+    }
+}
+
 /// Parallel communication interface
 ///
 /// This interface implements a "8080" style write-only display interface using any
@@ -199,6 +289,8 @@ where
     type Word = BUS::Word;
     type Error = ParallelError<BUS::Error, DC::Error, WR::Error>;
 
+    const KIND: InterfaceKind = BUS::KIND;
+
     fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
         self.dc.set_low().map_err(ParallelError::Dc)?;
         self.send_word(BUS::Word::from(command))?;
@@ -254,3 +346,67 @@ fn is_same<const N: usize, T: Copy + Eq>(array: [T; N]) -> Option<T> {
     }
     Some(first)
 }
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use embedded_hal::digital;
+
+    use super::*;
+
+    /// Pin which stores its state in a bit of a shared value.
+    struct BitPin<'a> {
+        value: &'a Cell<u16>,
+        bit: u16,
+    }
+
+    impl digital::ErrorType for BitPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for BitPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.value.set(self.value.get() & !(1 << self.bit));
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.value.set(self.value.get() | (1 << self.bit));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn swapped_bytes() {
+        let value = Cell::new(0);
+        let pin = |bit| BitPin { value: &value, bit };
+        let mut bus = Generic16BitBus::new((
+            pin(0),
+            pin(1),
+            pin(2),
+            pin(3),
+            pin(4),
+            pin(5),
+            pin(6),
+            pin(7),
+            pin(8),
+            pin(9),
+            pin(10),
+            pin(11),
+            pin(12),
+            pin(13),
+            pin(14),
+            pin(15),
+        ));
+
+        bus.set_value(0x1234).unwrap();
+        assert_eq!(value.get(), 0x1234);
+
+        let mut bus = bus.word_order(WordOrder::SwappedBytes);
+        bus.set_value(0x1234).unwrap();
+        assert_eq!(value.get(), 0x3412);
+        bus.set_value(0x002A).unwrap();
+        assert_eq!(value.get(), 0x2A00);
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Event, Interface, InterfaceKind, SpeedProfile};
+
+/// Interface wrapper which reports speed profile changes to a hook
+///
+/// The hook is called before the display is reset and after the init sequence has
+/// completed. It can be used to change the clock frequency of the SPI bus, for HALs which
+/// support reclocking at runtime, e.g. to initialize a panel with 10 MHz and write the pixel
+/// data with 62.5 MHz. The hook usually needs shared access to the bus, e.g. through a
+/// `RefCell` which is also used by the `SpiDevice` of the wrapped interface.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{
+///     interface::{ReclockingInterface, SpeedProfile},
+///     models::ST7789,
+///     Builder,
+/// };
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut frequency = 0;
+/// let di = ReclockingInterface::new(di, |profile| {
+///     frequency = match profile {
+///         SpeedProfile::Init => 10_000_000,
+///         SpeedProfile::Pixels => 62_500_000,
+///     };
+/// });
+///
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// # drop(display);
+///
+/// assert_eq!(frequency, 62_500_000);
+/// ```
+pub struct ReclockingInterface<DI, F> {
+    di: DI,
+    hook: F,
+}
+
+impl<DI, F> ReclockingInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(SpeedProfile),
+{
+    /// Creates a new reclocking interface, which calls `hook` for every speed profile change.
+    pub fn new(di: DI, hook: F) -> Self {
+        Self { di, hook }
+    }
+
+    /// Consumes the reclocking interface and returns the wrapped interface and the hook.
+    pub fn release(self) -> (DI, F) {
+        (self.di, self.hook)
+    }
+}
+
+impl<DI, F> Interface for ReclockingInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(SpeedProfile),
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_command(command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels(pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels_from_buffer::<N>(data)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        if let Event::SpeedProfile(profile) = event {
+            (self.hook)(profile);
+        }
+        self.di.handle_event(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::{
+        _mock::{MockDelay, MockOutputPin},
+        models::ST7789,
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn init_uses_init_profile() {
+        let profile = Cell::new(None);
+        let commands = Cell::new(0);
+        let di = ReclockingInterface::new(
+            crate::interface::TracingInterface::new(crate::_mock::MockDisplayInterface, |_, _| {
+                // All init commands are sent with the init profile
+                assert_eq!(profile.get(), Some(SpeedProfile::Init));
+                commands.set(commands.get() + 1);
+            }),
+            |p| profile.set(Some(p)),
+        );
+
+        let _display = Builder::new(ST7789, di)
+            .reset_pin(MockOutputPin)
+            .display_on_after_first_draw()
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(profile.get(), Some(SpeedProfile::Pixels));
+        assert!(commands.get() > 0);
+    }
+}
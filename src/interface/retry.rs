@@ -0,0 +1,201 @@
+use embedded_hal::delay::DelayNs;
+
+use super::{Event, Interface, InterfaceKind};
+
+/// Number of pixels which are buffered to be able to resend them
+const CHUNK_SIZE: usize = 32;
+
+/// Interface wrapper which retries failed transfers
+///
+/// Long cables and noisy environments can cause sporadic errors in some HALs, which would
+/// otherwise abort the drawing operation. This interface retries every failed call of the
+/// wrapped interface up to `retries` times. Before each retry it waits for a backoff time,
+/// which starts at [`backoff_us`](Self::backoff_us) and is doubled after every failed
+/// attempt. If the last attempt fails, its error is returned.
+///
+/// The pixel iterator of [`send_pixels`](Interface::send_pixels) can only be consumed once,
+/// so the pixels are forwarded in chunks of 32 pixels, which are resent on errors. Resending
+/// a transfer is only correct if the failed transfer didn't reach the display controller,
+/// which is the case for most errors reported by a HAL, like bus arbitration errors.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::RetryInterface, models::ST7789, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// # let retry_delay = mipidsi::_mock::MockDelay;
+/// let di = RetryInterface::new(di, retry_delay, 3).backoff_us(50);
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct RetryInterface<DI, D> {
+    di: DI,
+    delay: D,
+    retries: u32,
+    backoff_us: u32,
+}
+
+impl<DI, D> RetryInterface<DI, D>
+where
+    DI: Interface,
+    D: DelayNs,
+{
+    /// Creates a new retry interface, which retries failed calls up to `retries` times.
+    ///
+    /// The initial backoff time is 10 us.
+    pub fn new(di: DI, delay: D, retries: u32) -> Self {
+        Self {
+            di,
+            delay,
+            retries,
+            backoff_us: 10,
+        }
+    }
+
+    /// Sets the time in microseconds to wait before the first retry.
+    #[must_use]
+    pub fn backoff_us(mut self, backoff_us: u32) -> Self {
+        self.backoff_us = backoff_us;
+        self
+    }
+
+    /// Consumes the retry interface and returns the wrapped interface and the delay.
+    pub fn release(self) -> (DI, D) {
+        (self.di, self.delay)
+    }
+
+    // Calls `f` until it succeeds or the retries are exhausted.
+    fn retry<T>(
+        &mut self,
+        mut f: impl FnMut(&mut DI) -> Result<T, DI::Error>,
+    ) -> Result<T, DI::Error> {
+        let mut backoff_us = self.backoff_us;
+        let mut retries = self.retries;
+        loop {
+            match f(&mut self.di) {
+                Err(_) if retries > 0 => {
+                    self.delay.delay_us(backoff_us);
+                    backoff_us = backoff_us.saturating_mul(2);
+                    retries -= 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<DI, D> Interface for RetryInterface<DI, D>
+where
+    DI: Interface,
+    D: DelayNs,
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.retry(|di| di.send_command(command, args))
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter();
+        let Some(first) = pixels.next() else {
+            return Ok(());
+        };
+
+        let mut chunk = [first; CHUNK_SIZE];
+        let mut len = 1;
+        loop {
+            for (slot, pixel) in chunk[len..].iter_mut().zip(&mut pixels) {
+                *slot = pixel;
+                len += 1;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+
+            let pixels = &chunk[..len];
+            self.retry(|di| di.send_pixels(pixels.iter().copied()))?;
+            len = 0;
+        }
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.retry(|di| di.send_repeated_pixel(pixel, count))
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.retry(|di| di.send_pixels_from_buffer::<N>(data))
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.retry(|di| di.handle_event(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::MockDelay, _test::FlakyInterface, dcs::InterfaceExt, interface::ErrorKind,
+        models::ST7789, Builder,
+    };
+
+    use super::*;
+
+    // Delay which records the requested delays.
+    struct RecordingDelay<'a>(&'a Cell<[u32; 3]>);
+
+    impl DelayNs for RecordingDelay<'_> {
+        fn delay_ns(&mut self, ns: u32) {
+            let [_, a, b] = self.0.get();
+            self.0.set([a, b, ns / 1000]);
+        }
+    }
+
+    #[test]
+    fn failed_calls_are_retried() {
+        let di = FlakyInterface {
+            period: 2,
+            ..FlakyInterface::default()
+        };
+        let mut display = Builder::new(ST7789, RetryInterface::new(di, MockDelay, 1))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display
+            .set_pixels(0, 0, 9, 9, core::iter::repeat(Rgb565::RED).take(100))
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.di.pixels, 100);
+    }
+
+    #[test]
+    fn backoff_is_doubled() {
+        let delays = Cell::new([0; 3]);
+        let di = FlakyInterface {
+            period: 1,
+            ..FlakyInterface::default()
+        };
+        let mut di = RetryInterface::new(di, RecordingDelay(&delays), 3).backoff_us(20);
+
+        assert_eq!(di.write_raw(0x29, &[]), Err(ErrorKind::Bus));
+        assert_eq!(di.di.calls, 4);
+        assert_eq!(delays.get(), [20, 40, 80]);
+    }
+}
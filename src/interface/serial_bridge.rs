@@ -0,0 +1,301 @@
+use core::convert::Infallible;
+use std::{io, vec::Vec};
+
+use super::{Error, ErrorKind, Interface, InterfaceKind};
+
+/// Start byte of every frame
+const FRAME_START: u8 = 0xA5;
+/// Frame kind of a command with its parameters
+const KIND_COMMAND: u8 = 0x01;
+/// Frame kind of pixel data
+const KIND_PIXELS: u8 = 0x02;
+
+/// Interface which sends the commands and pixel data as frames over a serial link
+///
+/// Instead of driving a display controller, this interface encodes the command and pixel
+/// stream into frames and writes them to an [`io::Write`] implementation, e.g. a UART or
+/// USB CDC serial port. A [`SerialBridgeDecoder`] on the other side of the link, for example
+/// on a debugging rig or a hardware-in-the-loop test runner with the panel attached,
+/// decodes the frames and forwards them to the real interface or to a [`CaptureToImage`](super::CaptureToImage).
+///
+/// Every frame consists of the start byte `0xA5`, the frame kind, the length of the payload
+/// as a little-endian `u16` and the payload. The frame kind is `0x01` for a command, with the
+/// instruction and the parameters as payload, and `0x02` for pixel data. Pixel data is
+/// gathered into frames of at most `max_payload` bytes.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::{
+///     interface::{CaptureToImage, SerialBridgeDecoder, SerialBridgeInterface},
+///     models::ST7789,
+///     Builder,
+/// };
+///
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// // Sending side, which writes the frames into a `Vec` instead of a serial port
+/// let di = SerialBridgeInterface::new(Vec::new(), 1024);
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// display.clear(Rgb565::RED).unwrap();
+/// let (di, _model, _rst, _bl) = display.release();
+///
+/// // Receiving side
+/// let frames = di.release();
+/// let mut decoder = SerialBridgeDecoder::new(frames.as_slice());
+/// let mut capture = CaptureToImage::new(240, 320);
+/// while decoder.forward(&mut capture).unwrap() {}
+/// assert_eq!(capture.pixel(0, 0), Some([255, 0, 0]));
+/// ```
+pub struct SerialBridgeInterface<W> {
+    writer: W,
+    buffer: Vec<u8>,
+    max_payload: usize,
+}
+
+impl<W: io::Write> SerialBridgeInterface<W> {
+    /// Creates a new serial bridge interface.
+    ///
+    /// `max_payload` is the maximum size of the pixel data in a frame, it is limited to
+    /// `u16::MAX` bytes.
+    pub fn new(writer: W, max_payload: usize) -> Self {
+        let max_payload = max_payload.clamp(1, usize::from(u16::MAX));
+
+        Self {
+            writer,
+            buffer: Vec::with_capacity(max_payload),
+            max_payload,
+        }
+    }
+
+    /// Consumes the interface and returns the writer.
+    pub fn release(self) -> W {
+        self.writer
+    }
+
+    // Sends the buffered pixel data.
+    fn flush_pixels(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            write_frame(&mut self.writer, KIND_PIXELS, &self.buffer)?;
+            self.buffer.clear();
+        }
+        self.writer.flush()
+    }
+
+    fn write_pixel_bytes(&mut self, bytes: impl IntoIterator<Item = u8>) -> io::Result<()> {
+        for byte in bytes {
+            self.buffer.push(byte);
+            if self.buffer.len() == self.max_payload {
+                self.flush_pixels()?;
+            }
+        }
+        self.flush_pixels()
+    }
+}
+
+fn write_frame(writer: &mut impl io::Write, kind: u8, payload: &[u8]) -> io::Result<()> {
+    // The length is limited by `max_payload` and by the DCS parameter count
+    let len = payload.len() as u16;
+
+    writer.write_all(&[FRAME_START, kind])?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(payload)
+}
+
+impl<W: io::Write> Interface for SerialBridgeInterface<W> {
+    type Word = u8;
+    type Error = SerialBridgeError<Infallible>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        let mut payload = Vec::with_capacity(args.len() + 1);
+        payload.push(command);
+        payload.extend_from_slice(args);
+
+        write_frame(&mut self.writer, KIND_COMMAND, &payload)
+            .and_then(|()| self.writer.flush())
+            .map_err(SerialBridgeError::Io)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.write_pixel_bytes(pixels.into_iter().flatten())
+            .map_err(SerialBridgeError::Io)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.write_pixel_bytes(core::iter::repeat(pixel).take(count as usize).flatten())
+            .map_err(SerialBridgeError::Io)
+    }
+}
+
+/// Decoded frame of a [`SerialBridgeInterface`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerialBridgeFrame {
+    /// Command with the instruction and the parameters
+    Command {
+        /// Instruction code
+        instruction: u8,
+        /// Parameters
+        params: Vec<u8>,
+    },
+    /// Pixel data
+    Pixels(Vec<u8>),
+}
+
+/// Serial bridge error
+///
+/// The [`SerialBridgeInterface`] only returns [`Io`](Self::Io) errors, the
+/// [`Interface`](Self::Interface) errors are returned by [`SerialBridgeDecoder::forward`].
+#[derive(Debug)]
+pub enum SerialBridgeError<E> {
+    /// Error of the serial link or an invalid frame
+    Io(io::Error),
+    /// Error of the interface the frames are forwarded to
+    Interface(E),
+}
+
+impl<E: Error> Error for SerialBridgeError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io(_) => ErrorKind::Bus,
+            Self::Interface(error) => error.kind(),
+        }
+    }
+}
+
+/// Decoder for the frames of a [`SerialBridgeInterface`]
+///
+/// Reads the frames from an [`io::Read`] implementation, e.g. the serial port which receives
+/// the frames. See [`SerialBridgeInterface`] for an example.
+///
+/// Requires the `std` feature.
+pub struct SerialBridgeDecoder<R> {
+    reader: R,
+}
+
+impl<R: io::Read> SerialBridgeDecoder<R> {
+    /// Creates a new decoder.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Consumes the decoder and returns the reader.
+    pub fn release(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next frame.
+    ///
+    /// Returns `None` if the reader reached the end of the stream between two frames. Frames
+    /// with a wrong start byte or an unknown kind are reported as [`io::ErrorKind::InvalidData`].
+    pub fn next_frame(&mut self) -> io::Result<Option<SerialBridgeFrame>> {
+        let mut header = [0; 4];
+        match self.reader.read_exact(&mut header[..1]) {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            result => result?,
+        }
+        self.reader.read_exact(&mut header[1..])?;
+
+        let [start, kind, len @ ..] = header;
+        if start != FRAME_START {
+            return Err(invalid_data("invalid frame start byte"));
+        }
+
+        let mut payload = std::vec![0; usize::from(u16::from_le_bytes(len))];
+        self.reader.read_exact(&mut payload)?;
+
+        match kind {
+            KIND_COMMAND => {
+                let Some((&instruction, params)) = payload.split_first() else {
+                    return Err(invalid_data("empty command frame"));
+                };
+                Ok(Some(SerialBridgeFrame::Command {
+                    instruction,
+                    params: params.to_vec(),
+                }))
+            }
+            KIND_PIXELS => Ok(Some(SerialBridgeFrame::Pixels(payload))),
+            _ => Err(invalid_data("unknown frame kind")),
+        }
+    }
+
+    /// Reads the next frame and sends it to `di`.
+    ///
+    /// Returns `false` if the reader reached the end of the stream.
+    pub fn forward<DI: Interface<Word = u8>>(
+        &mut self,
+        di: &mut DI,
+    ) -> Result<bool, SerialBridgeError<DI::Error>> {
+        match self.next_frame().map_err(SerialBridgeError::Io)? {
+            Some(SerialBridgeFrame::Command {
+                instruction,
+                params,
+            }) => di
+                .send_command(instruction, &params)
+                .map_err(SerialBridgeError::Interface)?,
+            Some(SerialBridgeFrame::Pixels(data)) => di
+                .send_pixels_from_buffer::<1>(&data)
+                .map_err(SerialBridgeError::Interface)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_encoded_and_decoded() {
+        let mut di = SerialBridgeInterface::new(Vec::new(), 4);
+        di.send_command(0x2A, &[0, 1, 0, 2]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 3).unwrap();
+
+        let data = di.release();
+        assert_eq!(data[..9], [0xA5, 0x01, 5, 0, 0x2A, 0, 1, 0, 2]);
+
+        let mut decoder = SerialBridgeDecoder::new(data.as_slice());
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.next_frame().unwrap() {
+            frames.push(frame);
+        }
+        assert_eq!(
+            frames,
+            [
+                SerialBridgeFrame::Command {
+                    instruction: 0x2A,
+                    params: std::vec![0, 1, 0, 2],
+                },
+                SerialBridgeFrame::Pixels(std::vec![0x12, 0x34, 0x12, 0x34]),
+                SerialBridgeFrame::Pixels(std::vec![0x12, 0x34]),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_frames_are_rejected() {
+        let mut decoder = SerialBridgeDecoder::new(&[0x00, 0x01, 0, 0][..]);
+        let error = decoder.next_frame().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        // Truncated frame
+        let mut decoder = SerialBridgeDecoder::new(&[0xA5, 0x02, 4, 0, 1][..]);
+        let error = decoder.next_frame().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
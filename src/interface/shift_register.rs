@@ -0,0 +1,164 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{InterfaceKind, OutputBus};
+
+/// Word of a [`ShiftRegisterBus`]
+///
+/// Implemented for [`u8`] (one shift register) and [`u16`] (two daisy-chained shift registers).
+pub trait ShiftRegisterWord: Copy + Eq + sealed::Sealed {
+    /// Interface kind of a [`ParallelInterface`](super::ParallelInterface) using a bus with this word.
+    const KIND: InterfaceKind;
+
+    /// Byte representation, in the order the bytes are shifted out.
+    type Bytes: AsRef<[u8]>;
+
+    /// Converts the word into bytes, the most significant byte is shifted out first.
+    fn to_bytes(self) -> Self::Bytes;
+}
+
+impl ShiftRegisterWord for u8 {
+    const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+    type Bytes = [u8; 1];
+
+    fn to_bytes(self) -> Self::Bytes {
+        [self]
+    }
+}
+
+impl ShiftRegisterWord for u16 {
+    const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+    type Bytes = [u8; 2];
+
+    fn to_bytes(self) -> Self::Bytes {
+        self.to_be_bytes()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+}
+
+/// An [`OutputBus`] implementation using 74HC595 shift registers
+///
+/// The data pins of the display are driven by one or two daisy-chained 74HC595 shift
+/// registers, which are connected to an SPI bus. This only requires three MCU pins for the
+/// data bus of an 8080 style display, at the expense of a lower transfer rate.
+///
+/// The chip select line of the `SpiDevice` must be connected to the storage register clock
+/// input (`RCLK`, sometimes called `ST_CP`) of all shift registers. The registers output the
+/// new value on the rising edge of the clock, which happens when chip select is deasserted at
+/// the end of each transfer. For 16-bit buses the first register in the chain drives the
+/// data pins D0 to D7.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::interface::{ParallelInterface, ShiftRegisterBus};
+///
+/// # let spi = mipidsi::_mock::MockSpi;
+/// # let dc = mipidsi::_mock::MockOutputPin;
+/// # let wr = mipidsi::_mock::MockOutputPin;
+/// let bus: ShiftRegisterBus<_, u16> = ShiftRegisterBus::new(spi);
+/// let di = ParallelInterface::new(bus, dc, wr);
+/// ```
+pub struct ShiftRegisterBus<SPI, W = u8> {
+    spi: SPI,
+    last: Option<W>,
+}
+
+impl<SPI, W> ShiftRegisterBus<SPI, W>
+where
+    SPI: SpiDevice,
+    W: ShiftRegisterWord,
+{
+    /// Creates a new bus. This does not change the outputs of the shift registers.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi, last: None }
+    }
+
+    /// Consumes the bus and returns the SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI, W> OutputBus for ShiftRegisterBus<SPI, W>
+where
+    SPI: SpiDevice,
+    W: ShiftRegisterWord,
+{
+    type Word = W;
+    type Error = SPI::Error;
+
+    const KIND: InterfaceKind = W::KIND;
+
+    fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+        // The outputs already have the right value, e.g. when filling the screen
+        if self.last == Some(value) {
+            return Ok(());
+        }
+
+        self.last = None;
+        self.spi.write(value.to_bytes().as_ref())?;
+        self.last = Some(value);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    use super::*;
+
+    /// SPI device which records the last transfer and counts the transfers.
+    #[derive(Default)]
+    struct RecordingSpi {
+        data: [u8; 2],
+        transfers: u32,
+    }
+
+    impl ErrorType for RecordingSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(data) = operation {
+                    self.data = [0; 2];
+                    self.data[..data.len()].copy_from_slice(data);
+                }
+            }
+            self.transfers += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn values_are_shifted_out() {
+        let mut bus = ShiftRegisterBus::<_, u8>::new(RecordingSpi::default());
+        bus.set_value(0x2A).unwrap();
+        assert_eq!(bus.spi.data, [0x2A, 0]);
+
+        let mut bus = ShiftRegisterBus::<_, u16>::new(RecordingSpi::default());
+        bus.set_value(0x1234).unwrap();
+        assert_eq!(bus.spi.data, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn unchanged_values_are_skipped() {
+        let mut bus = ShiftRegisterBus::<_, u16>::new(RecordingSpi::default());
+        bus.set_value(0x1234).unwrap();
+        bus.set_value(0x1234).unwrap();
+        bus.set_value(0x5678).unwrap();
+
+        assert_eq!(bus.release().transfers, 2);
+    }
+}
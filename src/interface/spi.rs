@@ -1,6 +1,9 @@
-use embedded_hal::{digital::OutputPin, spi::SpiDevice};
+use embedded_hal::{
+    digital::OutputPin,
+    spi::{Operation, SpiBus, SpiDevice},
+};
 
-use super::Interface;
+use super::{Error, ErrorKind, Event, Interface, InterfaceKind, InterfaceRead};
 
 /// Spi interface error
 #[derive(Clone, Copy, Debug)]
@@ -11,6 +14,15 @@ pub enum SpiError<SPI, DC> {
     Dc(DC),
 }
 
+impl<SPI: core::fmt::Debug, DC: core::fmt::Debug> Error for SpiError<SPI, DC> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(_) => ErrorKind::Bus,
+            Self::Dc(_) => ErrorKind::Pin,
+        }
+    }
+}
+
 /// Spi interface, including a buffer
 ///
 /// The buffer is used to gather batches of pixel data to be sent over SPI.
@@ -19,16 +31,33 @@ pub enum SpiError<SPI, DC> {
 ///
 /// You may want to use [static_cell](https://crates.io/crates/static_cell)
 /// to obtain a `&'static mut [u8; N]` buffer.
+///
+/// If the SPI clock frequency is set with [`clock_hz`](Self::clock_hz), the driver checks it
+/// against the [`MAX_SERIAL_CLOCK_HZ`](crate::models::Model::MAX_SERIAL_CLOCK_HZ) of the
+/// model during the initialization and panics in debug builds if the limit is exceeded.
 pub struct SpiInterface<'a, SPI, DC> {
     spi: SPI,
     dc: DC,
     buffer: &'a mut [u8],
+    clock_hz: Option<u32>,
 }
 
 impl<'a, SPI: SpiDevice, DC: OutputPin> SpiInterface<'a, SPI, DC> {
     /// Create new interface
     pub fn new(spi: SPI, dc: DC, buffer: &'a mut [u8]) -> Self {
-        Self { spi, dc, buffer }
+        Self {
+            spi,
+            dc,
+            buffer,
+            clock_hz: None,
+        }
+    }
+
+    /// Sets the SPI clock frequency, which is checked against the limit of the model.
+    #[must_use]
+    pub fn clock_hz(mut self, clock_hz: u32) -> Self {
+        self.clock_hz = Some(clock_hz);
+        self
     }
 }
 
@@ -36,6 +65,8 @@ impl<SPI: SpiDevice, DC: OutputPin> Interface for SpiInterface<'_, SPI, DC> {
     type Word = u8;
     type Error = SpiError<SPI::Error, DC::Error>;
 
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
     fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
         self.dc.set_low().map_err(SpiError::Dc)?;
         self.spi.write(&[command]).map_err(SpiError::Spi)?;
@@ -48,52 +79,777 @@ impl<SPI: SpiDevice, DC: OutputPin> Interface for SpiInterface<'_, SPI, DC> {
         &mut self,
         pixels: impl IntoIterator<Item = [Self::Word; N]>,
     ) -> Result<(), Self::Error> {
-        let mut arrays = pixels.into_iter();
-
-        assert!(self.buffer.len() >= N);
-
-        let mut done = false;
-        while !done {
-            let mut i = 0;
-            for chunk in self.buffer.chunks_exact_mut(N) {
-                if let Some(array) = arrays.next() {
-                    let chunk: &mut [u8; N] = chunk.try_into().unwrap();
-                    *chunk = array;
-                    i += N;
-                } else {
-                    done = true;
-                    break;
-                };
-            }
-            self.spi.write(&self.buffer[..i]).map_err(SpiError::Spi)?;
+        let spi = &mut self.spi;
+        write_pixels(self.buffer, pixels, |data| {
+            spi.write(data).map_err(SpiError::Spi)
+        })
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let spi = &mut self.spi;
+        write_repeated_pixel(self.buffer, pixel, count, |data| {
+            spi.write(data).map_err(SpiError::Spi)
+        })
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        // The data is sent directly, without copying it into the buffer
+        let len = data.len() - data.len() % N;
+        if len > 0 {
+            self.spi.write(&data[..len]).map_err(SpiError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        if let Event::ClockLimit(max_hz) = event {
+            check_clock_limit(self.clock_hz, max_hz);
+        }
+        Ok(())
+    }
+}
+
+/// Reads require a connected MISO line. The command and the read are sent in a single
+/// transaction, which keeps CS asserted.
+impl<SPI: SpiDevice, DC: OutputPin> InterfaceRead for SpiInterface<'_, SPI, DC> {
+    fn read_command(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        // DC is ignored by the controller while data is read
+        self.dc.set_low().map_err(SpiError::Dc)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[command]), Operation::Read(buffer)])
+            .map_err(SpiError::Spi)?;
+        self.dc.set_high().map_err(SpiError::Dc)?;
+        Ok(())
+    }
+}
+
+/// Spi bus interface error
+#[derive(Clone, Copy, Debug)]
+pub enum SpiBusError<SPI, DC, CS> {
+    /// SPI bus error
+    Spi(SPI),
+    /// Data/command pin error
+    Dc(DC),
+    /// Chip select pin error
+    Cs(CS),
+}
+
+impl<SPI: core::fmt::Debug, DC: core::fmt::Debug, CS: core::fmt::Debug> Error
+    for SpiBusError<SPI, DC, CS>
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(_) => ErrorKind::Bus,
+            Self::Dc(_) | Self::Cs(_) => ErrorKind::Pin,
+        }
+    }
+}
+
+/// Spi interface with a manually controlled chip select pin, including a buffer
+///
+/// Unlike [`SpiInterface`], which uses a separate [`SpiDevice`] transaction for every write,
+/// this interface keeps the chip select line asserted while the address window,
+/// `WriteMemoryStart` and the pixel data of a drawing operation are sent.
+/// Some display controllers, like certain ST7789 clones, corrupt the data if
+/// CS is deasserted in the middle of a memory write.
+///
+/// Because the chip select pin is controlled by the interface, the SPI bus
+/// can't be shared with other devices while the interface exists.
+///
+/// See [`SpiInterface`] for information about the buffer and the clock frequency check.
+pub struct SpiBusInterface<'a, SPI, DC, CS> {
+    spi: SPI,
+    dc: DC,
+    cs: CS,
+    buffer: &'a mut [u8],
+    in_transaction: bool,
+    clock_hz: Option<u32>,
+}
+
+impl<'a, SPI: SpiBus, DC: OutputPin, CS: OutputPin> SpiBusInterface<'a, SPI, DC, CS> {
+    /// Create new interface
+    pub fn new(spi: SPI, dc: DC, cs: CS, buffer: &'a mut [u8]) -> Self {
+        Self {
+            spi,
+            dc,
+            cs,
+            buffer,
+            in_transaction: false,
+            clock_hz: None,
+        }
+    }
+
+    /// Sets the SPI clock frequency, which is checked against the limit of the model.
+    #[must_use]
+    pub fn clock_hz(mut self, clock_hz: u32) -> Self {
+        self.clock_hz = Some(clock_hz);
+        self
+    }
+
+    /// Release the SPI bus and the pins
+    pub fn release(self) -> (SPI, DC, CS) {
+        (self.spi, self.dc, self.cs)
+    }
+
+    /// Asserts CS, unless a transaction is already in progress.
+    fn select(&mut self) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>> {
+        if !self.in_transaction {
+            self.cs.set_low().map_err(SpiBusError::Cs)?;
+        }
+        Ok(())
+    }
+
+    /// Deasserts CS, unless a transaction is in progress.
+    ///
+    /// CS is deasserted even if flushing the bus fails.
+    fn deselect(&mut self) -> Result<(), SpiBusError<SPI::Error, DC::Error, CS::Error>> {
+        if !self.in_transaction {
+            let flushed = self.spi.flush().map_err(SpiBusError::Spi);
+            self.cs.set_high().map_err(SpiBusError::Cs)?;
+            flushed?;
         }
         Ok(())
     }
 
+    /// Calls `f` with CS asserted.
+    ///
+    /// If `f` fails, CS is deasserted and a transaction in progress is ended, so that a single
+    /// failed write doesn't leave the bus selected for all following writes.
+    fn selected<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, SpiBusError<SPI::Error, DC::Error, CS::Error>>,
+    ) -> Result<R, SpiBusError<SPI::Error, DC::Error, CS::Error>> {
+        self.select()?;
+        match f(self) {
+            Ok(result) => {
+                self.deselect()?;
+                Ok(result)
+            }
+            Err(e) => {
+                self.in_transaction = false;
+                // The original error is more useful than a pin error
+                let _ = self.cs.set_high();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<SPI: SpiBus, DC: OutputPin, CS: OutputPin> Interface for SpiBusInterface<'_, SPI, DC, CS> {
+    type Word = u8;
+    type Error = SpiBusError<SPI::Error, DC::Error, CS::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.selected(|di| {
+            // DC must not change while the previous data is still being shifted out
+            di.spi.flush().map_err(SpiBusError::Spi)?;
+            di.dc.set_low().map_err(SpiBusError::Dc)?;
+            di.spi.write(&[command]).map_err(SpiBusError::Spi)?;
+            di.spi.flush().map_err(SpiBusError::Spi)?;
+            di.dc.set_high().map_err(SpiBusError::Dc)?;
+            di.spi.write(args).map_err(SpiBusError::Spi)
+        })
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.selected(|di| {
+            let spi = &mut di.spi;
+            write_pixels(di.buffer, pixels, |data| {
+                spi.write(data).map_err(SpiBusError::Spi)
+            })
+        })
+    }
+
     fn send_repeated_pixel<const N: usize>(
         &mut self,
         pixel: [Self::Word; N],
         count: u32,
     ) -> Result<(), Self::Error> {
-        let fill_count = core::cmp::min(count, (self.buffer.len() / N) as u32);
-        let filled_len = fill_count as usize * N;
-        for chunk in self.buffer[..(filled_len)].chunks_exact_mut(N) {
-            let chunk: &mut [u8; N] = chunk.try_into().unwrap();
-            *chunk = pixel;
+        self.selected(|di| {
+            let spi = &mut di.spi;
+            write_repeated_pixel(di.buffer, pixel, count, |data| {
+                spi.write(data).map_err(SpiBusError::Spi)
+            })
+        })
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        let len = data.len() - data.len() % N;
+        if len == 0 {
+            return Ok(());
         }
 
-        let mut count = count;
-        while count >= fill_count {
-            self.spi
-                .write(&self.buffer[..filled_len])
-                .map_err(SpiError::Spi)?;
-            count -= fill_count;
+        self.selected(|di| di.spi.write(&data[..len]).map_err(SpiBusError::Spi))
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        match event {
+            Event::BeginTransaction => {
+                self.select()?;
+                self.in_transaction = true;
+            }
+            Event::EndTransaction => {
+                self.in_transaction = false;
+                self.deselect()?;
+            }
+            Event::ClockLimit(max_hz) => check_clock_limit(self.clock_hz, max_hz),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Reads require a connected MISO line.
+impl<SPI: SpiBus, DC: OutputPin, CS: OutputPin> InterfaceRead for SpiBusInterface<'_, SPI, DC, CS> {
+    fn read_command(&mut self, command: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.selected(|di| {
+            di.spi.flush().map_err(SpiBusError::Spi)?;
+            di.dc.set_low().map_err(SpiBusError::Dc)?;
+            di.spi.write(&[command]).map_err(SpiBusError::Spi)?;
+            di.spi.read(buffer).map_err(SpiBusError::Spi)
+        })?;
+        self.dc.set_high().map_err(SpiBusError::Dc)
+    }
+}
+
+/// 3-wire Spi interface error
+#[derive(Clone, Copy, Debug)]
+pub enum Spi3WireError<SPI> {
+    /// SPI bus error
+    Spi(SPI),
+}
+
+impl<SPI: core::fmt::Debug> Error for Spi3WireError<SPI> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(_) => ErrorKind::Bus,
         }
-        if count != 0 {
+    }
+}
+
+/// 3-wire Spi interface without a data/command pin, including a buffer
+///
+/// Some breakout boards are wired for the 3-wire serial interface, which transfers 9-bit
+/// words with the data/command flag as the first bit of each word. This interface packs
+/// the 9-bit words into the bytes of a regular 8-bit [`SpiDevice`]. Each write contains
+/// the words for a command and its parameters or for as many whole pixels as fit into the
+/// buffer. Unused bits at the end of a write are set to zero and are ignored by the
+/// controller when CS is deasserted.
+///
+/// The buffer must be at least 9 bytes long, larger buffers reduce the number of writes.
+/// Commands with more parameters than fit into the buffer are split into multiple writes.
+/// See [`SpiInterface`] for information about the clock frequency check.
+pub struct Spi3WireInterface<'a, SPI> {
+    spi: SPI,
+    buffer: &'a mut [u8],
+    clock_hz: Option<u32>,
+}
+
+impl<'a, SPI: SpiDevice> Spi3WireInterface<'a, SPI> {
+    /// Create new interface
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is shorter than 9 bytes.
+    pub fn new(spi: SPI, buffer: &'a mut [u8]) -> Self {
+        assert!(buffer.len() >= 9);
+
+        Self {
+            spi,
+            buffer,
+            clock_hz: None,
+        }
+    }
+
+    /// Sets the SPI clock frequency, which is checked against the limit of the model.
+    #[must_use]
+    pub fn clock_hz(mut self, clock_hz: u32) -> Self {
+        self.clock_hz = Some(clock_hz);
+        self
+    }
+
+    /// Release the SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Packs the words into the buffer and writes them.
+    ///
+    /// `dc` is the data/command flag of the first word, all following words are data.
+    /// A new write is started if the next `N` words don't fit into the buffer.
+    fn write_words<const N: usize>(
+        &mut self,
+        mut dc: bool,
+        words: impl IntoIterator<Item = [u8; N]>,
+    ) -> Result<(), Spi3WireError<SPI::Error>> {
+        let capacity = self.buffer.len() * 8;
+        let mut bits = 0;
+
+        for chunk in words {
+            if bits + 9 * N > capacity {
+                self.spi
+                    .write(&self.buffer[..bits.div_ceil(8)])
+                    .map_err(Spi3WireError::Spi)?;
+                bits = 0;
+            }
+            for byte in chunk {
+                bits = push_word(self.buffer, bits, dc, byte);
+                dc = true;
+            }
+        }
+
+        if bits > 0 {
             self.spi
-                .write(&self.buffer[..(count as usize * pixel.len())])
-                .map_err(SpiError::Spi)?;
+                .write(&self.buffer[..bits.div_ceil(8)])
+                .map_err(Spi3WireError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+/// Stores a 9-bit word at the bit position `bits` and returns the new bit position.
+fn push_word(buffer: &mut [u8], bits: usize, dc: bool, byte: u8) -> usize {
+    let word = (u16::from(dc) << 8) | u16::from(byte);
+    for i in 0..9 {
+        let position = bits + i;
+        if position % 8 == 0 {
+            buffer[position / 8] = 0;
+        }
+        if word & (0x100 >> i) != 0 {
+            buffer[position / 8] |= 0x80 >> (position % 8);
+        }
+    }
+    bits + 9
+}
+
+impl<SPI: SpiDevice> Interface for Spi3WireInterface<'_, SPI> {
+    type Word = u8;
+    type Error = Spi3WireError<SPI::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial3Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        let command = [[command]].into_iter();
+        let args = args.iter().map(|arg| [*arg]);
+
+        // The flag of the first word is cleared for the command
+        self.write_words(false, command.chain(args))
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.write_words(true, pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.write_words(true, core::iter::repeat(pixel).take(count as usize))
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        if let Event::ClockLimit(max_hz) = event {
+            check_clock_limit(self.clock_hz, max_hz);
         }
         Ok(())
     }
 }
+
+/// Panics in debug builds if the clock frequency is known and exceeds `max_hz`.
+pub(super) fn check_clock_limit(clock_hz: Option<u32>, max_hz: u32) {
+    if let Some(clock_hz) = clock_hz {
+        debug_assert!(
+            clock_hz <= max_hz,
+            "the SPI clock of {clock_hz} Hz exceeds the maximum of {max_hz} Hz of the display \
+             controller, which can cause corrupted pixels"
+        );
+    }
+}
+
+/// Sends pixels in batches, using `buffer` to gather the pixel data.
+///
+/// Every write, except for the last one, contains as many whole pixels as fit into the buffer.
+pub(super) fn write_pixels<const N: usize, E>(
+    buffer: &mut [u8],
+    pixels: impl IntoIterator<Item = [u8; N]>,
+    mut write: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut arrays = pixels.into_iter();
+
+    assert!(buffer.len() >= N);
+
+    let mut done = false;
+    while !done {
+        let mut i = 0;
+        for chunk in buffer.chunks_exact_mut(N) {
+            if let Some(array) = arrays.next() {
+                let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+                *chunk = array;
+                i += N;
+            } else {
+                done = true;
+                break;
+            };
+        }
+        if i > 0 {
+            write(&buffer[..i])?;
+        }
+    }
+    Ok(())
+}
+
+/// Sends a pixel `count` times, using `buffer` to gather the pixel data.
+///
+/// Every write, except for the last one, contains as many whole pixels as fit into the buffer.
+pub(super) fn write_repeated_pixel<const N: usize, E>(
+    buffer: &mut [u8],
+    pixel: [u8; N],
+    count: u32,
+    mut write: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+    assert!(buffer.len() >= N);
+
+    if count == 0 {
+        return Ok(());
+    }
+
+    let fill_count = core::cmp::min(count, (buffer.len() / N) as u32);
+    let filled_len = fill_count as usize * N;
+    for chunk in buffer[..(filled_len)].chunks_exact_mut(N) {
+        let chunk: &mut [u8; N] = chunk.try_into().unwrap();
+        *chunk = pixel;
+    }
+
+    let mut count = count;
+    while count >= fill_count {
+        write(&buffer[..filled_len])?;
+        count -= fill_count;
+    }
+    if count != 0 {
+        write(&buffer[..(count as usize * pixel.len())])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    use embedded_hal::{digital, spi};
+
+    use crate::{
+        _mock::{MockDelay, MockOutputPin, MockSpi},
+        models::{ST7735s, GC9A01},
+        Builder,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct State {
+        cs_low: Cell<bool>,
+        selections: Cell<u32>,
+        writes: Cell<u32>,
+        fail_writes: Cell<bool>,
+    }
+
+    struct RecordingBus<'a>(&'a State);
+
+    impl spi::ErrorType for RecordingBus<'_> {
+        type Error = spi::ErrorKind;
+    }
+
+    impl SpiBus for RecordingBus<'_> {
+        fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+            assert!(self.0.cs_low.get(), "read while CS is deasserted");
+            // RDDID response of an ST7789 (85h 85h 52h), after a dummy clock cycle
+            let data = [0x42, 0xC2, 0xA9, 0x00];
+            words.copy_from_slice(&data[..words.len()]);
+            Ok(())
+        }
+
+        fn write(&mut self, _words: &[u8]) -> Result<(), Self::Error> {
+            assert!(self.0.cs_low.get(), "write while CS is deasserted");
+            if self.0.fail_writes.get() {
+                return Err(spi::ErrorKind::Other);
+            }
+            self.0.writes.set(self.0.writes.get() + 1);
+            Ok(())
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    struct RecordingCs<'a>(&'a State);
+
+    impl digital::ErrorType for RecordingCs<'_> {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for RecordingCs<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.cs_low.set(true);
+            self.0.selections.set(self.0.selections.get() + 1);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.cs_low.set(false);
+            Ok(())
+        }
+    }
+
+    fn interface<'a>(
+        state: &'a State,
+        buffer: &'a mut [u8],
+    ) -> SpiBusInterface<'a, RecordingBus<'a>, MockOutputPin, RecordingCs<'a>> {
+        SpiBusInterface::new(
+            RecordingBus(state),
+            MockOutputPin,
+            RecordingCs(state),
+            buffer,
+        )
+    }
+
+    #[test]
+    fn cs_per_write_without_transaction() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut di = interface(&state, &mut buffer);
+
+        di.send_command(0x2A, &[0, 0, 0, 9]).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_repeated_pixel([0x12, 0x34], 5).unwrap();
+
+        assert_eq!(state.selections.get(), 3);
+        assert!(!state.cs_low.get());
+    }
+
+    #[test]
+    fn cs_held_during_transaction() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut di = interface(&state, &mut buffer);
+
+        di.handle_event(Event::BeginTransaction).unwrap();
+        di.send_command(0x2A, &[0, 0, 0, 9]).unwrap();
+        di.send_command(0x2B, &[0, 0, 0, 0]).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[0x12, 0x34]; 10]).unwrap();
+        assert!(state.cs_low.get());
+        di.handle_event(Event::EndTransaction).unwrap();
+
+        assert_eq!(state.selections.get(), 1);
+        assert!(!state.cs_low.get());
+    }
+
+    #[test]
+    fn cs_is_deasserted_after_errors() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut di = interface(&state, &mut buffer);
+
+        di.handle_event(Event::BeginTransaction).unwrap();
+        di.send_command(0x2C, &[]).unwrap();
+        state.fail_writes.set(true);
+        assert!(di.send_pixels([[0x12, 0x34]; 10]).is_err());
+        assert!(!state.cs_low.get());
+
+        // The failed transaction was ended, so the next write selects the bus again
+        state.fail_writes.set(false);
+        di.send_command(0x2C, &[]).unwrap();
+        assert_eq!(state.selections.get(), 2);
+        assert!(!state.cs_low.get());
+    }
+
+    #[test]
+    fn display_transaction_is_ended_after_errors() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut display = Builder::new(ST7735s, interface(&state, &mut buffer))
+            .init(&mut MockDelay)
+            .unwrap();
+        let area = Rectangle::new(Point::zero(), Size::new(10, 10));
+
+        state.fail_writes.set(true);
+        assert!(display.fill_solid(&area, Rgb565::RED).is_err());
+        assert!(!state.cs_low.get());
+
+        state.fail_writes.set(false);
+        let selections = state.selections.get();
+        display.fill_solid(&area, Rgb565::RED).unwrap();
+        assert_eq!(state.selections.get(), selections + 1);
+        assert!(!state.cs_low.get());
+    }
+
+    #[test]
+    fn buffer_is_sent_in_a_single_write() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut di = interface(&state, &mut buffer);
+
+        di.send_command(0x2C, &[]).unwrap();
+        state.writes.set(0);
+        // The incomplete pixel at the end is ignored
+        di.send_pixels_from_buffer::<2>(&[0x12; 21]).unwrap();
+        di.send_pixels_from_buffer::<2>(&[0x12; 1]).unwrap();
+
+        assert_eq!(state.writes.get(), 1);
+        assert!(!state.cs_low.get());
+    }
+
+    #[test]
+    fn display_id_is_read_without_dummy_bit() {
+        let state = State::default();
+        let mut buffer = [0; 4];
+        let mut display = Builder::new(ST7735s, interface(&state, &mut buffer))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(display.read_display_id().unwrap(), [0x85, 0x85, 0x52]);
+        assert!(!state.cs_low.get());
+    }
+
+    /// SpiDevice which records the last write.
+    #[derive(Default)]
+    struct RecordingSpi {
+        data: [u8; 16],
+        len: usize,
+        writes: u32,
+    }
+
+    impl spi::ErrorType for RecordingSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for RecordingSpi {
+        fn transaction(
+            &mut self,
+            operations: &mut [spi::Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let spi::Operation::Write(data) = operation {
+                    self.data[..data.len()].copy_from_slice(data);
+                    self.len = data.len();
+                    self.writes += 1;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn three_wire_words_are_packed() {
+        let mut buffer = [0; 9];
+        let mut di = Spi3WireInterface::new(RecordingSpi::default(), &mut buffer);
+
+        // 0 00101010, 1 00000000, 1 00000001 and 5 padding bits
+        di.send_command(0x2A, &[0x00, 0x01]).unwrap();
+        assert_eq!(di.spi.data[..di.spi.len], [0x15, 0x40, 0x20, 0x20]);
+
+        // 4 pixels with 18 bits each fill the buffer
+        di.spi.writes = 0;
+        di.send_repeated_pixel([0xFF, 0x00], 5).unwrap();
+        assert_eq!(di.spi.writes, 2);
+        assert_eq!(di.spi.data[..di.spi.len], [0xFF, 0xC0, 0x00]);
+    }
+
+    // Calls `f` with a write function and returns the lengths of all writes.
+    fn write_lengths(
+        f: impl FnOnce(&mut dyn FnMut(&[u8]) -> Result<(), Infallible>),
+    ) -> [usize; 4] {
+        let mut lengths = [0; 4];
+        let mut writes = 0;
+        f(&mut |data: &[u8]| {
+            lengths[writes] = data.len();
+            writes += 1;
+            Ok(())
+        });
+        lengths
+    }
+
+    #[test]
+    fn pixels_are_sent_in_maximal_chunks() {
+        let mut buffer = [0; 8];
+
+        let lengths = write_lengths(|write| {
+            write_pixels(&mut buffer, [[0x12, 0x34]; 10], write).unwrap();
+        });
+        assert_eq!(lengths, [8, 8, 4, 0]);
+
+        // Only whole pixels are written and no empty write is sent at the end.
+        let lengths = write_lengths(|write| {
+            write_pixels(&mut buffer, [[0x12, 0x34, 0x56]; 4], write).unwrap();
+        });
+        assert_eq!(lengths, [6, 6, 0, 0]);
+    }
+
+    #[test]
+    fn repeated_pixels_are_sent_in_maximal_chunks() {
+        let mut buffer = [0; 8];
+
+        let lengths = write_lengths(|write| {
+            write_repeated_pixel(&mut buffer, [0x12, 0x34], 10, write).unwrap();
+        });
+        assert_eq!(lengths, [8, 8, 4, 0]);
+
+        let lengths = write_lengths(|write| {
+            write_repeated_pixel(&mut buffer, [0x12, 0x34, 0x56], 4, write).unwrap();
+        });
+        assert_eq!(lengths, [6, 6, 0, 0]);
+
+        let lengths = write_lengths(|write| {
+            write_repeated_pixel(&mut buffer, [0x12, 0x34], 0, write).unwrap();
+        });
+        assert_eq!(lengths, [0; 4]);
+    }
+
+    #[test]
+    fn clock_within_limit() {
+        let mut buffer = [0; 16];
+        let di = SpiInterface::new(MockSpi, MockOutputPin, &mut buffer).clock_hz(10_000_000);
+        Builder::new(ST7735s, di).init(&mut MockDelay).unwrap();
+
+        // The clock isn't checked for models without a known limit
+        let di = SpiInterface::new(MockSpi, MockOutputPin, &mut buffer).clock_hz(80_000_000);
+        Builder::new(GC9A01, di).init(&mut MockDelay).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "the SPI clock of 40000000 Hz exceeds the maximum of 15000000 Hz")]
+    fn overclocked_spi_panics() {
+        let mut buffer = [0; 16];
+        let di = SpiInterface::new(MockSpi, MockOutputPin, &mut buffer).clock_hz(40_000_000);
+        Builder::new(ST7735s, di).init(&mut MockDelay).unwrap();
+    }
+}
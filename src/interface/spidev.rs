@@ -0,0 +1,213 @@
+use embedded_hal::digital::OutputPin;
+use spidev::{SpiModeFlags, Spidev, SpidevOptions};
+use std::{fs, io, io::Write, path::Path, vec, vec::Vec};
+
+use super::{
+    check_clock_limit, write_pixels, write_repeated_pixel, Error, ErrorKind, Event, Interface,
+    InterfaceKind,
+};
+
+/// Transfer size which is used if the `bufsiz` parameter of the spidev driver can't be read.
+const DEFAULT_TRANSFER_SIZE: usize = 4096;
+
+/// Spidev interface error
+#[derive(Debug)]
+pub enum SpidevError<DC> {
+    /// Spidev device error
+    Spi(io::Error),
+    /// Data/command pin error
+    Dc(DC),
+}
+
+impl<DC: core::fmt::Debug> Error for SpidevError<DC> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(_) => ErrorKind::Bus,
+            Self::Dc(_) => ErrorKind::Pin,
+        }
+    }
+}
+
+/// SPI interface for Linux `spidev` devices
+///
+/// Every write to a spidev device is a separate system call, which makes the
+/// [`SpiInterface`](super::SpiInterface) with a small buffer slow on hosts like the
+/// Raspberry Pi. This interface owns a buffer with the maximum transfer size of the spidev
+/// driver and gathers the pixel data of a memory write into as few transfers as possible.
+/// Raw pixel data from [`Display::set_pixels_from_buffer`](crate::Display::set_pixels_from_buffer)
+/// is submitted directly in chunks of this size, without copying it.
+///
+/// [`open`](Self::open) reads the transfer size from the `bufsiz` parameter of the spidev
+/// kernel module, which is 4096 bytes by default. A larger limit, e.g. with
+/// `spidev.bufsiz=65536` on the kernel command line, reduces the number of transfers further.
+///
+/// Requires the `spidev` feature, which is only available on Linux.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mipidsi::{interface::SpidevInterface, models::ST7789, Builder};
+///
+/// # let dc = mipidsi::_mock::MockOutputPin;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let di = SpidevInterface::open("/dev/spidev0.1", dc, 60_000_000).unwrap();
+/// let mut display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct SpidevInterface<DC> {
+    spidev: Spidev,
+    dc: DC,
+    buffer: Vec<u8>,
+    clock_hz: Option<u32>,
+}
+
+impl<DC: OutputPin> SpidevInterface<DC> {
+    /// Opens and configures the spidev device at `path` for SPI mode 0 with the given clock
+    /// frequency.
+    ///
+    /// The transfer size is read from `/sys/module/spidev/parameters/bufsiz`.
+    pub fn open(path: impl AsRef<Path>, dc: DC, clock_hz: u32) -> io::Result<Self> {
+        let mut spidev = Spidev::open(path)?;
+        spidev.configure(
+            &SpidevOptions::new()
+                .bits_per_word(8)
+                .max_speed_hz(clock_hz)
+                .mode(SpiModeFlags::SPI_MODE_0)
+                .build(),
+        )?;
+
+        let transfer_size = fs::read_to_string("/sys/module/spidev/parameters/bufsiz")
+            .ok()
+            .and_then(|bufsiz| bufsiz.trim().parse().ok())
+            .unwrap_or(DEFAULT_TRANSFER_SIZE);
+
+        Ok(Self::new(spidev, dc, transfer_size).clock_hz(clock_hz))
+    }
+
+    /// Creates a new interface for an already configured spidev device.
+    ///
+    /// `transfer_size` must not exceed the `bufsiz` parameter of the spidev driver.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `transfer_size` is smaller than 4 bytes.
+    pub fn new(spidev: Spidev, dc: DC, transfer_size: usize) -> Self {
+        assert!(transfer_size >= 4);
+
+        Self {
+            spidev,
+            dc,
+            buffer: vec![0; transfer_size],
+            clock_hz: None,
+        }
+    }
+
+    /// Sets the SPI clock frequency, which is checked against the limit of the model.
+    #[must_use]
+    pub fn clock_hz(mut self, clock_hz: u32) -> Self {
+        self.clock_hz = Some(clock_hz);
+        self
+    }
+
+    /// Returns the maximum number of bytes which are sent in a single transfer.
+    pub fn transfer_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Consumes the interface and returns the spidev device and the data/command pin.
+    pub fn release(self) -> (Spidev, DC) {
+        (self.spidev, self.dc)
+    }
+}
+
+impl<DC: OutputPin> Interface for SpidevInterface<DC> {
+    type Word = u8;
+    type Error = SpidevError<DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpidevError::Dc)?;
+        self.spidev
+            .write_all(&[command])
+            .map_err(SpidevError::Spi)?;
+        self.dc.set_high().map_err(SpidevError::Dc)?;
+        if !args.is_empty() {
+            self.spidev.write_all(args).map_err(SpidevError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let spidev = &mut self.spidev;
+        write_pixels(&mut self.buffer, pixels, |data| {
+            spidev.write_all(data).map_err(SpidevError::Spi)
+        })
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let spidev = &mut self.spidev;
+        write_repeated_pixel(&mut self.buffer, pixel, count, |data| {
+            spidev.write_all(data).map_err(SpidevError::Spi)
+        })
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        // Whole pixels are sent directly, in chunks of the transfer size
+        let chunk_len = self.buffer.len() - self.buffer.len() % N;
+        let len = data.len() - data.len() % N;
+        for chunk in data[..len].chunks(chunk_len) {
+            self.spidev.write_all(chunk).map_err(SpidevError::Spi)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        if let Event::ClockLimit(max_hz) = event {
+            check_clock_limit(self.clock_hz, max_hz);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, path::PathBuf};
+
+    use crate::_mock::MockOutputPin;
+
+    use super::*;
+
+    #[test]
+    fn data_is_written_to_the_device() {
+        // Regular files accept the same writes as spidev devices
+        let path: PathBuf =
+            std::env::temp_dir().join(std::format!("mipidsi-spidev-{}", std::process::id()));
+        let spidev = Spidev::new(File::create(&path).unwrap());
+        let mut di = SpidevInterface::new(spidev, MockOutputPin, 5);
+        assert_eq!(di.transfer_size(), 5);
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels([[1, 2], [3, 4], [5, 6]]).unwrap();
+        di.send_repeated_pixel([7, 8], 3).unwrap();
+        di.send_pixels_from_buffer::<2>(&[9, 10, 11, 12, 13])
+            .unwrap();
+        drop(di);
+
+        let data = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            data,
+            [0x2C, 1, 2, 3, 4, 5, 6, 7, 8, 7, 8, 7, 8, 9, 10, 11, 12]
+        );
+    }
+}
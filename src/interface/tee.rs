@@ -0,0 +1,203 @@
+use super::{Error, ErrorKind, Event, Interface, InterfaceKind};
+
+/// Tee interface error
+#[derive(Clone, Copy, Debug)]
+pub enum TeeError<A, B> {
+    /// Error caused by the first interface
+    First(A),
+    /// Error caused by the second interface
+    Second(B),
+}
+
+impl<A: Error, B: Error> Error for TeeError<A, B> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::First(error) => error.kind(),
+            Self::Second(error) => error.kind(),
+        }
+    }
+}
+
+/// Number of pixels which are forwarded to both interfaces at once
+const CHUNK_SIZE: usize = 32;
+
+/// Interface which mirrors all commands and pixel data to two interfaces
+///
+/// This can be used to send the output to a physical display and to a capture or simulator
+/// interface at the same time, e.g. for debugging or to record a demo. The first interface
+/// is the primary interface, which defines the [`KIND`](Interface::KIND) used by the model
+/// and should be the physical display.
+///
+/// The pixel iterator of [`send_pixels`](Interface::send_pixels) can only be consumed once,
+/// so the pixels are forwarded to both interfaces in chunks of 32 pixels. This reduces the
+/// size of the transfers of buffered interfaces, like [`SpiInterface`](super::SpiInterface).
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::TeeInterface, models::ST7789, Builder};
+///
+/// # let panel = mipidsi::_mock::MockDisplayInterface;
+/// # let capture = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let di = TeeInterface::new(panel, capture);
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// ```
+pub struct TeeInterface<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> TeeInterface<A, B>
+where
+    A: Interface,
+    B: Interface<Word = A::Word>,
+{
+    /// Creates a new interface, which forwards everything to `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Consumes the interface and returns both interfaces.
+    pub fn release(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Interface for TeeInterface<A, B>
+where
+    A: Interface,
+    B: Interface<Word = A::Word>,
+{
+    type Word = A::Word;
+    type Error = TeeError<A::Error, B::Error>;
+
+    const KIND: InterfaceKind = A::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.first
+            .send_command(command, args)
+            .map_err(TeeError::First)?;
+        self.second
+            .send_command(command, args)
+            .map_err(TeeError::Second)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter();
+
+        while let Some(first) = pixels.next() {
+            let mut chunk = [first; CHUNK_SIZE];
+            let mut len = 1;
+            for (slot, pixel) in chunk[1..].iter_mut().zip(pixels.by_ref()) {
+                *slot = pixel;
+                len += 1;
+            }
+
+            let chunk = &chunk[..len];
+            self.first
+                .send_pixels(chunk.iter().copied())
+                .map_err(TeeError::First)?;
+            self.second
+                .send_pixels(chunk.iter().copied())
+                .map_err(TeeError::Second)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.first
+            .send_repeated_pixel(pixel, count)
+            .map_err(TeeError::First)?;
+        self.second
+            .send_repeated_pixel(pixel, count)
+            .map_err(TeeError::Second)
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.first
+            .send_pixels_from_buffer::<N>(data)
+            .map_err(TeeError::First)?;
+        self.second
+            .send_pixels_from_buffer::<N>(data)
+            .map_err(TeeError::Second)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.first.handle_event(event).map_err(TeeError::First)?;
+        self.second.handle_event(event).map_err(TeeError::Second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+
+    /// Interface which counts the commands and sums up the pixel data.
+    #[derive(Default)]
+    struct SummingInterface {
+        commands: u32,
+        pixels: u32,
+        sum: u32,
+    }
+
+    impl Interface for SummingInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            self.commands += 1;
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            for pixel in pixels {
+                self.pixels += 1;
+                self.sum += pixel.iter().map(|&word| u32::from(word)).sum::<u32>();
+            }
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.send_pixels((0..count).map(|_| pixel))
+        }
+    }
+
+    #[test]
+    fn everything_is_mirrored() {
+        let mut di = TeeInterface::new(SummingInterface::default(), SummingInterface::default());
+
+        di.send_command(0x2C, &[]).unwrap();
+        di.send_pixels((0..100u8).map(|i| [i, 1])).unwrap();
+        di.send_repeated_pixel([2, 3], 10).unwrap();
+
+        let (first, second) = di.release();
+        for di in [first, second] {
+            assert_eq!(di.commands, 1);
+            assert_eq!(di.pixels, 110);
+            assert_eq!(di.sum, (0..100).sum::<u32>() + 100 + 50);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use embedded_hal::delay::DelayNs;
+
+use super::{Event, Interface, InterfaceKind};
+
+/// Interface wrapper which limits the pixel write rate
+///
+/// AMOLED panels draw more current for bright pixels and can brown out on weak supplies if
+/// large bright areas are written at full speed. This interface splits the pixel data into
+/// chunks of at most `max_pixels_per_ms` pixels and waits for one millisecond after each
+/// chunk, which limits the rate to `max_pixels_per_ms` pixels per millisecond. The time
+/// which is needed for the transfers isn't taken into account, so the actual rate is lower.
+///
+/// Commands are forwarded without delays.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::ThrottledInterface, models::RM67162, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// # let throttle_delay = mipidsi::_mock::MockDelay;
+/// let di = ThrottledInterface::new(di, throttle_delay, 2000);
+/// let display = Builder::new(RM67162, di).init(&mut delay).unwrap();
+/// ```
+pub struct ThrottledInterface<DI, D> {
+    di: DI,
+    delay: D,
+    max_pixels_per_ms: u32,
+}
+
+impl<DI, D> ThrottledInterface<DI, D>
+where
+    DI: Interface,
+    D: DelayNs,
+{
+    /// Creates a new throttled interface, which sends at most `max_pixels_per_ms` pixels
+    /// per millisecond.
+    ///
+    /// A limit of zero is treated as one pixel per millisecond.
+    pub fn new(di: DI, delay: D, max_pixels_per_ms: u32) -> Self {
+        Self {
+            di,
+            delay,
+            max_pixels_per_ms: max_pixels_per_ms.max(1),
+        }
+    }
+
+    /// Consumes the throttled interface and returns the wrapped interface and the delay.
+    pub fn release(self) -> (DI, D) {
+        (self.di, self.delay)
+    }
+}
+
+impl<DI, D> Interface for ThrottledInterface<DI, D>
+where
+    DI: Interface,
+    D: DelayNs,
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.di.send_command(command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        let mut pixels = pixels.into_iter().peekable();
+        while pixels.peek().is_some() {
+            self.di
+                .send_pixels((&mut pixels).take(self.max_pixels_per_ms as usize))?;
+            self.delay.delay_ms(1);
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        let mut remaining = count;
+        while remaining > 0 {
+            let chunk = remaining.min(self.max_pixels_per_ms);
+            self.di.send_repeated_pixel(pixel, chunk)?;
+            self.delay.delay_ms(1);
+            remaining -= chunk;
+        }
+        Ok(())
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        let len = data.len() - data.len() % N;
+        let chunk_len = (self.max_pixels_per_ms as usize).saturating_mul(N);
+        for chunk in data[..len].chunks(chunk_len.max(1)) {
+            self.di.send_pixels_from_buffer::<N>(chunk)?;
+            self.delay.delay_ms(1);
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.di.handle_event(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::_test::RecordingInterface;
+
+    use super::*;
+
+    // Delay which counts the waited milliseconds.
+    struct CountingDelay<'a>(&'a Cell<u32>);
+
+    impl DelayNs for CountingDelay<'_> {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0.set(self.0.get() + ns / 1_000_000);
+        }
+    }
+
+    #[test]
+    fn pixels_are_sent_in_limited_chunks() {
+        let ms = Cell::new(0);
+        let mut di =
+            ThrottledInterface::new(RecordingInterface::default(), CountingDelay(&ms), 100);
+
+        di.send_pixels([[0x12, 0x34]; 250]).unwrap();
+        assert_eq!(ms.get(), 3);
+        di.send_repeated_pixel([0x12, 0x34], 1000).unwrap();
+        assert_eq!(ms.get(), 13);
+        di.send_pixels_from_buffer::<2>(&[0x12; 401]).unwrap();
+        assert_eq!(ms.get(), 15);
+
+        let (di, _) = di.release();
+        assert_eq!(di.pixels, 1450);
+        assert_eq!(di.max_pixels, 100);
+    }
+}
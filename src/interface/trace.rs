@@ -0,0 +1,264 @@
+use super::{Event, Interface, InterfaceKind};
+
+/// Interface wrapper which reports every command to a trace hook
+///
+/// The hook is called with the instruction code and parameters of each command before it
+/// is passed on to the wrapped interface. This includes the commands sent by the model
+/// init sequence and makes it possible to debug a blank screen with a debug probe and a
+/// logger instead of a logic analyzer. Pixel data isn't reported.
+///
+/// With the `trace` feature the commands are recorded with `log` or `defmt` without this
+/// wrapper. The hook doesn't depend on a specific logging framework, e.g. it can forward the
+/// commands to `log` with `|cmd, params| log::trace!("{cmd:02X} {params:02X?}")` or to
+/// `defmt` with `|cmd, params| defmt::trace!("{=u8:02X} {=[u8]:02X}", cmd, params)`.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{interface::TracingInterface, models::ST7789, Builder};
+///
+/// # let di = mipidsi::_mock::MockDisplayInterface;
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let mut commands = 0;
+/// let di = TracingInterface::new(di, |_instruction, _params| commands += 1);
+///
+/// let display = Builder::new(ST7789, di).init(&mut delay).unwrap();
+/// let (di, _model, _rst, _bl) = display.release();
+/// drop(di);
+///
+/// assert!(commands > 0);
+/// ```
+pub struct TracingInterface<DI, F> {
+    di: DI,
+    hook: F,
+}
+
+impl<DI, F> TracingInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(u8, &[u8]),
+{
+    /// Creates a new tracing interface, which calls `hook` for every command sent to `di`.
+    pub fn new(di: DI, hook: F) -> Self {
+        Self { di, hook }
+    }
+
+    /// Consumes the tracing interface and returns the wrapped interface and the hook.
+    pub fn release(self) -> (DI, F) {
+        (self.di, self.hook)
+    }
+}
+
+impl<DI, F> Interface for TracingInterface<DI, F>
+where
+    DI: Interface,
+    F: FnMut(u8, &[u8]),
+{
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        (self.hook)(command, args);
+        self.di.send_command(command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels(pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels_from_buffer::<N>(data)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.di.handle_event(event)
+    }
+}
+
+/// A recorded command
+///
+/// Command records store the instruction code and the parameters of a command, which makes
+/// it possible to replay a trace captured with [`TracingInterface`] with [`replay`], e.g.
+/// against a simulator or a different panel to find the command that causes a mismatch.
+///
+/// Records can be serialized into a compact binary format with [`encode`](Self::encode),
+/// which consists of the instruction code, the number of parameters and the parameters
+/// themselves. A sequence of encoded records can be decoded again with
+/// [`decode`](Self::decode), e.g. after transferring it from the device with a debug probe.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::interface::{replay, CommandRecord};
+///
+/// # let mut di = mipidsi::_mock::MockDisplayInterface;
+/// const TRACE: &[CommandRecord] = &[
+///     CommandRecord::new(0x36, &[0x60]),
+///     CommandRecord::new(0x21, &[]),
+///     CommandRecord::new(0x29, &[]),
+/// ];
+///
+/// replay(&mut di, TRACE).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandRecord<'a> {
+    instruction: u8,
+    params: &'a [u8],
+}
+
+impl<'a> CommandRecord<'a> {
+    /// Creates a new command record.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `params` contains more than 255 bytes.
+    pub const fn new(instruction: u8, params: &'a [u8]) -> Self {
+        assert!(params.len() <= u8::MAX as usize);
+        Self {
+            instruction,
+            params,
+        }
+    }
+
+    /// Returns the instruction code.
+    pub const fn instruction(&self) -> u8 {
+        self.instruction
+    }
+
+    /// Returns the parameters.
+    pub const fn params(&self) -> &'a [u8] {
+        self.params
+    }
+
+    /// Returns the length of the encoded record in bytes.
+    pub const fn encoded_len(&self) -> usize {
+        self.params.len() + 2
+    }
+
+    /// Encodes the record into `buffer`.
+    ///
+    /// Returns the number of written bytes or `None` if the buffer is too small.
+    pub fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        let len = self.encoded_len();
+        let buffer = buffer.get_mut(..len)?;
+        buffer[0] = self.instruction;
+        buffer[1] = self.params.len() as u8;
+        buffer[2..].copy_from_slice(self.params);
+        Some(len)
+    }
+
+    /// Decodes the first record in `bytes`.
+    ///
+    /// Returns the record and the remaining bytes or `None` if `bytes` doesn't start with
+    /// a complete record.
+    pub fn decode(bytes: &'a [u8]) -> Option<(Self, &'a [u8])> {
+        let (&instruction, rest) = bytes.split_first()?;
+        let (&len, rest) = rest.split_first()?;
+        let params = rest.get(..usize::from(len))?;
+        let rest = &rest[params.len()..];
+        Some((Self::new(instruction, params), rest))
+    }
+}
+
+/// Sends the recorded commands to an interface.
+///
+/// The commands are sent in order without any delays between them. Traces which contain
+/// reset or sleep mode commands should therefore be split into multiple parts when they are
+/// replayed on a physical display, with the required delays in between.
+pub fn replay<DI: Interface>(di: &mut DI, records: &[CommandRecord<'_>]) -> Result<(), DI::Error> {
+    for record in records {
+        di.send_command(record.instruction, record.params)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::{_mock::MockDisplayInterface, dcs::InterfaceExt};
+
+    use super::*;
+
+    #[test]
+    fn commands_are_traced() {
+        let last = Cell::new((0, [0; 4], 0));
+        let mut di = TracingInterface::new(MockDisplayInterface, |cmd, params: &[u8]| {
+            let mut buf = [0; 4];
+            buf[..params.len()].copy_from_slice(params);
+            last.set((cmd, buf, params.len()));
+        });
+
+        di.write_raw(0x2A, &[0, 1, 0, 2]).unwrap();
+        assert_eq!(last.get(), (0x2A, [0, 1, 0, 2], 4));
+
+        di.send_repeated_pixel([0xFFu8; 2], 10).unwrap();
+        assert_eq!(last.get().0, 0x2A);
+
+        di.write_raw(0x29, &[]).unwrap();
+        assert_eq!(last.get(), (0x29, [0; 4], 0));
+    }
+
+    #[test]
+    fn recorded_commands_are_replayed() {
+        let mut log = [0; 64];
+        let mut len = 0;
+        {
+            let mut di = TracingInterface::new(MockDisplayInterface, |cmd, params: &[u8]| {
+                len += CommandRecord::new(cmd, params)
+                    .encode(&mut log[len..])
+                    .unwrap();
+            });
+            di.write_raw(0x36, &[0x60]).unwrap();
+            di.write_raw(0x2A, &[0, 1, 0, 2]).unwrap();
+            di.write_raw(0x29, &[]).unwrap();
+        }
+        assert_eq!(len, 3 + 6 + 2);
+
+        let mut records = [CommandRecord::new(0, &[]); 3];
+        let mut bytes = &log[..len];
+        for record in &mut records {
+            (*record, bytes) = CommandRecord::decode(bytes).unwrap();
+        }
+        assert!(bytes.is_empty());
+        assert_eq!(records[1], CommandRecord::new(0x2A, &[0, 1, 0, 2]));
+
+        let mut commands = [(0, 0); 3];
+        let mut count = 0;
+        {
+            let mut di = TracingInterface::new(MockDisplayInterface, |cmd, params: &[u8]| {
+                commands[count] = (cmd, params.len());
+                count += 1;
+            });
+            replay(&mut di, &records).unwrap();
+        }
+        assert_eq!(commands, [(0x36, 1), (0x2A, 4), (0x29, 0)]);
+    }
+
+    #[test]
+    fn incomplete_records_are_not_decoded() {
+        assert_eq!(CommandRecord::decode(&[]), None);
+        assert_eq!(CommandRecord::decode(&[0x36]), None);
+        assert_eq!(CommandRecord::decode(&[0x2A, 4, 0, 1, 0]), None);
+        assert_eq!(
+            CommandRecord::decode(&[0x29, 0, 0x2C]),
+            Some((CommandRecord::new(0x29, &[]), &[0x2C][..]))
+        );
+    }
+}
@@ -11,18 +11,55 @@
 //! available:
 //! - SPI ([`interface::SpiInterface`])
 //! - 8080 style parallel via GPIO ([`interface::ParallelInterface`])
+//! - I2C, directly or through an I2C-to-SPI bridge ([`interface::I2cInterface`])
+//!
+//! The interface traits are defined in the `mipidsi-interface-core` crate, which only changes
+//! when the traits change. HAL crates can depend on it to provide their own interfaces, e.g.
+//! with DMA transfers.
 //!
 //! An optional batching of draws is supported via the `batch` feature (default on)
 //!
+//! The `alloc` feature is deprecated and has no effect, the batched pixels are streamed to
+//! the display without buffering them.
+//!
+//! The buffers of the SPI interfaces are borrowed slices, so on targets with a heap, e.g.
+//! Linux hosts like the Raspberry Pi, they can be allocated at runtime with `vec![0; size]`.
+//!
+//! The `std` feature enables the `interface::CaptureToImage` interface, which saves the
+//! drawn content as PNG images on hosts with a file system, and the
+//! `interface::SerialBridgeInterface`, which sends the commands and pixel data to a panel
+//! attached to a different device over a serial link.
+//!
+//! The `spidev` feature enables the `interface::SpidevInterface` on Linux, which sends the
+//! pixel data to `spidev` devices in transfers of the maximum size of the spidev driver.
+//!
+//! The `trace` feature records every command and the steps of the display initialization
+//! as trace records with `log` and/or `defmt`, depending on which of these optional
+//! dependencies is enabled, e.g. with `features = ["trace", "defmt"]`.
+//!
+//! The `rtic` feature enables the `rtic` module with a lock-free mailbox for
+//! flush requests from interrupt handlers.
+//!
+//! The `display-interface` feature enables the `interface::CompatInterface` adapter, which
+//! allows `display-interface` 0.5 implementations to be used while migrating to the builtin
+//! interfaces.
+//!
 //! ### List of supported models
 //!
 //! * GC9107
+//! * GC9307
 //! * GC9A01
+//! * GC9A01A
+//! * HX8347
+//! * ILI9327
 //! * ILI9341
 //! * ILI9342C
 //! * ILI9486
+//! * ILI9488
+//! * LG4573A
 //! * RM67162
-//! * ST7735
+//! * ST7365P
+//! * ST7735 (ST7735S, ST7735R and ST7735B)
 //! * ST7789
 //! * ST7796
 //!
@@ -106,38 +143,88 @@ use dcs::InterfaceExt;
 
 pub mod interface;
 
+use embedded_graphics_core::{geometry::Dimensions, primitives::Rectangle};
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::OutputPin;
 
 pub mod options;
-use interface::InterfacePixelFormat;
-use options::MemoryMapping;
+use interface::{Event, InterfacePixelFormat};
 
 mod builder;
-pub use builder::{Builder, NoResetPin};
+pub use builder::{Builder, ConfigurationWarning, InitError, InitHook, NoResetPin};
+
+pub mod backlight;
+use backlight::{Backlight, NoBacklight};
 
 pub mod dcs;
 
+pub mod color;
+
 pub mod models;
 use models::Model;
 
 mod graphics;
 
+mod timing;
+
+mod trace;
+
 mod test_image;
 pub use test_image::TestImage;
 
+pub mod calibration;
+
+pub mod self_test;
+
+pub mod compat;
+
+pub mod framebuffer;
+
+pub mod glyph_cache;
+
+pub mod marquee;
+
+pub mod offscreen;
+
+pub mod poll;
+
+pub mod scroller;
+
+pub mod cropped;
+
+pub mod tune;
+
+#[cfg(feature = "rtic")]
+pub mod rtic;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "batch")]
 mod batch;
 
+/// Returns the number of pixels in a window with inclusive start and end coordinates.
+pub(crate) fn pixel_count(sx: u16, sy: u16, ex: u16, ey: u16) -> u32 {
+    let width = u32::from(ex) - u32::from(sx) + 1;
+    let height = u32::from(ey) - u32::from(sy) + 1;
+    width.saturating_mul(height)
+}
+
+/// Adds two RAM line numbers modulo the number of lines.
+pub(crate) fn add_lines(a: u16, b: u16, lines: u16) -> u16 {
+    ((u32::from(a) + u32::from(b)) % u32::from(lines)) as u16
+}
+
 ///
 /// Display driver to connect to TFT displays.
 ///
-pub struct Display<DI, MODEL, RST>
+pub struct Display<DI, MODEL, RST, BL = NoBacklight>
 where
     DI: interface::Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
 {
     // DCS provider
     di: DI,
@@ -145,20 +232,39 @@ where
     model: MODEL,
     // Reset pin
     rst: Option<RST>,
+    // Backlight
+    bl: Option<BL>,
+    // Backlight brightness in percent, restored after waking up
+    backlight_brightness: u8,
     // Model Options, includes current orientation
     options: options::ModelOptions,
     // Current MADCTL value copy for runtime updates
     madctl: dcs::SetAddressMode,
     // State monitor for sleeping TODO: refactor to a Model-connected state machine
     sleeping: bool,
+    // The display will be turned on by `first_draw`
+    display_on_pending: bool,
+    // Area all drawing operations are restricted to
+    persistent_window: Option<Rectangle>,
+    // A grouped update started by `transaction` is in progress
+    in_update: bool,
+    // Last address window in controller address coordinates
+    address_window: Option<(u16, u16, u16, u16)>,
+    // Last vertical scroll offset
+    scroll_offset: u16,
+    // Last vertical scroll area, as the top fixed area and the number of scrolled lines
+    scroll_area: (u16, u16),
+    // Offset between display coordinates and controller address coordinates
+    address_offset: (u16, u16),
 }
 
-impl<DI, M, RST> Display<DI, M, RST>
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
 where
     DI: interface::Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
 {
     ///
     /// Returns currently set [options::Orientation]
@@ -178,9 +284,86 @@ where
     /// # let mut display = mipidsi::_mock::new_mock_display();
     /// display.set_orientation(Orientation::default().rotate(Rotation::Deg180)).unwrap();
     /// ```
-    pub fn set_orientation(&mut self, orientation: options::Orientation) -> Result<(), DI::Error> {
-        self.madctl = self.madctl.with_orientation(orientation); // set orientation
-        self.di.write_command(self.madctl)?;
+    ///
+    /// Returns [`SetOrientationError::InvalidOffset`] without changing the orientation if the
+    /// display offset and the mounting correction result in addresses outside of the
+    /// controller address space in the new orientation.
+    pub fn set_orientation(
+        &mut self,
+        orientation: options::Orientation,
+    ) -> Result<(), SetOrientationError<DI::Error>> {
+        let mut options = self.options.clone();
+        options.orientation = orientation;
+        let address_offset = options
+            .address_offset(M::FRAMEBUFFER_SIZE)
+            .ok_or(SetOrientationError::InvalidOffset)?;
+
+        let hardware_orientation = options.hardware_orientation();
+        self.madctl = self.madctl.with_orientation(hardware_orientation); // set orientation
+        self.model
+            .update_address_mode(&mut self.di, self.madctl)
+            .map_err(SetOrientationError::Interface)?;
+        self.options.orientation = orientation;
+        self.address_offset = address_offset;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns currently set [options::ColorOrder]
+    ///
+    pub fn color_order(&self) -> options::ColorOrder {
+        self.options.color_order
+    }
+
+    ///
+    /// Sets display [options::ColorOrder]
+    ///
+    /// This can be used to change the subpixel order at runtime, e.g. if the
+    /// correct order is only known after probing the display or after user
+    /// calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::ColorOrder;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_color_order(ColorOrder::Bgr).unwrap();
+    /// ```
+    pub fn set_color_order(&mut self, color_order: options::ColorOrder) -> Result<(), DI::Error> {
+        self.madctl = self.madctl.with_color_order(color_order);
+        self.model.update_address_mode(&mut self.di, self.madctl)?;
+        self.options.color_order = color_order;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns currently set [options::ColorInversion]
+    ///
+    pub fn invert_colors(&self) -> options::ColorInversion {
+        self.options.invert_colors
+    }
+
+    ///
+    /// Sets display [options::ColorInversion]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::ColorInversion;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_invert_colors(ColorInversion::Inverted).unwrap();
+    /// ```
+    pub fn set_invert_colors(
+        &mut self,
+        color_inversion: options::ColorInversion,
+    ) -> Result<(), DI::Error> {
+        self.di
+            .write_command(dcs::SetInvertMode::new(color_inversion))?;
+        self.options.invert_colors = color_inversion;
 
         Ok(())
     }
@@ -202,7 +385,12 @@ where
     /// # let mut display = mipidsi::_mock::new_mock_display();
     /// display.set_pixel(100, 200, Rgb565::new(251, 188, 20)).unwrap();
     /// ```
-    pub fn set_pixel(&mut self, x: u16, y: u16, color: M::ColorFormat) -> Result<(), DI::Error> {
+    pub fn set_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: M::ColorFormat,
+    ) -> Result<(), SetPixelsError<DI::Error>> {
         self.set_pixels(x, y, x, y, core::iter::once(color))
     }
 
@@ -214,6 +402,10 @@ where
     /// checking is performed on the `colors` iterator and drawing will wrap around if the
     /// iterator returns more color values than the number of pixels in the given region.
     ///
+    /// All colors are passed to the interface in a single [`Interface::send_pixels`](interface::Interface::send_pixels) call
+    /// inside of one transaction. Interfaces with a buffer, like [`interface::SpiInterface`],
+    /// send the pixel data in chunks, which fill the whole buffer.
+    ///
     /// This is a low level function, which isn't intended to be used in regular user code.
     /// Consider using the [`fill_contiguous`](https://docs.rs/embedded-graphics/latest/embedded_graphics/draw_target/trait.DrawTarget.html#method.fill_contiguous)
     /// function from the `embedded-graphics` crate as an alternative instead.
@@ -233,6 +425,9 @@ where
     /// result in undefined behavior.
     ///
     /// </div>
+    ///
+    /// Returns [`SetPixelsError::InvalidWindow`] without drawing anything if the region is
+    /// outside of the controller address space after the display offset was added.
     pub fn set_pixels<T>(
         &mut self,
         sx: u16,
@@ -240,15 +435,239 @@ where
         ex: u16,
         ey: u16,
         colors: T,
-    ) -> Result<(), DI::Error>
+    ) -> Result<(), SetPixelsError<DI::Error>>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        let (sx, sy, ex, ey) = self
+            .offset_window(sx, sy, ex, ey)
+            .ok_or(SetPixelsError::InvalidWindow)?;
+        self.write_pixels_raw(sx, sy, ex, ey, colors)
+            .map_err(SetPixelsError::Interface)
+    }
+
+    /// Sets pixel colors in a rectangular region and checks the number of colors.
+    ///
+    /// This function works like [`set_pixels`](Self::set_pixels), but returns
+    /// [`SetPixelsError::PixelCountMismatch`] if `colors` doesn't contain exactly one color for
+    /// each pixel in the region. Surplus colors aren't drawn and don't wrap around, but if the
+    /// iterator ends early the remaining pixels keep their previous content. This is useful to
+    /// detect off-by-one errors in custom drawing code.
+    ///
+    /// [`SetPixelsError::InvalidWindow`] is returned without drawing anything if `ex` is
+    /// smaller than `sx` or `ey` is smaller than `sy`.
+    ///
+    /// The same restrictions on the coordinate ranges as for [`set_pixels`](Self::set_pixels) apply.
+    pub fn set_pixels_checked<T>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: T,
+    ) -> Result<(), SetPixelsError<DI::Error>>
     where
         T: IntoIterator<Item = M::ColorFormat>,
     {
-        self.set_address_window(sx, sy, ex, ey)?;
+        if ex < sx || ey < sy {
+            return Err(SetPixelsError::InvalidWindow);
+        }
+
+        let mut colors = colors.into_iter();
+        let mut remaining = pixel_count(sx, sy, ex, ey);
+
+        let pixels = core::iter::from_fn(|| {
+            if remaining == 0 {
+                return None;
+            }
+            let color = colors.next()?;
+            remaining -= 1;
+            Some(color)
+        });
+        self.set_pixels(sx, sy, ex, ey, pixels)?;
+
+        if remaining != 0 || colors.next().is_some() {
+            return Err(SetPixelsError::PixelCountMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Sets pixel colors in a rectangular region from a buffer of raw pixel data.
+    ///
+    /// Unlike [`set_pixels`](Self::set_pixels), the pixel data isn't converted by the driver,
+    /// but passed to the interface as one slice with
+    /// [`Interface::send_pixels_from_buffer`](interface::Interface::send_pixels_from_buffer).
+    /// This allows interfaces to send the pixel data directly from the buffer, e.g. with DMA,
+    /// and [`interface::SpiInterface`] sends it in a single SPI write without copying it.
+    ///
+    /// `data` must contain the pixels in the format which is sent to the display controller,
+    /// e.g. 2 big endian bytes per pixel for `Rgb565` colors on 8-bit interfaces
+    /// and 3 bytes per pixel for `Rgb666` colors. An incomplete pixel at the end of the
+    /// buffer is ignored. The same restrictions on the coordinate ranges and the same errors
+    /// as for [`set_pixels`](Self::set_pixels) apply.
+    pub fn set_pixels_from_buffer(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        data: &[DI::Word],
+    ) -> Result<(), SetPixelsError<DI::Error>> {
+        let (sx, sy, ex, ey) = self
+            .offset_window(sx, sy, ex, ey)
+            .ok_or(SetPixelsError::InvalidWindow)?;
+        self.write_memory(sx, sy, ex, ey, |model, di| {
+            model.send_pixels_from_buffer(di, data)
+        })
+        .map_err(SetPixelsError::Interface)
+    }
+
+    /// Groups all drawing operations in `f` into a single update.
+    ///
+    /// Interfaces which defer the pixel data and send it when they are flushed, e.g. in sync
+    /// with the tearing effect signal, send all drawing operations of the update together in
+    /// a single burst after `f` returns. This allows tear-free updates of multiple widgets
+    /// without flushing them manually in the right order. Other interfaces send the pixel
+    /// data immediately, see [`Event::BeginUpdate`].
+    ///
+    /// The update is ended even if `f` returns an error. Nested transactions are part of
+    /// the outermost update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display
+    ///     .transaction(|display| {
+    ///         display.fill_solid(&Rectangle::new(Point::new(0, 0), Size::new(10, 10)), Rgb565::RED)?;
+    ///         display.fill_solid(&Rectangle::new(Point::new(100, 0), Size::new(10, 10)), Rgb565::BLUE)
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, DI::Error>,
+    ) -> Result<R, DI::Error> {
+        if self.in_update {
+            return f(self);
+        }
+
+        self.di.handle_event(Event::BeginUpdate)?;
+        self.in_update = true;
+        let result = f(self);
+        self.in_update = false;
+        let end = self.di.handle_event(Event::EndUpdate);
+
+        let result = result?;
+        end?;
+        Ok(result)
+    }
 
-        self.di.write_command(dcs::WriteMemoryStart)?;
+    /// Marks an area of the display as invalid.
+    ///
+    /// Interfaces which keep a copy of the display RAM and only send the changed areas when
+    /// they are flushed add the area to their dirty window, so the area is sent again with
+    /// the next flush even if no pixels were written. This is useful if the content of the
+    /// panel is known to be corrupted, e.g. after an ESD event. The area is given in display
+    /// coordinates and is clipped to the display. Other interfaces ignore this call, see
+    /// [`Event::Invalidate`].
+    pub fn invalidate(&mut self, area: Rectangle) -> Result<(), DI::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
 
-        M::ColorFormat::send_pixels(&mut self.di, colors)
+        let Some((sx, sy, ex, ey)) = self.offset_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        ) else {
+            return Ok(());
+        };
+        self.di.handle_event(Event::Invalidate { sx, sy, ex, ey })
+    }
+
+    /// Restricts all drawing operations to the given area.
+    ///
+    /// While a persistent window is set, all [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget)
+    /// operations, including [`clear`](embedded_graphics_core::draw_target::DrawTarget::clear),
+    /// are clipped to `area`. This is useful if only a part of the display is ever updated,
+    /// e.g. a gauge on a dashboard, because pixels outside of the window are neither
+    /// computed nor sent to the display.
+    ///
+    /// The window is defined in display coordinates and isn't changed if the orientation is
+    /// changed. Low level functions like [`set_pixels`](Self::set_pixels) aren't affected
+    /// by the window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_persistent_window(Rectangle::new(Point::new(10, 10), Size::new(100, 50)));
+    ///
+    /// // Only clears the persistent window
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// ```
+    pub fn set_persistent_window(&mut self, area: Rectangle) {
+        self.persistent_window = Some(area);
+    }
+
+    /// Fills the whole framebuffer of the controller with a single color.
+    ///
+    /// Unlike [`clear`](embedded_graphics_core::draw_target::DrawTarget::clear), this method
+    /// ignores the display size, the offset and the persistent window and fills the entire
+    /// controller RAM, including the parts outside of the visible panel. The address window
+    /// is only set once and the color is sent with a single
+    /// [`Interface::send_repeated_pixel`](interface::Interface::send_repeated_pixel) call,
+    /// split into the largest transfers the interface supports. The previous address window
+    /// is restored afterwards, so that raw pixel data sent with [`dcs`](Self::dcs) continues
+    /// to use it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.clear_raw(Rgb565::BLACK).unwrap();
+    /// ```
+    pub fn clear_raw(&mut self, color: M::ColorFormat) -> Result<(), DI::Error> {
+        let (width, height) = M::FRAMEBUFFER_SIZE;
+        let (columns, rows) = if self.options.hardware_orientation().rotation.is_horizontal() {
+            (width, height)
+        } else {
+            (height, width)
+        };
+        let previous = self.address_window;
+
+        self.fill_raw(
+            0,
+            0,
+            columns.saturating_sub(1),
+            rows.saturating_sub(1),
+            color,
+        )?;
+
+        if let Some((sx, sy, ex, ey)) = previous {
+            self.set_address_window_raw(sx, sy, ex, ey)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the persistent window set by [`set_persistent_window`](Self::set_persistent_window).
+    pub fn clear_persistent_window(&mut self) {
+        self.persistent_window = None;
+    }
+
+    /// Returns the current persistent window.
+    pub fn persistent_window(&self) -> Option<Rectangle> {
+        self.persistent_window
     }
 
     /// Sets the vertical scroll region.
@@ -266,6 +685,9 @@ where
     ///
     /// After the scrolling region is defined the [`set_vertical_scroll_offset`](Self::set_vertical_scroll_offset) can be
     /// used to scroll the display.
+    ///
+    /// Use a [`Scroller`](crate::scroller::Scroller) to define the scrolling region in the
+    /// coordinates of the current orientation.
     pub fn set_vertical_scroll_region(
         &mut self,
         top_fixed_area: u16,
@@ -273,17 +695,22 @@ where
     ) -> Result<(), DI::Error> {
         let rows = M::FRAMEBUFFER_SIZE.1;
 
-        let vscrdef = if top_fixed_area + bottom_fixed_area > rows {
-            dcs::SetScrollArea::new(rows, 0, 0)
-        } else {
-            dcs::SetScrollArea::new(
-                top_fixed_area,
-                rows - top_fixed_area - bottom_fixed_area,
-                bottom_fixed_area,
-            )
-        };
+        let (top_fixed_area, lines, bottom_fixed_area) =
+            if u32::from(top_fixed_area) + u32::from(bottom_fixed_area) > u32::from(rows) {
+                (rows, 0, 0)
+            } else {
+                (
+                    top_fixed_area,
+                    rows - top_fixed_area - bottom_fixed_area,
+                    bottom_fixed_area,
+                )
+            };
 
-        self.di.write_command(vscrdef)
+        let vscrdef = dcs::SetScrollArea::new(top_fixed_area, lines, bottom_fixed_area);
+        self.di.write_command(vscrdef)?;
+        self.scroll_area = (top_fixed_area, lines);
+
+        Ok(())
     }
 
     /// Sets the vertical scroll offset.
@@ -295,36 +722,132 @@ where
     /// using this method.
     pub fn set_vertical_scroll_offset(&mut self, offset: u16) -> Result<(), DI::Error> {
         let vscad = dcs::SetScrollStart::new(offset);
-        self.di.write_command(vscad)
+        self.di.write_command(vscad)?;
+        self.scroll_offset = offset;
+
+        Ok(())
+    }
+
+    /// Returns the vertical scroll offset set by [`set_vertical_scroll_offset`](Self::set_vertical_scroll_offset).
+    pub fn vertical_scroll_offset(&self) -> u16 {
+        self.scroll_offset
     }
 
     ///
     /// Release resources allocated to this driver back.
-    /// This returns the display interface, reset pin and and the model deconstructing the driver.
+    /// This returns the display interface, the model, reset pin and backlight deconstructing the driver.
     ///
-    pub fn release(self) -> (DI, M, Option<RST>) {
-        (self.di, self.model, self.rst)
+    pub fn release(self) -> (DI, M, Option<RST>, Option<BL>) {
+        (self.di, self.model, self.rst, self.bl)
     }
 
-    // Sets the address window for the display.
-    fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DI::Error> {
-        // add clipping offsets if present
-        let mut offset = self.options.display_offset;
-        let mapping = MemoryMapping::from(self.options.orientation);
-        if mapping.reverse_columns {
-            offset.0 = M::FRAMEBUFFER_SIZE.0 - (self.options.display_size.0 + offset.0);
-        }
-        if mapping.reverse_rows {
-            offset.1 = M::FRAMEBUFFER_SIZE.1 - (self.options.display_size.1 + offset.1);
-        }
-        if mapping.swap_rows_and_columns {
-            offset = (offset.1, offset.0);
+    // Returns the offset between display coordinates and controller address coordinates.
+    fn address_offset(&self) -> (u16, u16) {
+        self.address_offset
+    }
+
+    // Converts a window in display coordinates to controller address coordinates.
+    //
+    // Returns `None` if the window is outside of the controller address space.
+    fn offset_window(&self, sx: u16, sy: u16, ex: u16, ey: u16) -> Option<(u16, u16, u16, u16)> {
+        let (ox, oy) = self.address_offset();
+        Some((
+            sx.checked_add(ox)?,
+            sy.checked_add(oy)?,
+            ex.checked_add(ox)?,
+            ey.checked_add(oy)?,
+        ))
+    }
+
+    // Writes pixels to a region in display coordinates for the `DrawTarget` implementation.
+    //
+    // Regions outside of the controller address space are skipped without an error, because
+    // `DrawTarget` operations don't report pixels outside of the display either.
+    fn draw_pixels<T>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: T,
+    ) -> Result<(), DI::Error>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        match self.offset_window(sx, sy, ex, ey) {
+            Some((sx, sy, ex, ey)) => self.write_pixels_raw(sx, sy, ex, ey, colors),
+            None => Ok(()),
         }
+    }
+
+    // Sets the address window in controller address coordinates.
+    fn set_address_window_raw(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error> {
+        self.model
+            .set_address_window(&mut self.di, sx, sy, ex, ey)?;
+        self.address_window = Some((sx, sy, ex, ey));
+        Ok(())
+    }
+
+    // Writes pixels to a region in controller address coordinates.
+    fn write_pixels_raw<T>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: T,
+    ) -> Result<(), DI::Error>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        let endianness = self.options.endianness;
+        self.write_memory(sx, sy, ex, ey, |model, di| {
+            model.send_pixels(di, colors, endianness)
+        })
+    }
 
-        let (sx, sy, ex, ey) = (sx + offset.0, sy + offset.1, ex + offset.0, ey + offset.1);
+    // Fills a region in controller address coordinates with a single color.
+    fn fill_raw(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error> {
+        let count = pixel_count(sx, sy, ex, ey);
+        let endianness = self.options.endianness;
+        self.write_memory(sx, sy, ex, ey, |model, di| {
+            model.send_repeated_pixel(di, color, count, endianness)
+        })
+    }
 
-        self.di.write_command(dcs::SetColumnAddress::new(sx, ex))?;
-        self.di.write_command(dcs::SetPageAddress::new(sy, ey))
+    // Sets the address window to a region in controller address coordinates and sends the
+    // pixel data with `send` in a single interface transaction. The transaction is ended
+    // even if an error occurs, otherwise interfaces which keep CS asserted during a
+    // transaction would stay selected.
+    fn write_memory(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        send: impl FnOnce(&mut M, &mut DI) -> Result<(), DI::Error>,
+    ) -> Result<(), DI::Error> {
+        self.di.handle_event(Event::BeginTransaction)?;
+        let result = self
+            .set_address_window_raw(sx, sy, ex, ey)
+            .and_then(|()| self.model.write_memory_start(&mut self.di))
+            .and_then(|()| send(&mut self.model, &mut self.di));
+        let end = self.di.handle_event(Event::EndTransaction);
+        result?;
+        end
     }
 
     ///
@@ -338,6 +861,38 @@ where
             .write_command(dcs::SetTearingEffect::new(tearing_effect))
     }
 
+    /// Enters the idle mode.
+    ///
+    /// In idle mode the controller reduces the color depth, which saves power while still
+    /// showing the display content, e.g. for a clock face on a battery-powered device.
+    /// The reduced color depth depends on the controller, most controllers show 8 colors.
+    pub fn enter_idle_mode(&mut self) -> Result<(), DI::Error> {
+        self.model.set_idle_mode(&mut self.di, true)
+    }
+
+    /// Exits the idle mode.
+    pub fn exit_idle_mode(&mut self) -> Result<(), DI::Error> {
+        self.model.set_idle_mode(&mut self.di, false)
+    }
+
+    /// Sets the partial area and enters the partial mode.
+    ///
+    /// In partial mode only the rows from `start_row` to `end_row`, which are inclusive,
+    /// show the display content and the rest of the panel is blank, which saves power.
+    ///
+    /// Like [`set_vertical_scroll_region`](Self::set_vertical_scroll_region), this method is
+    /// not affected by the current display orientation and the rows are the RAM rows of the
+    /// default orientation.
+    pub fn set_partial_area(&mut self, start_row: u16, end_row: u16) -> Result<(), DI::Error> {
+        self.model
+            .set_partial_area(&mut self.di, start_row, end_row)
+    }
+
+    /// Exits the partial mode and shows the whole display content again.
+    pub fn exit_partial_mode(&mut self) -> Result<(), DI::Error> {
+        self.model.exit_partial_mode(&mut self.di)
+    }
+
     ///
     /// Returns `true` if display is currently set to sleep.
     ///
@@ -345,12 +900,98 @@ where
         self.sleeping
     }
 
+    ///
+    /// Returns the backlight brightness in percent.
+    ///
+    /// This is the brightness that was last set by [Self::set_backlight], even if the
+    /// backlight is currently turned off because the display is sleeping.
+    ///
+    pub fn backlight(&self) -> u8 {
+        self.backlight_brightness
+    }
+
+    ///
+    /// Sets the backlight brightness in percent.
+    ///
+    /// Values larger than 100 are treated as 100. Backlights which don't support dimming
+    /// are turned on for all non zero values. If the display is sleeping the brightness
+    /// will be applied after it is woken up. Does nothing if no backlight was set in the
+    /// [Builder].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_backlight(50).unwrap();
+    /// ```
+    pub fn set_backlight(&mut self, percent: u8) -> Result<(), BL::Error> {
+        self.backlight_brightness = percent.min(100);
+
+        match self.bl.as_mut() {
+            Some(bl) if !self.sleeping => bl.set_brightness(self.backlight_brightness),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Draws the first frame and turns on the display afterwards.
+    ///
+    /// If the display was initialized with
+    /// [`display_on_after_first_draw`](Builder::display_on_after_first_draw), it stays turned
+    /// off until this method is called. `draw` is called first, then the display is turned on
+    /// and this method waits for the [`DISPLAY_ON_DELAY_US`](Model::DISPLAY_ON_DELAY_US) of
+    /// the model. If the display is already turned on, only `draw` is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{Builder, models::ST7789};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .display_on_after_first_draw()
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// display
+    ///     .first_draw(&mut delay, |display| display.clear(Rgb565::BLACK))
+    ///     .unwrap();
+    /// ```
+    pub fn first_draw<D, F, E>(&mut self, delay: &mut D, draw: F) -> Result<(), E>
+    where
+        D: DelayNs,
+        F: FnOnce(&mut Self) -> Result<(), E>,
+        E: From<DI::Error>,
+    {
+        draw(self)?;
+
+        if self.display_on_pending {
+            self.di.write_command(dcs::SetDisplayOn)?;
+            delay.delay_us(M::DISPLAY_ON_DELAY_US);
+            self.display_on_pending = false;
+        }
+
+        Ok(())
+    }
+
     ///
     /// Puts the display to sleep, reducing power consumption.
     /// Need to call [Self::wake] before issuing other commands
     ///
-    pub fn sleep<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        self.di.write_command(dcs::EnterSleepMode)?;
+    /// The backlight is turned off before the display enters sleep mode.
+    ///
+    pub fn sleep<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        if let Some(bl) = self.bl.as_mut() {
+            bl.set_brightness(0).map_err(SleepError::Backlight)?;
+        }
+        self.di
+            .write_command(dcs::EnterSleepMode)
+            .map_err(SleepError::Interface)?;
         // All supported models requires a 120ms delay before issuing other commands
         delay.delay_us(120_000);
         self.sleeping = true;
@@ -360,11 +1001,22 @@ where
     ///
     /// Wakes the display after it's been set to sleep via [Self::sleep]
     ///
-    pub fn wake<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        self.di.write_command(dcs::ExitSleepMode)?;
+    /// The backlight brightness is restored after the display left sleep mode.
+    ///
+    pub fn wake<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        self.di
+            .write_command(dcs::ExitSleepMode)
+            .map_err(SleepError::Interface)?;
         // ST7789 and st7735s have the highest minimal delay of 120ms
         delay.delay_us(120_000);
         self.sleeping = false;
+        if let Some(bl) = self.bl.as_mut() {
+            bl.set_brightness(self.backlight_brightness)
+                .map_err(SleepError::Backlight)?;
+        }
         Ok(())
     }
 
@@ -381,6 +1033,192 @@ where
     }
 }
 
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: interface::Interface,
+    M: models::PageFlip,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Shows the memory page which was drawn to since the last flip.
+    ///
+    /// All following drawing operations write to the other page, which isn't visible until
+    /// the next call to this method. Only available for models which implement
+    /// [`PageFlip`](models::PageFlip).
+    pub fn flip(&mut self) -> Result<(), DI::Error> {
+        self.model.flip(&mut self.di)
+    }
+}
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: interface::Interface,
+    M: models::DisplayBrightness,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Sets the display brightness (WRDISBV).
+    ///
+    /// `0` is the lowest and `255` the highest brightness. The brightness is only applied if
+    /// the brightness control block was enabled with
+    /// [`set_brightness_control`](Self::set_brightness_control). Only available for models
+    /// which implement [`DisplayBrightness`](models::DisplayBrightness).
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<(), DI::Error> {
+        self.di
+            .write_command(dcs::SetDisplayBrightness::new(brightness))
+    }
+
+    /// Configures the brightness control (WRCTRLD).
+    ///
+    /// Only available for models which implement [`DisplayBrightness`](models::DisplayBrightness).
+    pub fn set_brightness_control(
+        &mut self,
+        control: options::BrightnessControl,
+    ) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::WriteCtrlDisplay::new(control))
+    }
+}
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: interface::Interface<Word = u8>,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<u8>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Writes pre-encoded pixel data into a rectangular region.
+    ///
+    /// This is intended for UI toolkits like LVGL or Slint, which render into a buffer of
+    /// raw pixel data. The bytes are sent to the interface without converting them to
+    /// colors first, so they must be in the format which is sent to the display controller,
+    /// e.g. 2 big endian bytes per pixel for `Rgb565` and 3 bytes per pixel for `Rgb666`
+    /// colors. The [`Endianness`](options::Endianness) option isn't applied.
+    ///
+    /// Unlike [`set_pixels_from_buffer`](Self::set_pixels_from_buffer), the region and the
+    /// length of `pixel_bytes` are checked first. [`SetPixelsError::InvalidWindow`] is returned
+    /// if `ex` is smaller than `sx` or `ey` is smaller than `sy` and
+    /// [`SetPixelsError::PixelCountMismatch`] if the length doesn't match the size of the
+    /// region, see [`Model::raw_pixel_data_len`]. Nothing is drawn in both cases. The pixel
+    /// data is then sent like by [`set_pixels_from_buffer`](Self::set_pixels_from_buffer).
+    /// Only available for 8-bit interfaces.
+    ///
+    /// The same restrictions on the coordinate ranges as for [`set_pixels`](Self::set_pixels) apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // 2x2 pixels rendered by a UI toolkit into an Rgb565 buffer
+    /// let buffer = [0xF8, 0x00, 0x07, 0xE0, 0x00, 0x1F, 0xFF, 0xFF];
+    /// display.write_raw_window(10, 10, 11, 11, &buffer).unwrap();
+    ///
+    /// assert!(display.write_raw_window(10, 10, 11, 11, &buffer[..6]).is_err());
+    /// ```
+    pub fn write_raw_window(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        pixel_bytes: &[u8],
+    ) -> Result<(), SetPixelsError<DI::Error>> {
+        if ex < sx || ey < sy {
+            return Err(SetPixelsError::InvalidWindow);
+        }
+
+        let len = self
+            .model
+            .raw_pixel_data_len(pixel_count(sx, sy, ex, ey), DI::KIND);
+        if pixel_bytes.len() != len {
+            return Err(SetPixelsError::PixelCountMismatch);
+        }
+
+        self.set_pixels_from_buffer(sx, sy, ex, ey, pixel_bytes)
+    }
+}
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: interface::InterfaceRead,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Reads the display identification information (RDDID).
+    ///
+    /// Returns the manufacturer ID, the module or driver version ID and the module or
+    /// driver ID. This can be used to check the wiring and the controller type at runtime.
+    /// Only available for interfaces which implement
+    /// [`InterfaceRead`](interface::InterfaceRead).
+    pub fn read_display_id(&mut self) -> Result<[u8; 3], DI::Error> {
+        self.read_data(0x04)
+    }
+
+    /// Reads the display status (RDDST).
+    ///
+    /// Returns the four status bytes, which contain e.g. the address mode, the pixel
+    /// format and the sleep and display on state. Only available for interfaces which
+    /// implement [`InterfaceRead`](interface::InterfaceRead).
+    pub fn read_status(&mut self) -> Result<[u8; 4], DI::Error> {
+        self.read_data(0x09)
+    }
+
+    // Sends a read command and removes the dummy cycle in front of the data.
+    fn read_data<const N: usize>(&mut self, command: u8) -> Result<[u8; N], DI::Error> {
+        let mut buffer = [0; 5];
+        let buffer = &mut buffer[..N + 1];
+        self.di.read_command(command, buffer)?;
+
+        let mut data = [0; N];
+        match DI::KIND {
+            // Serial interfaces send a single dummy bit
+            interface::InterfaceKind::Serial4Line | interface::InterfaceKind::Serial3Line => {
+                for (i, byte) in data.iter_mut().enumerate() {
+                    *byte = (buffer[i] << 1) | (buffer[i + 1] >> 7);
+                }
+            }
+            // Parallel interfaces send a dummy byte
+            _ => data.copy_from_slice(&buffer[1..]),
+        }
+
+        Ok(data)
+    }
+}
+
+/// Error returned by [`Display::sleep`] and [`Display::wake`].
+#[derive(Debug)]
+pub enum SleepError<DI, BL> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// Error caused by the [`Backlight`] implementation.
+    Backlight(BL),
+}
+
+/// Error returned by [`Display::set_pixels`] and the related methods.
+#[derive(Debug)]
+pub enum SetPixelsError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The number of colors didn't match the number of pixels in the region.
+    PixelCountMismatch,
+    /// The end coordinates of the region were smaller than the start coordinates or the
+    /// region was outside of the controller address space.
+    InvalidWindow,
+}
+
+/// Error returned by [`Display::set_orientation`].
+#[derive(Debug)]
+pub enum SetOrientationError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The display area isn't inside of the controller address space in the new orientation.
+    InvalidOffset,
+}
+
 /// Mock implementations of embedded-hal and interface traits.
 ///
 /// Do not use types in this module outside of doc tests.
@@ -388,9 +1226,13 @@ where
 pub mod _mock {
     use core::convert::Infallible;
 
-    use embedded_hal::{delay::DelayNs, digital, spi};
+    use embedded_hal::{delay::DelayNs, digital, pwm, spi};
 
-    use crate::{interface::Interface, models::ILI9341Rgb565, Builder, Display, NoResetPin};
+    use crate::{
+        interface::{Interface, InterfaceKind},
+        models::ILI9341Rgb565,
+        Builder, Display, NoResetPin,
+    };
 
     pub fn new_mock_display() -> Display<MockDisplayInterface, ILI9341Rgb565, NoResetPin> {
         Builder::new(ILI9341Rgb565, MockDisplayInterface)
@@ -414,6 +1256,22 @@ pub mod _mock {
         type Error = core::convert::Infallible;
     }
 
+    pub struct MockPwm;
+
+    impl pwm::SetDutyCycle for MockPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            100
+        }
+
+        fn set_duty_cycle(&mut self, _duty: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl pwm::ErrorType for MockPwm {
+        type Error = core::convert::Infallible;
+    }
+
     pub struct MockSpi;
 
     impl spi::SpiDevice for MockSpi {
@@ -441,6 +1299,235 @@ pub mod _mock {
         type Word = u8;
         type Error = Infallible;
 
+        // A parallel interface supports all color formats of the builtin models.
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            // Like a real interface, consume all pixels.
+            pixels.into_iter().for_each(drop);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+}
+
+/// Test helpers shared by the unit tests of multiple modules.
+#[cfg(test)]
+pub(crate) mod _test {
+    use core::{convert::Infallible, marker::PhantomData};
+
+    use crate::{
+        _mock::MockDelay,
+        interface::{ErrorKind, Event, Interface, InterfaceKind},
+        models::ST7789,
+        options::Orientation,
+        Builder, Display,
+    };
+
+    /// Interface which records the last address window, scroll area and scroll offset.
+    ///
+    /// The instruction code and the first parameter of the first 64 commands are recorded in
+    /// `commands` and the first 64 bytes of pixel data of the last memory write in `data`.
+    /// `pixels` counts all sent pixels, `max_pixels` is the largest number of pixels which
+    /// were sent in a single call and `transactions` is the number of open transactions.
+    #[derive(Default)]
+    pub(crate) struct RecordingInterface {
+        pub commands: heapless::Vec<(u8, u8), 64>,
+        pub last_command: u8,
+        pub columns: [u8; 4],
+        pub pages: [u8; 4],
+        pub scroll_area: [u8; 6],
+        pub scroll_offset: [u8; 2],
+        pub windows: u32,
+        pub pixels: u32,
+        pub max_pixels: u32,
+        pub data: heapless::Vec<u8, 64>,
+        pub invalidated: Option<(u16, u16, u16, u16)>,
+        pub transactions: u32,
+    }
+
+    impl RecordingInterface {
+        fn record_pixels(&mut self, pixels: u32) {
+            self.pixels += pixels;
+            self.max_pixels = self.max_pixels.max(pixels);
+        }
+    }
+
+    impl Interface for RecordingInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+            let _ = self
+                .commands
+                .push((command, args.first().copied().unwrap_or_default()));
+            self.last_command = command;
+
+            match command {
+                // Vendor commands of some models reuse these instruction codes with other
+                // parameters
+                0x2A if args.len() == 4 => {
+                    self.columns.copy_from_slice(args);
+                    self.windows += 1;
+                }
+                0x2B if args.len() == 4 => self.pages.copy_from_slice(args),
+                0x2C => self.data.clear(),
+                0x33 if args.len() == 6 => self.scroll_area.copy_from_slice(args),
+                0x37 if args.len() == 2 => self.scroll_offset.copy_from_slice(args),
+                _ => {}
+            }
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            let mut count = 0;
+            for pixel in pixels {
+                let _ = self.data.extend_from_slice(&pixel);
+                count += 1;
+            }
+            self.record_pixels(count);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.record_pixels(count);
+            Ok(())
+        }
+
+        fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+            match event {
+                Event::Invalidate { sx, sy, ex, ey } => self.invalidated = Some((sx, sy, ex, ey)),
+                Event::BeginTransaction => self.transactions += 1,
+                Event::EndTransaction => self.transactions -= 1,
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    /// Interface which fails the first `failures` calls and every call with a multiple of
+    /// `period`, if it isn't zero.
+    ///
+    /// `calls` counts all calls, including the failed ones, and `pixels` the successfully
+    /// sent pixels.
+    #[derive(Default)]
+    pub(crate) struct FlakyInterface {
+        pub failures: u32,
+        pub period: u32,
+        pub calls: u32,
+        pub pixels: u32,
+    }
+
+    impl FlakyInterface {
+        fn call(&mut self) -> Result<(), ErrorKind> {
+            self.calls += 1;
+            if self.failures > 0 {
+                self.failures -= 1;
+                Err(ErrorKind::Bus)
+            } else if self.period != 0 && self.calls % self.period == 0 {
+                Err(ErrorKind::Bus)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl Interface for FlakyInterface {
+        type Word = u8;
+        type Error = ErrorKind;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            self.call()
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            let count = pixels.into_iter().count() as u32;
+            self.call()?;
+            self.pixels += count;
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.call()?;
+            self.pixels += count;
+            Ok(())
+        }
+    }
+
+    /// Interface kind of a [`NullInterface`].
+    pub(crate) trait Kind {
+        const KIND: InterfaceKind;
+    }
+
+    /// Serial interface with data/command pin.
+    pub(crate) struct Serial4Line;
+
+    impl Kind for Serial4Line {
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+    }
+
+    /// 8 bit parallel interface.
+    pub(crate) struct Parallel8Bit;
+
+    impl Kind for Parallel8Bit {
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+    }
+
+    /// 18 bit parallel interface.
+    pub(crate) struct Parallel18Bit;
+
+    impl Kind for Parallel18Bit {
+        const KIND: InterfaceKind = InterfaceKind::Parallel18Bit;
+    }
+
+    /// Interface without any function, which transfers words of type `W` and reports the
+    /// interface kind `K`.
+    pub(crate) struct NullInterface<W, K = Serial4Line>(PhantomData<(W, K)>);
+
+    impl<W, K> NullInterface<W, K> {
+        pub(crate) const fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<W: Copy, K: Kind> Interface for NullInterface<W, K> {
+        type Word = W;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = K::KIND;
+
         fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
             Ok(())
         }
@@ -460,4 +1547,14 @@ pub mod _mock {
             Ok(())
         }
     }
+
+    pub(crate) fn display(
+        orientation: Orientation,
+    ) -> Display<RecordingInterface, ST7789, crate::NoResetPin> {
+        Builder::new(ST7789, RecordingInterface::default())
+            .display_size(240, 240)
+            .orientation(orientation)
+            .init(&mut MockDelay)
+            .unwrap()
+    }
 }
@@ -0,0 +1,317 @@
+//! Marquee using hardware scrolling.
+//!
+//! Many controllers have more RAM than the connected panel can show, e.g. the
+//! ST7789 has RAM for 240x320 pixels, but is often used with 240x240 panels.
+//! [`Marquee`] uses the vertical scrolling feature of the controller to move
+//! the content of the whole RAM through the display, which doesn't require any
+//! pixels to be redrawn when the marquee is animated.
+//!
+//! The content is drawn to a strip, which is stored in the controller RAM as a
+//! ring buffer. The currently visible part of the strip starts at
+//! [`Marquee::position`] and the remaining RAM lines after the visible part are
+//! hidden. New content, like the next characters of a long text, can be drawn to
+//! the hidden part with [`Marquee::canvas`] before it is scrolled into view by
+//! [`Marquee::step`]. The marquee is built on top of the [off-screen
+//! region](crate::offscreen) support.
+//!
+//! The scrolling direction is always along the vertical axis of the display in
+//! the default orientation. Use an orientation with a 90° or 270° rotation for
+//! horizontally scrolling text.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{
+//!     mono_font::{ascii::FONT_10X20, MonoTextStyle},
+//!     pixelcolor::Rgb565,
+//!     prelude::*,
+//!     primitives::Rectangle,
+//!     text::{Baseline, Text},
+//! };
+//! use mipidsi::{
+//!     marquee::Marquee,
+//!     models::ST7789,
+//!     options::{Orientation, Rotation},
+//!     Builder,
+//! };
+//!
+//! # let di = mipidsi::_mock::MockDisplayInterface;
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! let mut display = Builder::new(ST7789, di)
+//!     .display_size(240, 240)
+//!     .orientation(Orientation::new().rotate(Rotation::Deg90))
+//!     .init(&mut delay)
+//!     .unwrap();
+//!
+//! let style = MonoTextStyle::new(&FONT_10X20, Rgb565::WHITE);
+//! let mut marquee = Marquee::new(&mut display).unwrap();
+//!
+//! for c in "Hello, world! ".chars().cycle().take(100) {
+//!     // Draw the next character at the end of the visible part of the strip.
+//!     let mut text = [0; 4];
+//!     let position = Point::new(marquee.position() + 240, 110);
+//!     marquee
+//!         .canvas(&mut display)
+//!         .fill_solid(&Rectangle::new(position, Size::new(10, 20)), Rgb565::BLACK)
+//!         .unwrap();
+//!     Text::with_baseline(c.encode_utf8(&mut text), position, style, Baseline::Top)
+//!         .draw(&mut marquee.canvas(&mut display))
+//!         .unwrap();
+//!
+//!     // Scroll the character into view.
+//!     for _ in 0..10 {
+//!         marquee.step(&mut display, 1).unwrap();
+//!         // delay.delay_ms(20);
+//!     }
+//! }
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Marquee using hardware scrolling.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct Marquee {
+    position: i32,
+}
+
+impl Marquee {
+    /// Creates a new marquee.
+    ///
+    /// The whole RAM is used as the vertical scroll region and the scroll offset is
+    /// reset. The current display content becomes the start of the strip.
+    pub fn new<DI, M, RST, BL>(display: &mut Display<DI, M, RST, BL>) -> Result<Self, DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        display.set_vertical_scroll_region(0, 0)?;
+        display.set_vertical_scroll_offset(0)?;
+
+        Ok(Self { position: 0 })
+    }
+
+    /// Returns the position of the first visible line of the strip.
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Scrolls the strip by the given number of lines.
+    pub fn step<DI, M, RST, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL>,
+        lines: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let lines = lines % M::FRAMEBUFFER_SIZE.1;
+        display.reveal_offscreen(lines)?;
+        self.position = self.position.wrapping_add(i32::from(lines));
+
+        Ok(())
+    }
+
+    /// Returns a draw target for the strip.
+    ///
+    /// The coordinate along the scrolling axis is the position in the strip. Only the
+    /// part of the strip which fits into the RAM, starting at the current
+    /// [`position`](Self::position), can be drawn to.
+    pub fn canvas<'a, DI, M, RST, BL>(
+        &self,
+        display: &'a mut Display<DI, M, RST, BL>,
+    ) -> MarqueeCanvas<'a, DI, M, RST, BL>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        MarqueeCanvas {
+            display,
+            position: self.position,
+        }
+    }
+}
+
+/// Draw target for the strip of a [`Marquee`].
+pub struct MarqueeCanvas<'a, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut Display<DI, M, RST, BL>,
+    position: i32,
+}
+
+impl<DI, M, RST, BL> MarqueeCanvas<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    // Converts a rectangle in strip coordinates into the coordinates used by
+    // `Display::fill_ram`, which are relative to the first visible line.
+    fn to_ram(&self, area: &Rectangle) -> Rectangle {
+        let area = area.intersection(&self.bounding_box());
+
+        if self.display.scrolls_horizontally() {
+            Rectangle::new(area.top_left - Point::new(self.position, 0), area.size)
+        } else {
+            Rectangle::new(area.top_left - Point::new(0, self.position), area.size)
+        }
+    }
+}
+
+impl<DI, M, RST, BL> DrawTarget for MarqueeCanvas<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let area = self.to_ram(&Rectangle::new(point, Size::new(1, 1)));
+            if !area.is_zero_sized() {
+                self.display.draw_ram_pixel(area.top_left, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.to_ram(area);
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.display.fill_ram(&area, color)
+    }
+}
+
+impl<DI, M, RST, BL> Dimensions for MarqueeCanvas<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let (rows, width) = self.display.ram_lines();
+        let (rows, width) = (u32::from(rows), u32::from(width));
+
+        if self.display.scrolls_horizontally() {
+            Rectangle::new(Point::new(self.position, 0), Size::new(rows, width))
+        } else {
+            Rectangle::new(Point::new(0, self.position), Size::new(width, rows))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _test::display,
+        options::{Orientation, Rotation},
+    };
+
+    use super::*;
+
+    #[test]
+    fn strip_wraps_around() {
+        let mut display = display(Orientation::new());
+        let mut marquee = Marquee::new(&mut display).unwrap();
+        marquee.step(&mut display, 100).unwrap();
+        assert_eq!(marquee.position(), 100);
+        assert_eq!(unsafe { display.dcs() }.scroll_offset, [0, 100]);
+
+        let area = Rectangle::new(Point::new(0, 300), Size::new(240, 40));
+        unsafe { display.dcs() }.windows = 0;
+        marquee
+            .canvas(&mut display)
+            .fill_solid(&area, Rgb565::RED)
+            .unwrap();
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, 2);
+        assert_eq!(di.columns, [0, 0, 0, 239]);
+        assert_eq!(di.pages, [0, 0, 0, 19]);
+    }
+
+    #[test]
+    fn lines_before_position_are_not_drawn() {
+        let mut display = display(Orientation::new());
+        let mut marquee = Marquee::new(&mut display).unwrap();
+        marquee.step(&mut display, 50).unwrap();
+
+        unsafe { display.dcs() }.windows = 0;
+        marquee
+            .canvas(&mut display)
+            .draw_iter([
+                Pixel(Point::new(0, 49), Rgb565::RED),
+                Pixel(Point::new(0, 370), Rgb565::RED),
+            ])
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.windows, 0);
+    }
+
+    #[test]
+    fn reversed_rows_scroll_backwards() {
+        let mut display = display(Orientation::new().rotate(Rotation::Deg270));
+        let mut marquee = Marquee::new(&mut display).unwrap();
+        marquee.step(&mut display, 10).unwrap();
+        assert_eq!(unsafe { display.dcs() }.scroll_offset, [0x01, 0x36]);
+
+        // The strip is horizontal in this orientation.
+        marquee
+            .canvas(&mut display)
+            .fill_solid(
+                &Rectangle::new(Point::new(250, 0), Size::new(1, 240)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        // (250 + 80 lines offset) wraps around to RAM line 10
+        assert_eq!(di.columns, [0, 10, 0, 10]);
+        assert_eq!(di.pages, [0, 0, 0, 239]);
+    }
+}
@@ -1,27 +1,55 @@
 //! Display models.
 
-use crate::{dcs::SetAddressMode, interface::Interface, options::ModelOptions};
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterIdleMode, EnterNormalMode, EnterPartialMode, ExitIdleMode, InterfaceExt,
+        SetAddressMode, SetColumnAddress, SetDisplayOn, SetPageAddress, SetPartialArea,
+        WriteMemoryStart,
+    },
+    interface::{Interface, InterfaceKind, InterfacePixelFormat},
+    options::{ColorInversion, ColorOrder, Endianness, ModelOptions},
+};
 use embedded_graphics_core::prelude::RgbColor;
 use embedded_hal::delay::DelayNs;
 
 // existing model implementations
+mod dual;
 mod gc9107;
+mod gc9307;
 mod gc9a01;
+mod hx8347;
+mod ili9327;
 mod ili9341;
 mod ili9342c;
 mod ili934x;
 mod ili9486;
+mod ili9488;
+mod lg4573a;
+mod registry;
 mod rm67162;
+mod st7365p;
+mod st7735b;
+mod st7735r;
 mod st7735s;
 mod st7789;
 mod st7796;
 
+pub use dual::*;
 pub use gc9107::*;
+pub use gc9307::*;
 pub use gc9a01::*;
+pub use hx8347::*;
+pub use ili9327::*;
 pub use ili9341::*;
 pub use ili9342c::*;
 pub use ili9486::*;
+pub use ili9488::*;
+pub use lg4573a::*;
+pub use registry::*;
 pub use rm67162::*;
+pub use st7365p::*;
+pub use st7735b::*;
+pub use st7735r::*;
 pub use st7735s::*;
 pub use st7789::*;
 pub use st7796::*;
@@ -32,17 +60,613 @@ pub trait Model {
     type ColorFormat: RgbColor;
 
     /// The framebuffer size in pixels.
+    ///
+    /// This is the size of the controller RAM as columns and rows in the native orientation
+    /// of the controller, which can be larger than the connected panel. The visible part
+    /// of the RAM is selected with the display size and offset options. All RAM addresses
+    /// must fit into the 16-bit addresses of the DCS commands, so neither dimension can
+    /// exceed 65535 pixels. The driver calculates pixel counts and scroll positions with
+    /// 32-bit integers, which can't overflow for any framebuffer size.
     const FRAMEBUFFER_SIZE: (u16, u16);
 
+    /// The default subpixel order.
+    ///
+    /// Used by [`ModelOptions::full_size`] and can be overridden by models for
+    /// controllers which are commonly wired in BGR order. The
+    /// [`Builder::color_order`](crate::Builder::color_order) setting always
+    /// takes precedence over this value.
+    const DEFAULT_COLOR_ORDER: ColorOrder = ColorOrder::Rgb;
+
+    /// The default color inversion.
+    ///
+    /// Used by [`ModelOptions::full_size`] and can be overridden by models for
+    /// panels which commonly need inverted colors. The
+    /// [`Builder::invert_colors`](crate::Builder::invert_colors) setting always
+    /// takes precedence over this value.
+    const DEFAULT_COLOR_INVERSION: ColorInversion = ColorInversion::Normal;
+
+    /// The maximum serial clock frequency in Hz.
+    ///
+    /// This is the write clock frequency for serial interfaces specified in the datasheet of the
+    /// controller, or `None` if it isn't known. Many panels work with higher frequencies, but
+    /// exceeding the limit is a common cause of corrupted pixels. The value is sent to the
+    /// interface as an [`Event::ClockLimit`](crate::interface::Event::ClockLimit) by
+    /// [`Builder::init`](crate::Builder::init).
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = None;
+
+    /// The delay after the `SetDisplayOn` command in microseconds.
+    ///
+    /// Some controllers, like the ST7789, corrupt the data which is sent too soon after
+    /// `SetDisplayOn`. The model waits for this time at the end of [`init`](Self::init) and
+    /// the builder waits for it if the display is turned on after a splash screen or an
+    /// [`init_hook`](crate::Builder::init_hook).
+    const DISPLAY_ON_DELAY_US: u32 = 0;
+
     /// Initializes the display for this model with MADCTL from [crate::Display]
     /// and returns the value of MADCTL set by init
+    ///
+    /// The display should be turned on at the end of the init sequence, followed by the
+    /// [`DISPLAY_ON_DELAY_US`](Self::DISPLAY_ON_DELAY_US), unless
+    /// [`ModelOptions::display_on`] is `false`.
+    ///
+    /// Implementations should return [`ConfigurationError::UnsupportedInterface`] with the
+    /// supported combinations if the controller doesn't support the color format on the
+    /// interface [`kind`](Interface::KIND) of `DI`.
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface;
+
+    /// Sets the address window for the following memory write.
+    ///
+    /// The start and end addresses are inclusive controller RAM addresses. The default
+    /// implementation sends the DCS `SetColumnAddress` and `SetPageAddress` commands.
+    /// Models for register based controllers, which don't implement these commands,
+    /// override this method.
+    fn set_address_window<DI>(
+        &mut self,
+        di: &mut DI,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(SetColumnAddress::new(sx, ex))?;
+        di.write_command(SetPageAddress::new(sy, ey))
+    }
+
+    /// Starts a memory write at the start of the address window.
+    ///
+    /// The default implementation sends the DCS `WriteMemoryStart` command.
+    fn write_memory_start<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(WriteMemoryStart)
+    }
+
+    /// Updates the address mode after the orientation or the color order was changed.
+    ///
+    /// The default implementation sends the DCS `SetAddressMode` command.
+    fn update_address_mode<DI>(
+        &mut self,
+        di: &mut DI,
+        address_mode: SetAddressMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(address_mode)
+    }
+
+    /// Enters or exits the idle mode.
+    ///
+    /// The default implementation sends the DCS `EnterIdleMode` or `ExitIdleMode` command.
+    fn set_idle_mode<DI>(&mut self, di: &mut DI, idle: bool) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        if idle {
+            di.write_command(EnterIdleMode)
+        } else {
+            di.write_command(ExitIdleMode)
+        }
+    }
+
+    /// Sets the partial area and enters the partial mode.
+    ///
+    /// The start and end rows are inclusive RAM rows. The default implementation sends the
+    /// DCS `SetPartialArea` and `EnterPartialMode` commands.
+    fn set_partial_area<DI>(
+        &mut self,
+        di: &mut DI,
+        start_row: u16,
+        end_row: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(SetPartialArea::new(start_row, end_row))?;
+        di.write_command(EnterPartialMode)
+    }
+
+    /// Exits the partial mode.
+    ///
+    /// The default implementation sends the DCS `EnterNormalMode` command.
+    fn exit_partial_mode<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_command(EnterNormalMode)
+    }
+
+    /// Sends the pixel data of a memory write.
+    ///
+    /// The default implementation converts the colors with the [`InterfacePixelFormat`] of
+    /// the color format, and sends 16-bit pixels in the byte order given by `endianness`.
+    /// Models which send the pixels in a different format than the color format, like
+    /// [`ILI9488Rgb565`], override this method together with
+    /// [`send_repeated_pixel`](Self::send_repeated_pixel) and
+    /// [`send_pixels_from_buffer`](Self::send_pixels_from_buffer). These models ignore
+    /// `endianness` unless they send 16-bit pixels.
+    fn send_pixels<DI>(
+        &mut self,
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self::ColorFormat>,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        Self::ColorFormat::send_pixels(di, pixels, endianness)
+    }
+
+    /// Sends the same pixel multiple times during a memory write.
+    ///
+    /// The default implementation converts the color with the [`InterfacePixelFormat`] of
+    /// the color format.
+    fn send_repeated_pixel<DI>(
+        &mut self,
+        di: &mut DI,
+        pixel: Self::ColorFormat,
+        count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        Self::ColorFormat::send_repeated_pixel(di, pixel, count, endianness)
+    }
+
+    /// Sends a buffer of raw pixel data during a memory write.
+    ///
+    /// The default implementation sends whole pixels in the format of the
+    /// [`InterfacePixelFormat`] of the color format.
+    fn send_pixels_from_buffer<DI>(
+        &mut self,
+        di: &mut DI,
+        data: &[DI::Word],
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        Self::ColorFormat::send_pixels_from_buffer(di, data)
+    }
+
+    /// Returns the number of bytes of raw pixel data for `pixels` pixels on 8-bit interfaces
+    /// of the given `kind`.
+    ///
+    /// Used by [`Display::write_raw_window`](crate::Display::write_raw_window) to check the
+    /// length of the pixel data. The default implementation is based on the bits per pixel of
+    /// the color format, with 18-bit colors sent as 3 bytes. Models which override
+    /// [`send_pixels`](Self::send_pixels) to send a different format need to override this
+    /// method too.
+    fn raw_pixel_data_len(&self, pixels: u32, _kind: InterfaceKind) -> usize {
+        let bits = match BitsPerPixel::from_rgb_color::<Self::ColorFormat>() {
+            BitsPerPixel::Three => 3,
+            BitsPerPixel::Eight => 8,
+            BitsPerPixel::Twelve => 12,
+            BitsPerPixel::Sixteen => 16,
+            BitsPerPixel::Eighteen | BitsPerPixel::TwentyFour => 24,
+        };
+        (pixels as usize * bits).div_ceil(8)
+    }
+}
+
+/// Page flipping capability of controllers with two memory pages.
+///
+/// Some controllers, e.g. with external display RAM, can show one memory page while the
+/// other page is written. Drawing into the hidden page and flipping the pages afterwards
+/// results in tear-free updates without a framebuffer on the microcontroller.
+///
+/// Models for these controllers implement this trait, which enables [`Display::flip`](crate::Display::flip).
+/// After [`Model::init`] the first page should be visible and the second page should be
+/// written by drawing operations.
+pub trait PageFlip: Model {
+    /// Shows the page which was written since the last flip and directs all following
+    /// memory writes to the other page.
+    ///
+    /// Implementations need to keep track of the visible page themselves.
+    fn flip<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface;
+}
+
+/// Brightness control capability of controllers which support the WRDISBV and WRCTRLD commands.
+///
+/// These controllers can dim the panel or drive the backlight through the command interface,
+/// which allows designs without a PWM controlled backlight to change the brightness.
+///
+/// Models for these controllers implement this trait, which enables
+/// [`Display::set_brightness`](crate::Display::set_brightness) and
+/// [`Display::set_brightness_control`](crate::Display::set_brightness_control).
+pub trait DisplayBrightness: Model {}
+
+/// Error returned by [`Model::init`].
+#[derive(Debug)]
+pub enum ModelInitError<DiError> {
+    /// Error caused by the display interface.
+    Interface(DiError),
+
+    /// Invalid configuration error.
+    InvalidConfiguration(ConfigurationError),
+}
+
+impl<DiError> From<DiError> for ModelInitError<DiError> {
+    fn from(value: DiError) -> Self {
+        Self::Interface(value)
+    }
+}
+
+/// Invalid configuration error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigurationError {
+    /// The controller doesn't support the color format on this kind of interface.
+    ///
+    /// Contains the combinations of interface kind and color depth which are supported
+    /// by the model, to make it easier to pick the right model variant for the interface.
+    UnsupportedInterface(&'static [(InterfaceKind, BitsPerPixel)]),
+    /// The display offset and the mounting correction result in addresses outside of the
+    /// controller address space.
+    InvalidOffset,
+}
+
+/// Commands and parameters of a fixed part of an init sequence.
+///
+/// The parts of init sequences which don't depend on the [`ModelOptions`] are stored in
+/// constant tables, which are placed in flash instead of being built by code at runtime.
+pub(crate) type Commands = &'static [(u8, &'static [u8])];
+
+/// Turns on the display at the end of an init sequence.
+///
+/// The display is left turned off if [`ModelOptions::display_on`] is `false`. `delay_us`
+/// is the [`Model::DISPLAY_ON_DELAY_US`] of the model.
+pub(crate) fn turn_on_display<DI, DELAY>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    delay_us: u32,
+) -> Result<(), DI::Error>
+where
+    DI: Interface,
+    DELAY: DelayNs,
+{
+    if options.display_on {
+        di.write_command(SetDisplayOn)?;
+        delay.delay_us(delay_us);
+    }
+
+    Ok(())
+}
+
+/// Sends all commands of a table.
+pub(crate) fn write_commands<DI: Interface>(
+    di: &mut DI,
+    commands: Commands,
+) -> Result<(), DI::Error> {
+    for &(command, params) in commands {
+        di.write_raw(command, params)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{
+        draw_target::DrawTarget,
+        pixelcolor::{Rgb565, RgbColor},
+    };
+
+    use crate::{
+        _mock::MockDelay,
+        _test::RecordingInterface,
+        options::{Mounting, Orientation, Rotation},
+        Builder,
+    };
+
+    use super::*;
+
+    /// Expected address window for the whole display, with inclusive start and end addresses.
+    struct Window {
+        rotation: Rotation,
+        mirrored: bool,
+        columns: (u16, u16),
+        pages: (u16, u16),
+    }
+
+    const fn window(
+        rotation: Rotation,
+        mirrored: bool,
+        columns: (u16, u16),
+        pages: (u16, u16),
+    ) -> Window {
+        Window {
+            rotation,
+            mirrored,
+            columns,
+            pages,
+        }
+    }
+
+    fn address(args: [u8; 4]) -> (u16, u16) {
+        (
+            u16::from_be_bytes([args[0], args[1]]),
+            u16::from_be_bytes([args[2], args[3]]),
+        )
+    }
+
+    // Checks the address window for every orientation of a panel, which is smaller than the
+    // framebuffer of the controller.
+    fn check_windows<M>(
+        model: impl Fn() -> M,
+        size: (u16, u16),
+        offset: (u16, u16),
+        expected: &[Window; 8],
+    ) where
+        M: Model<ColorFormat = Rgb565>,
+    {
+        for window in expected {
+            let orientation = Orientation {
+                rotation: window.rotation,
+                mirrored: window.mirrored,
+            };
+            let mut display = Builder::new(model(), RecordingInterface::default())
+                .display_size(size.0, size.1)
+                .display_offset(offset.0, offset.1)
+                .orientation(orientation)
+                .init(&mut MockDelay)
+                .unwrap();
+
+            display.clear(Rgb565::BLACK).unwrap();
+            let di = unsafe { display.dcs() };
+            assert_eq!(address(di.columns), window.columns, "{orientation:?}");
+            assert_eq!(address(di.pages), window.pages, "{orientation:?}");
+
+            // Single pixels are drawn relative to the same offset
+            display.set_pixel(1, 2, Rgb565::RED).unwrap();
+            let di = unsafe { display.dcs() };
+            let start = (window.columns.0 + 1, window.pages.0 + 2);
+            assert_eq!(address(di.columns), (start.0, start.0), "{orientation:?}");
+            assert_eq!(address(di.pages), (start.1, start.1), "{orientation:?}");
+
+            // Changing the orientation at runtime results in the same window
+            let mut display = Builder::new(model(), RecordingInterface::default())
+                .display_size(size.0, size.1)
+                .display_offset(offset.0, offset.1)
+                .init(&mut MockDelay)
+                .unwrap();
+            display.set_orientation(orientation).unwrap();
+            assert_eq!(display.orientation(), orientation);
+
+            display.clear(Rgb565::BLACK).unwrap();
+            let di = unsafe { display.dcs() };
+            assert_eq!(address(di.columns), window.columns, "{orientation:?}");
+            assert_eq!(address(di.pages), window.pages, "{orientation:?}");
+        }
+    }
+
+    /// Model with two memory pages, which are selected by a vendor command.
+    struct PageFlipModel {
+        page: u8,
+    }
+
+    impl Model for PageFlipModel {
+        type ColorFormat = Rgb565;
+        const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+
+        fn init<DELAY, DI>(
+            &mut self,
+            _di: &mut DI,
+            _delay: &mut DELAY,
+            options: &ModelOptions,
+        ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+        where
+            DELAY: DelayNs,
+            DI: Interface,
+        {
+            Ok(SetAddressMode::from(options))
+        }
+    }
+
+    impl PageFlip for PageFlipModel {
+        fn flip<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+        where
+            DI: Interface,
+        {
+            self.page ^= 1;
+            di.send_command(0xF0, &[self.page])
+        }
+    }
+
+    #[test]
+    fn flip_is_forwarded_to_model() {
+        let mut display = Builder::new(PageFlipModel { page: 0 }, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.flip().unwrap();
+        display.flip().unwrap();
+        display.flip().unwrap();
+
+        let (_, model, _, _) = display.release();
+        assert_eq!(model.page, 1);
+    }
+
+    #[test]
+    fn mounting_is_applied_in_addition_to_the_orientation() {
+        let mounting = Mounting::new()
+            .orientation(Orientation::new().rotate(Rotation::Deg180))
+            .offset(2, -1);
+        let mut display = Builder::new(ST7789, RecordingInterface::default())
+            .display_size(135, 240)
+            .display_offset(52, 40)
+            .mounting(mounting)
+            .orientation(Orientation::new().rotate(Rotation::Deg90))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        // Same window as for a 270° orientation without mounting, shifted by the correction
+        display.clear(Rgb565::BLACK).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(address(di.columns), (39, 278));
+        assert_eq!(address(di.pages), (54, 188));
+
+        display.set_orientation(Orientation::new()).unwrap();
+        display.clear(Rgb565::BLACK).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(address(di.columns), (55, 189));
+        assert_eq!(address(di.pages), (39, 278));
+    }
+
+    #[test]
+    fn st7789_135x240() {
+        // e.g. TTGO T-Display
+        check_windows(
+            || ST7789,
+            (135, 240),
+            (52, 40),
+            &[
+                window(Rotation::Deg0, false, (52, 186), (40, 279)),
+                window(Rotation::Deg0, true, (53, 187), (40, 279)),
+                window(Rotation::Deg90, false, (40, 279), (53, 187)),
+                window(Rotation::Deg90, true, (40, 279), (52, 186)),
+                window(Rotation::Deg180, false, (53, 187), (40, 279)),
+                window(Rotation::Deg180, true, (52, 186), (40, 279)),
+                window(Rotation::Deg270, false, (40, 279), (52, 186)),
+                window(Rotation::Deg270, true, (40, 279), (53, 187)),
+            ],
+        );
+    }
+
+    #[test]
+    fn st7735s_80x160() {
+        // 0.96" panels, the visible area is centered in the framebuffer
+        check_windows(
+            || ST7735s,
+            (80, 160),
+            (26, 1),
+            &[
+                window(Rotation::Deg0, false, (26, 105), (1, 160)),
+                window(Rotation::Deg0, true, (26, 105), (1, 160)),
+                window(Rotation::Deg90, false, (1, 160), (26, 105)),
+                window(Rotation::Deg90, true, (1, 160), (26, 105)),
+                window(Rotation::Deg180, false, (26, 105), (1, 160)),
+                window(Rotation::Deg180, true, (26, 105), (1, 160)),
+                window(Rotation::Deg270, false, (1, 160), (26, 105)),
+                window(Rotation::Deg270, true, (1, 160), (26, 105)),
+            ],
+        );
+    }
+
+    #[test]
+    fn st7735r_128x128() {
+        // 1.44" panels, the visible area isn't centered in the framebuffer rows
+        check_windows(
+            || ST7735R,
+            (128, 128),
+            (2, 3),
+            &[
+                window(Rotation::Deg0, false, (2, 129), (3, 130)),
+                window(Rotation::Deg0, true, (2, 129), (3, 130)),
+                window(Rotation::Deg90, false, (3, 130), (2, 129)),
+                window(Rotation::Deg90, true, (3, 130), (2, 129)),
+                window(Rotation::Deg180, false, (2, 129), (31, 158)),
+                window(Rotation::Deg180, true, (2, 129), (31, 158)),
+                window(Rotation::Deg270, false, (31, 158), (2, 129)),
+                window(Rotation::Deg270, true, (31, 158), (2, 129)),
+            ],
+        );
+    }
+
+    #[test]
+    fn gc9107_128x128() {
+        // e.g. M5Stack AtomS3, the visible area is at the end of the framebuffer rows
+        check_windows(
+            || GC9107,
+            (128, 128),
+            (0, 32),
+            &[
+                window(Rotation::Deg0, false, (0, 127), (32, 159)),
+                window(Rotation::Deg0, true, (0, 127), (32, 159)),
+                window(Rotation::Deg90, false, (32, 159), (0, 127)),
+                window(Rotation::Deg90, true, (32, 159), (0, 127)),
+                window(Rotation::Deg180, false, (0, 127), (0, 127)),
+                window(Rotation::Deg180, true, (0, 127), (0, 127)),
+                window(Rotation::Deg270, false, (0, 127), (0, 127)),
+                window(Rotation::Deg270, true, (0, 127), (0, 127)),
+            ],
+        );
+    }
+
+    #[test]
+    fn ili9327_240x400() {
+        // 3.2" panels, which only use the first 400 of the 432 RAM lines
+        check_windows(
+            || ILI9327Rgb565,
+            (240, 400),
+            (0, 0),
+            &[
+                window(Rotation::Deg0, false, (0, 239), (0, 399)),
+                window(Rotation::Deg0, true, (0, 239), (0, 399)),
+                window(Rotation::Deg90, false, (0, 399), (0, 239)),
+                window(Rotation::Deg90, true, (0, 399), (0, 239)),
+                window(Rotation::Deg180, false, (0, 239), (32, 431)),
+                window(Rotation::Deg180, true, (0, 239), (32, 431)),
+                window(Rotation::Deg270, false, (32, 431), (0, 239)),
+                window(Rotation::Deg270, true, (32, 431), (0, 239)),
+            ],
+        );
+    }
+
+    #[test]
+    fn gc9307_240x280() {
+        // 1.69" panels, the visible area is centered in the framebuffer rows
+        check_windows(
+            || GC9307,
+            (240, 280),
+            (0, 20),
+            &[
+                window(Rotation::Deg0, false, (0, 239), (20, 299)),
+                window(Rotation::Deg0, true, (0, 239), (20, 299)),
+                window(Rotation::Deg90, false, (20, 299), (0, 239)),
+                window(Rotation::Deg90, true, (20, 299), (0, 239)),
+                window(Rotation::Deg180, false, (0, 239), (20, 299)),
+                window(Rotation::Deg180, true, (0, 239), (20, 299)),
+                window(Rotation::Deg270, false, (20, 299), (0, 239)),
+                window(Rotation::Deg270, true, (20, 299), (0, 239)),
+            ],
+        );
+    }
 }
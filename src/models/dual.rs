@@ -0,0 +1,113 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::SetAddressMode,
+    interface::{Interface, InterfaceKind, InterfacePixelFormat},
+    models::{Model, ModelInitError},
+    options::{ColorInversion, ColorOrder, Endianness, ModelOptions},
+};
+
+/// Panel driven by two controllers of the same model.
+///
+/// The framebuffer contains the rows of both controllers and must be used with a
+/// [`DualInterface`](crate::interface::DualInterface), which splits the drawing operations
+/// between the controllers. The init sequence of the wrapped model is sent to both
+/// controllers.
+pub struct Dual<M>(pub M);
+
+impl<M: Model> Model for Dual<M> {
+    type ColorFormat = M::ColorFormat;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (M::FRAMEBUFFER_SIZE.0, M::FRAMEBUFFER_SIZE.1 * 2);
+    const DEFAULT_COLOR_ORDER: ColorOrder = M::DEFAULT_COLOR_ORDER;
+    const DEFAULT_COLOR_INVERSION: ColorInversion = M::DEFAULT_COLOR_INVERSION;
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = M::MAX_SERIAL_CLOCK_HZ;
+    const DISPLAY_ON_DELAY_US: u32 = M::DISPLAY_ON_DELAY_US;
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        self.0.init(di, delay, options)
+    }
+
+    fn set_address_window<DI>(
+        &mut self,
+        di: &mut DI,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.0.set_address_window(di, sx, sy, ex, ey)
+    }
+
+    fn write_memory_start<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.0.write_memory_start(di)
+    }
+
+    fn update_address_mode<DI>(
+        &mut self,
+        di: &mut DI,
+        address_mode: SetAddressMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        self.0.update_address_mode(di, address_mode)
+    }
+
+    fn send_pixels<DI>(
+        &mut self,
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self::ColorFormat>,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        self.0.send_pixels(di, pixels, endianness)
+    }
+
+    fn send_repeated_pixel<DI>(
+        &mut self,
+        di: &mut DI,
+        pixel: Self::ColorFormat,
+        count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        self.0.send_repeated_pixel(di, pixel, count, endianness)
+    }
+
+    fn send_pixels_from_buffer<DI>(
+        &mut self,
+        di: &mut DI,
+        data: &[DI::Word],
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        self.0.send_pixels_from_buffer(di, data)
+    }
+
+    fn raw_pixel_data_len(&self, pixels: u32, kind: InterfaceKind) -> usize {
+        self.0.raw_pixel_data_len(pixels, kind)
+    }
+}
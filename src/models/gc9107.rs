@@ -3,15 +3,55 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
     },
     interface::Interface,
+    models::{turn_on_display, write_commands, Commands, ModelInitError},
     options::ModelOptions,
 };
 
 use super::Model;
 
+// Commands before the address mode.
+const COMMANDS_1: Commands = &[
+    (0xB0, &[0xC0]),
+    (0xB2, &[0x2F]),
+    (0xB3, &[0x03]),
+    (0xB6, &[0x19]),
+    (0xB7, &[0x01]),
+];
+
+// Commands between the address mode and the pixel format.
+const COMMANDS_2: Commands = &[
+    (0xAC, &[0xCB]),
+    (0xAB, &[0x0E]),
+    (0xB4, &[0x04]),
+    (0xA8, &[0x19]),
+];
+
+// Commands after the pixel format.
+const COMMANDS_3: Commands = &[
+    (0xB8, &[0x08]),
+    (0xE8, &[0x24]),
+    (0xE9, &[0x48]),
+    (0xEA, &[0x22]),
+    (0xC6, &[0x30]),
+    (0xC7, &[0x18]),
+    (
+        0xF0,
+        &[
+            0x01, 0x2b, 0x23, 0x3c, 0xb7, 0x12, 0x17, 0x60, 0x00, 0x06, 0x0c, 0x17, 0x12, 0x1f,
+        ],
+    ),
+    (
+        0xF1,
+        &[
+            0x05, 0x2e, 0x2d, 0x44, 0xd6, 0x15, 0x17, 0xa0, 0x02, 0x0d, 0x0d, 0x1a, 0x18, 0x1f,
+        ],
+    ),
+];
+
 /// GC9107 display in Rgb565 color mode.
 pub struct GC9107;
 
@@ -24,7 +64,7 @@ impl Model for GC9107 {
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -36,56 +76,24 @@ impl Model for GC9107 {
         di.write_raw(0xEF, &[])?;
         delay.delay_ms(5);
 
-        di.write_raw(0xB0, &[0xC0])?;
-        di.write_raw(0xB2, &[0x2F])?;
-        di.write_raw(0xB3, &[0x03])?;
-        di.write_raw(0xB6, &[0x19])?;
-        di.write_raw(0xB7, &[0x01])?;
+        write_commands(di, COMMANDS_1)?;
 
         let madctl = SetAddressMode::from(options);
         di.write_command(madctl)?;
 
-        di.write_raw(0xAC, &[0xCB])?;
-        di.write_raw(0xAB, &[0x0E])?;
-
-        di.write_raw(0xB4, &[0x04])?;
-
-        di.write_raw(0xA8, &[0x19])?;
+        write_commands(di, COMMANDS_2)?;
 
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
         di.write_command(SetPixelFormat::new(pf))?;
 
-        di.write_raw(0xB8, &[0x08])?;
-
-        di.write_raw(0xE8, &[0x24])?;
-
-        di.write_raw(0xE9, &[0x48])?;
-
-        di.write_raw(0xEA, &[0x22])?;
-
-        di.write_raw(0xC6, &[0x30])?;
-        di.write_raw(0xC7, &[0x18])?;
-
-        di.write_raw(
-            0xF0,
-            &[
-                0x01, 0x2b, 0x23, 0x3c, 0xb7, 0x12, 0x17, 0x60, 0x00, 0x06, 0x0c, 0x17, 0x12, 0x1f,
-            ],
-        )?;
-
-        di.write_raw(
-            0xF1,
-            &[
-                0x05, 0x2e, 0x2d, 0x44, 0xd6, 0x15, 0x17, 0xa0, 0x02, 0x0d, 0x0d, 0x1a, 0x18, 0x1f,
-            ],
-        )?;
+        write_commands(di, COMMANDS_3)?;
 
         di.write_command(SetInvertMode::new(options.invert_colors))?;
 
         di.write_command(ExitSleepMode)?; // turn off sleep
         delay.delay_ms(120);
 
-        di.write_command(SetDisplayOn)?; // turn on display
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
         Ok(madctl)
     }
@@ -0,0 +1,85 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
+    },
+    interface::Interface,
+    models::{turn_on_display, write_commands, Commands, ModelInitError},
+    options::ModelOptions,
+};
+
+use super::Model;
+
+// Commands after the address mode and pixel format.
+const COMMANDS: Commands = &[
+    (0x85, &[0xC0]),
+    (0x86, &[0x98]),
+    (0x87, &[0x28]),
+    (0x89, &[0x33]),
+    (0x8B, &[0x84]),
+    (0x8D, &[0x3B]),
+    (0x8E, &[0x0F]),
+    (0x8F, &[0x70]),
+    (0xE8, &[0x13, 0x17]),
+    (0xEC, &[0x57, 0x07, 0xFF]),
+    (0xED, &[0x18, 0x09]),
+    (0xC9, &[0x10]),
+    (0xFF, &[0x61]),
+    (0x99, &[0x3A]),
+    (0x9D, &[0x43]),
+    (0x98, &[0x3E]),
+    (0x9C, &[0x4B]),
+    (0xF0, &[0x06, 0x08, 0x08, 0x06, 0x05, 0x1D]),
+    (0xF2, &[0x00, 0x01, 0x09, 0x07, 0x04, 0x23]),
+    (0xF1, &[0x3B, 0x68, 0x66, 0x36, 0x35, 0x2F]),
+    (0xF3, &[0x37, 0x6C, 0x66, 0x39, 0x38, 0x2F]),
+];
+
+/// GC9307 display in Rgb565 color mode.
+///
+/// The GC9307 is used in 1.69" and 2.0" 240x280 panels, which need to be configured with
+/// `display_size(240, 280)` and `display_offset(0, 20)`. These panels are often sold as ST7789
+/// panels, but the [`ST7789`](crate::models::ST7789) model doesn't initialize them correctly.
+pub struct GC9307;
+
+impl Model for GC9307 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        delay.delay_ms(120);
+
+        // enable the inter registers
+        di.write_raw(0xFE, &[])?;
+        di.write_raw(0xEF, &[])?;
+
+        let madctl = SetAddressMode::from(options);
+        di.write_command(madctl)?;
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?;
+
+        write_commands(di, COMMANDS)?;
+
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+        di.write_command(ExitSleepMode)?; // turn off sleep
+        delay.delay_ms(120);
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
@@ -3,11 +3,12 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
     },
     interface::Interface,
-    options::ModelOptions,
+    models::{turn_on_display, write_commands, Commands, ModelInitError},
+    options::{ColorInversion, ColorOrder, ModelOptions},
 };
 
 use super::Model;
@@ -15,6 +16,19 @@ use super::Model;
 /// GC9A01 display in Rgb565 color mode.
 pub struct GC9A01;
 
+/// GC9A01A display in Rgb565 color mode.
+///
+/// Newer round 240x240 panels use the GC9A01A revision, which needs slightly different
+/// display function and voltage settings than the [`GC9A01`]. These panels usually use
+/// the BGR color order and inverted colors, which are the default options of this model.
+pub struct GC9A01A;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Revision {
+    GC9A01,
+    GC9A01A,
+}
+
 impl Model for GC9A01 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 240);
@@ -24,102 +38,166 @@ impl Model for GC9A01 {
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
-        let madctl = SetAddressMode::from(options);
-
-        delay.delay_us(200_000);
-
-        di.write_raw(0xEF, &[])?; // inter register enable 2
-        di.write_raw(0xEB, &[0x14])?;
-        di.write_raw(0xFE, &[])?; // inter register enable 1
-        di.write_raw(0xEF, &[])?; // inter register enable 2
-        di.write_raw(0xEB, &[0x14])?;
-
-        di.write_raw(0x84, &[0x40])?;
-        di.write_raw(0x85, &[0xFF])?;
-        di.write_raw(0x86, &[0xFF])?;
-        di.write_raw(0x87, &[0xFF])?;
-        di.write_raw(0x88, &[0x0A])?;
-        di.write_raw(0x89, &[0x21])?;
-        di.write_raw(0x8A, &[0x00])?;
-        di.write_raw(0x8B, &[0x80])?;
-        di.write_raw(0x8C, &[0x01])?;
-        di.write_raw(0x8D, &[0x01])?;
-        di.write_raw(0x8E, &[0xFF])?;
-        di.write_raw(0x8F, &[0xFF])?;
-
-        di.write_raw(0xB6, &[0x00, 0x20])?; // display function control
-
-        di.write_command(madctl)?; // set memory data access control, Top -> Bottom, RGB, Left -> Right
-
-        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
-
-        di.write_raw(0x90, &[0x08, 0x08, 0x08, 0x08])?;
-        di.write_raw(0xBD, &[0x06])?;
-        di.write_raw(0xBC, &[0x00])?;
-        di.write_raw(0xFF, &[0x60, 0x01, 0x04])?;
-
-        di.write_raw(0xC3, &[0x13])?; // power control 2
-        di.write_raw(0xC4, &[0x13])?; // power control 3
-        di.write_raw(0xC9, &[0x22])?; // power control 4
-
-        di.write_raw(0xBE, &[0x11])?;
-        di.write_raw(0xE1, &[0x10, 0x0E])?;
-        di.write_raw(0xDF, &[0x20, 0x0c, 0x02])?;
+        let madctl = init_common(di, delay, options, Revision::GC9A01)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
-        di.write_raw(0xF0, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A])?; // gamma 1
-        di.write_raw(0xF1, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6f])?; // gamma 2
-        di.write_raw(0xF2, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A])?; // gamma 3
-        di.write_raw(0xF3, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6f])?; // gamma 4
+        Ok(madctl)
+    }
+}
 
-        di.write_raw(0xED, &[0x18, 0x0B])?;
-        di.write_raw(0xAE, &[0x77])?;
-        di.write_raw(0xCD, &[0x63])?;
-
-        di.write_raw(
-            0x70,
-            &[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03],
-        )?;
-
-        di.write_raw(0xE8, &[0x34])?; // framerate
-
-        di.write_raw(
-            0x62,
-            &[
-                0x18, 0x0D, 0x71, 0xED, 0x70, 0x70, 0x18, 0x0F, 0x71, 0xEF, 0x70, 0x70,
-            ],
-        )?;
-        di.write_raw(
-            0x63,
-            &[
-                0x18, 0x11, 0x71, 0xF1, 0x70, 0x70, 0x18, 0x13, 0x71, 0xF3, 0x70, 0x70,
-            ],
-        )?;
-        di.write_raw(0x64, &[0x28, 0x29, 0xF1, 0x01, 0xF1, 0x00, 0x07])?;
-        di.write_raw(
-            0x66,
-            &[0x3C, 0x00, 0xCD, 0x67, 0x45, 0x45, 0x10, 0x00, 0x00, 0x00],
-        )?;
-        di.write_raw(
-            0x67,
-            &[0x00, 0x3C, 0x00, 0x00, 0x00, 0x01, 0x54, 0x10, 0x32, 0x98],
-        )?;
-
-        di.write_raw(0x74, &[0x10, 0x85, 0x80, 0x00, 0x00, 0x4E, 0x00])?;
-        di.write_raw(0x98, &[0x3e, 0x07])?;
-
-        di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
-
-        di.write_command(ExitSleepMode)?; // turn off sleep
-        delay.delay_us(120_000);
-
-        di.write_command(SetDisplayOn)?; // turn on display
+impl Model for GC9A01A {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 240);
+    const DEFAULT_COLOR_ORDER: ColorOrder = ColorOrder::Bgr;
+    const DEFAULT_COLOR_INVERSION: ColorInversion = ColorInversion::Inverted;
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = init_common(di, delay, options, Revision::GC9A01A)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
         Ok(madctl)
     }
 }
+
+// Commands before the address mode and pixel format.
+const COMMANDS_1: Commands = &[
+    (0xEF, &[]), // inter register enable 2
+    (0xEB, &[0x14]),
+    (0xFE, &[]), // inter register enable 1
+    (0xEF, &[]), // inter register enable 2
+    (0xEB, &[0x14]),
+    (0x84, &[0x40]),
+    (0x85, &[0xFF]),
+    (0x86, &[0xFF]),
+    (0x87, &[0xFF]),
+    (0x88, &[0x0A]),
+    (0x89, &[0x21]),
+    (0x8A, &[0x00]),
+    (0x8B, &[0x80]),
+    (0x8C, &[0x01]),
+    (0x8D, &[0x01]),
+    (0x8E, &[0xFF]),
+    (0x8F, &[0xFF]),
+];
+
+// Commands after the address mode and pixel format.
+const COMMANDS_2: Commands = &[
+    (0x90, &[0x08, 0x08, 0x08, 0x08]),
+    (0xBD, &[0x06]),
+    (0xBC, &[0x00]),
+    (0xFF, &[0x60, 0x01, 0x04]),
+    (0xC3, &[0x13]), // power control 2
+    (0xC4, &[0x13]), // power control 3
+    (0xC9, &[0x22]), // power control 4
+    (0xBE, &[0x11]),
+    (0xE1, &[0x10, 0x0E]),
+];
+
+// Gamma settings.
+const GAMMA: Commands = &[
+    (0xF0, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A]), // gamma 1
+    (0xF1, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6f]), // gamma 2
+    (0xF2, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A]), // gamma 3
+    (0xF3, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6f]), // gamma 4
+];
+
+// Commands after the gamma settings.
+const COMMANDS_3: Commands = &[
+    (0xAE, &[0x77]),
+    (0xCD, &[0x63]),
+    (
+        0x70,
+        &[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03],
+    ),
+    (0xE8, &[0x34]), // framerate
+    (
+        0x62,
+        &[
+            0x18, 0x0D, 0x71, 0xED, 0x70, 0x70, 0x18, 0x0F, 0x71, 0xEF, 0x70, 0x70,
+        ],
+    ),
+    (
+        0x63,
+        &[
+            0x18, 0x11, 0x71, 0xF1, 0x70, 0x70, 0x18, 0x13, 0x71, 0xF3, 0x70, 0x70,
+        ],
+    ),
+    (0x64, &[0x28, 0x29, 0xF1, 0x01, 0xF1, 0x00, 0x07]),
+    (
+        0x66,
+        &[0x3C, 0x00, 0xCD, 0x67, 0x45, 0x45, 0x10, 0x00, 0x00, 0x00],
+    ),
+    (
+        0x67,
+        &[0x00, 0x3C, 0x00, 0x00, 0x00, 0x01, 0x54, 0x10, 0x32, 0x98],
+    ),
+    (0x74, &[0x10, 0x85, 0x80, 0x00, 0x00, 0x4E, 0x00]),
+    (0x98, &[0x3e, 0x07]),
+];
+
+fn init_common<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    revision: Revision,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    let gc9a01a = revision == Revision::GC9A01A;
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(200_000);
+
+    write_commands(di, COMMANDS_1)?;
+
+    // display function control
+    if gc9a01a {
+        di.write_raw(0xB6, &[0x00, 0x00])?;
+    } else {
+        di.write_raw(0xB6, &[0x00, 0x20])?;
+    }
+
+    di.write_command(madctl)?; // set memory data access control, Top -> Bottom, RGB, Left -> Right
+
+    let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Rgb565>());
+    di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
+
+    write_commands(di, COMMANDS_2)?;
+    if gc9a01a {
+        di.write_raw(0xDF, &[0x21, 0x0c, 0x02])?;
+    } else {
+        di.write_raw(0xDF, &[0x20, 0x0c, 0x02])?;
+    }
+
+    write_commands(di, GAMMA)?;
+
+    if gc9a01a {
+        di.write_raw(0xED, &[0x1B, 0x0B])?;
+    } else {
+        di.write_raw(0xED, &[0x18, 0x0B])?;
+    }
+    write_commands(di, COMMANDS_3)?;
+
+    di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
+
+    di.write_command(ExitSleepMode)?; // turn off sleep
+    delay.delay_us(120_000);
+
+    Ok(madctl)
+}
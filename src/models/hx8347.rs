@@ -0,0 +1,235 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{DcsCommand, InterfaceExt, SetAddressMode},
+    interface::Interface,
+    models::{Model, ModelInitError},
+    options::ModelOptions,
+};
+
+// Register indices
+const COLUMN_ADDRESS_START: u8 = 0x02;
+const ROW_ADDRESS_START: u8 = 0x06;
+const MEMORY_ACCESS_CONTROL: u8 = 0x16;
+const COLMOD: u8 = 0x17;
+const SRAM_WRITE: u8 = 0x22;
+const DISPLAY_CONTROL: u8 = 0x28;
+const POWER_CONTROL: u8 = 0x1F;
+
+/// HX8347-D and HX8347-G displays in Rgb565 color mode.
+///
+/// Unlike most other supported controllers, the HX8347 doesn't implement the MIPI DCS
+/// commands, but uses 8-bit registers, which are written by sending the register index as a
+/// command with the register value as its only parameter. The address window, the memory
+/// write and the address mode are set with the corresponding registers, but the
+/// functions of [`Display`](crate::Display) which send other DCS commands, like
+/// [`sleep`](crate::Display::sleep), [`set_invert_colors`](crate::Display::set_invert_colors),
+/// the scrolling or the tearing effect functions, aren't supported by this model.
+///
+/// Color inversion isn't configured by the init sequence, the
+/// [`invert_colors`](crate::Builder::invert_colors) option is ignored.
+pub struct HX8347;
+
+impl Model for HX8347 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        // Vendor timing and source driver settings
+        for (register, value) in [
+            (0x2E, 0x89),
+            (0x29, 0x8F),
+            (0x2B, 0x02),
+            (0xE2, 0x00),
+            (0xE4, 0x01),
+            (0xE5, 0x10),
+            (0xE6, 0x01),
+            (0xE7, 0x10),
+            (0xE8, 0x70),
+            (0xF2, 0x00),
+            (0xEA, 0x00),
+            (0xEB, 0x20),
+            (0xEC, 0x3C),
+            (0xED, 0xC8),
+            (0xE9, 0x38),
+            (0xF1, 0x01),
+        ] {
+            di.write_raw(register, &[value])?;
+        }
+
+        // Power supply and VCOM settings
+        di.write_raw(0x1B, &[0x1A])?;
+        di.write_raw(0x1A, &[0x02])?;
+        di.write_raw(0x24, &[0x61])?;
+        di.write_raw(0x25, &[0x5C])?;
+
+        // Start the oscillator and the power supply step by step
+        di.write_raw(0x18, &[0x36])?;
+        di.write_raw(0x19, &[0x01])?;
+        for value in [0x88, 0x80, 0x90, 0xD4] {
+            di.write_raw(POWER_CONTROL, &[value])?;
+            delay.delay_us(5_000);
+        }
+
+        di.write_raw(COLMOD, &[0x05])?; // 16 bits per pixel
+        di.write_raw(0x36, &[0x09])?; // panel characteristics
+        self.update_address_mode(di, madctl)?;
+
+        // Turn on the panel: GON = 1, DTE = 1 and D = 10, followed by D = 11
+        di.write_raw(DISPLAY_CONTROL, &[0x38])?;
+        delay.delay_us(40_000);
+        di.write_raw(DISPLAY_CONTROL, &[0x3C])?;
+
+        Ok(madctl)
+    }
+
+    fn set_address_window<DI>(
+        &mut self,
+        di: &mut DI,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The start and end addresses are stored in four consecutive registers each,
+        // starting with the high byte of the start address.
+        for (base, start, end) in [(COLUMN_ADDRESS_START, sx, ex), (ROW_ADDRESS_START, sy, ey)] {
+            let [start_high, start_low] = start.to_be_bytes();
+            let [end_high, end_low] = end.to_be_bytes();
+            for (register, value) in (base..).zip([start_high, start_low, end_high, end_low]) {
+                di.write_raw(register, &[value])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_memory_start<DI>(&mut self, di: &mut DI) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        di.write_raw(SRAM_WRITE, &[])
+    }
+
+    fn update_address_mode<DI>(
+        &mut self,
+        di: &mut DI,
+        address_mode: SetAddressMode,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The memory access control register uses the same bits as MADCTL.
+        let mut value = [0];
+        address_mode.fill_params_buf(&mut value);
+        di.write_raw(MEMORY_ACCESS_CONTROL, &value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_graphics_core::pixelcolor::RgbColor;
+
+    use crate::{
+        _mock::MockDelay,
+        interface::InterfaceKind,
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    use super::*;
+
+    // Records the values of the registers.
+    struct RegisterInterface {
+        registers: [u8; 256],
+        last: u8,
+        pixels: u32,
+    }
+
+    impl Interface for RegisterInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel8Bit;
+
+        fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+            assert!(
+                args.len() <= 1,
+                "register 0x{command:02X} with multiple values"
+            );
+            if let [value] = *args {
+                self.registers[usize::from(command)] = value;
+            }
+            self.last = command;
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            assert_eq!(self.last, SRAM_WRITE);
+            self.pixels += pixels.into_iter().count() as u32;
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            assert_eq!(self.last, SRAM_WRITE);
+            self.pixels += count;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn windows_and_address_mode_use_registers() {
+        let di = RegisterInterface {
+            registers: [0; 256],
+            last: 0,
+            pixels: 0,
+        };
+        let mut display = Builder::new(HX8347, di)
+            .orientation(Orientation::new().rotate(Rotation::Deg90))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.registers[usize::from(MEMORY_ACCESS_CONTROL)], 0x60);
+
+        display
+            .set_pixels(1, 2, 300, 201, [Rgb565::RED; 4])
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(
+            di.registers[0x02..0x0A],
+            [0x00, 0x01, 0x01, 0x2C, 0x00, 0x02, 0x00, 0xC9]
+        );
+        assert_eq!(di.pixels, 4);
+
+        display.set_orientation(Orientation::new()).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.registers[usize::from(MEMORY_ACCESS_CONTROL)], 0x00);
+    }
+}
@@ -0,0 +1,109 @@
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetInvertMode, SetPixelFormat,
+    },
+    interface::Interface,
+    models::{turn_on_display, ModelInitError},
+    options::ModelOptions,
+};
+
+use super::Model;
+
+/// ILI9327 display in Rgb565 color mode.
+///
+/// The ILI9327 has RAM for 240x432 pixels, which is used completely by 3.0" 240x432 panels.
+/// Panels with 240x400 pixels use the first 400 RAM lines and must be configured with
+/// `display_size(240, 400)`. The driver then automatically offsets the address window by 32
+/// lines in orientations which reverse the order of the rows.
+pub struct ILI9327Rgb565;
+
+/// ILI9327 display in Rgb666 color mode.
+///
+/// See [`ILI9327Rgb565`] for information about the display size.
+pub struct ILI9327Rgb666;
+
+impl Model for ILI9327Rgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 432);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+impl Model for ILI9327Rgb666 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 432);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+// common init for all color format models
+fn init_common<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    let madctl = SetAddressMode::from(options);
+
+    // The reset might have implicitly called the Sleep In command and SLPOUT must not be
+    // sent within 120 ms after the reset.
+    delay.delay_us(120_000);
+
+    di.write_command(ExitSleepMode)?;
+    delay.delay_us(120_000);
+
+    di.write_raw(0xB0, &[0x00])?; // unlock the manufacturer commands
+                                  // Panel driving setting: NL = 0x35 drives all (0x35 + 1) * 8 = 432 lines, the
+                                  // partial and scroll areas are therefore defined in the range of 432 RAM lines.
+    di.write_raw(0xC0, &[0x00, 0x35, 0x00, 0x00, 0x01, 0x02])?;
+    di.write_raw(0xC1, &[0x10, 0x10, 0x02, 0x02])?; // display timing for normal mode
+    di.write_raw(0xC5, &[0x04])?; // frame rate
+    di.write_raw(0xD2, &[0x01, 0x04])?; // power setting for normal mode
+    di.write_raw(0xCA, &[0x00])?; // DGC LUT
+    di.write_raw(0xEA, &[0x80])?; // enable 3 gamma
+
+    di.write_command(madctl)?;
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+    di.write_command(SetPixelFormat::new(pixel_format))?;
+    di.write_command(EnterNormalMode)?;
+
+    Ok(madctl)
+}
@@ -2,9 +2,11 @@ use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
 use embedded_hal::delay::DelayNs;
 
 use crate::{
-    dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
-    interface::Interface,
-    models::{ili934x, Model},
+    dcs::{BitsPerPixel, InterfaceExt, PixelFormat, SetAddressMode},
+    interface::{Interface, InterfaceKind},
+    models::{
+        ili934x, turn_on_display, ConfigurationError, DisplayBrightness, Model, ModelInitError,
+    },
     options::ModelOptions,
 };
 
@@ -14,40 +16,128 @@ pub struct ILI9341Rgb565;
 /// ILI9341 display in Rgb666 color mode.
 pub struct ILI9341Rgb666;
 
+/// ILI9341 display in Rgb666 color mode, connected to an 18-bit parallel bus.
+///
+/// [`ILI9341Rgb666`] sends each pixel as three bytes with the color channels
+/// stored in the upper 6 bits, which produces colors shifted by two bits on an
+/// 18-bit bus. This model must be used with an interface that transfers one
+/// 18-bit word per pixel, like a [`ParallelInterface`](crate::interface::ParallelInterface)
+/// with a [`Generic18BitBus`](crate::interface::Generic18BitBus).
+pub struct ILI9341Rgb666Parallel18;
+
 impl Model for ILI9341Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    // 100 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(10_000_000);
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        ili934x::init_common(di, delay, options, pf).map_err(Into::into)
+        let madctl = ili934x::init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
     }
 }
 
+impl DisplayBrightness for ILI9341Rgb565 {}
+
 impl Model for ILI9341Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    // 100 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(10_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = ili934x::init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+impl DisplayBrightness for ILI9341Rgb666 {}
+
+impl Model for ILI9341Rgb666Parallel18 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    // 100 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(10_000_000);
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
+        // The interface control setting below is only valid for an 18-bit parallel bus.
+        if DI::KIND != InterfaceKind::Parallel18Bit {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface(&[(
+                    InterfaceKind::Parallel18Bit,
+                    BitsPerPixel::Eighteen,
+                )]),
+            ));
+        }
+
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        ili934x::init_common(di, delay, options, pf).map_err(Into::into)
+        let madctl = ili934x::init_common(di, delay, options, pf)?;
+
+        // 8.3.2 Interface Control (F6h): WEMODE = 1, EPF = 00 and MDT = 00, which
+        // transfers a single 18-bit word per pixel in 262K color mode.
+        di.write_raw(0xF6, &[0x01, 0x00, 0x00])?;
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+impl DisplayBrightness for ILI9341Rgb666Parallel18 {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{_mock::MockDelay, _test::NullInterface, Builder, InitError};
+
+    use super::*;
+
+    #[test]
+    fn parallel18_requires_18bit_bus() {
+        let result =
+            Builder::new(ILI9341Rgb666Parallel18, NullInterface::<u32>::new()).init(&mut MockDelay);
+
+        let Err(InitError::InvalidConfiguration(ConfigurationError::UnsupportedInterface(
+            supported,
+        ))) = result
+        else {
+            panic!("unexpected result");
+        };
+        assert_eq!(
+            supported,
+            [(InterfaceKind::Parallel18Bit, BitsPerPixel::Eighteen)]
+        );
     }
 }
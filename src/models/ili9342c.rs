@@ -4,7 +4,7 @@ use embedded_hal::delay::DelayNs;
 use crate::{
     dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
     interface::Interface,
-    models::{ili934x, Model},
+    models::{ili934x, turn_on_display, Model, ModelInitError},
     options::ModelOptions,
 };
 
@@ -17,37 +17,47 @@ pub struct ILI9342CRgb666;
 impl Model for ILI9342CRgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 240);
+    // 100 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(10_000_000);
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        ili934x::init_common(di, delay, options, pf).map_err(Into::into)
+        let madctl = ili934x::init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
     }
 }
 
 impl Model for ILI9342CRgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 240);
+    // 100 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(10_000_000);
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        ili934x::init_common(di, delay, options, pf).map_err(Into::into)
+        let madctl = ili934x::init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
     }
 }
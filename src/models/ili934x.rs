@@ -2,10 +2,11 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
     },
     interface::Interface,
+    models::ModelInitError,
     options::ModelOptions,
 };
 
@@ -15,7 +16,7 @@ pub fn init_common<DELAY, DI>(
     delay: &mut DELAY,
     options: &ModelOptions,
     pixel_format: PixelFormat,
-) -> Result<SetAddressMode, DI::Error>
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
 where
     DELAY: DelayNs,
     DI: Interface,
@@ -44,7 +45,5 @@ where
     // 13.2 Power ON Sequence: Delay should be 60ms + 80ms
     delay.delay_us(140_000);
 
-    di.write_command(SetDisplayOn)?;
-
     Ok(madctl)
 }
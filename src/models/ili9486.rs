@@ -4,9 +4,10 @@ use embedded_hal::delay::DelayNs;
 use crate::{
     dcs::{
         BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
-        SetDisplayOn, SetInvertMode, SetPixelFormat,
+        SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
+    models::{turn_on_display, ModelInitError},
     options::ModelOptions,
 };
 
@@ -21,13 +22,17 @@ pub struct ILI9486Rgb666;
 impl Model for ILI9486Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+    // DISPON requires some time otherwise we risk SPI data issues
+    const DISPLAY_ON_DELAY_US: u32 = 120_000;
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -35,20 +40,27 @@ impl Model for ILI9486Rgb565 {
         delay.delay_us(120_000);
 
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        init_common(di, delay, options, pf)
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
     }
 }
 
 impl Model for ILI9486Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+    // DISPON requires some time otherwise we risk SPI data issues
+    const DISPLAY_ON_DELAY_US: u32 = 120_000;
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -56,7 +68,10 @@ impl Model for ILI9486Rgb666 {
         delay.delay_us(120_000);
 
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
-        init_common(di, delay, options, pf)
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
     }
 }
 
@@ -66,13 +81,14 @@ fn init_common<DELAY, DI>(
     delay: &mut DELAY,
     options: &ModelOptions,
     pixel_format: PixelFormat,
-) -> Result<SetAddressMode, DI::Error>
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
 where
     DELAY: DelayNs,
     DI: Interface,
 {
     let madctl = SetAddressMode::from(options);
     di.write_command(ExitSleepMode)?; // turn off sleep
+    delay.delay_us(5_000); // SLPOUT requires 5ms before the next command
     di.write_command(SetPixelFormat::new(pixel_format))?; // pixel format
     di.write_command(madctl)?; // left -> right, bottom -> top RGB
                                // dcs.write_command(Instruction::VCMOFSET, &[0x00, 0x48, 0x00, 0x48])?; //VCOM  Control 1 [00 40 00 40]
@@ -85,10 +101,6 @@ where
 
     di.write_raw(0xB6, &[0b0000_0010, 0x02, 0x3B])?; // DFC
     di.write_command(EnterNormalMode)?; // turn to normal mode
-    di.write_command(SetDisplayOn)?; // turn on display
-
-    // DISPON requires some time otherwise we risk SPI data issues
-    delay.delay_us(120_000);
 
     Ok(madctl)
 }
@@ -0,0 +1,333 @@
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    color::Rgb111,
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetInvertMode, SetPixelFormat,
+    },
+    interface::{Interface, InterfaceKind, InterfacePixelFormat},
+    models::{turn_on_display, ConfigurationError, ModelInitError},
+    options::{Endianness, ModelOptions},
+};
+
+use super::Model;
+
+/// ILI9488 display in Rgb565 color mode.
+///
+/// On 8-bit and 16-bit parallel interfaces the controller is configured for 16-bit pixels.
+/// The ILI9488 doesn't support 16-bit pixels on serial interfaces, so on these interfaces
+/// this model configures the controller for 18-bit pixels and expands every `Rgb565` color
+/// to `Rgb666` while the pixel data is sent. This allows `Rgb565` images and colors to be
+/// drawn without converting them first, but sends 3 bytes per pixel. Raw pixel data for
+/// [`Display::set_pixels_from_buffer`](crate::Display::set_pixels_from_buffer) and
+/// [`Display::write_raw_window`](crate::Display::write_raw_window) must be in the `Rgb666`
+/// format on serial interfaces. Other interfaces are rejected by
+/// [`init`](Model::init).
+pub struct ILI9488Rgb565;
+
+/// ILI9488 display in Rgb666 color mode.
+pub struct ILI9488Rgb666;
+
+/// ILI9488 display in 3-bit color mode.
+///
+/// Only the 8 colors of [`Rgb111`] can be shown, but 8 pixels are sent in 3 bytes, which
+/// needs an eighth of the bandwidth of [`ILI9488Rgb666`] on 8-bit interfaces. Raw pixel data
+/// for [`Display::set_pixels_from_buffer`](crate::Display::set_pixels_from_buffer) must be
+/// packed in the same way, with the first pixel in the highest bits. If the number of pixels
+/// isn't a multiple of 8, the last byte is padded with zero bits.
+pub struct ILI9488Rgb111;
+
+impl Model for ILI9488Rgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 50 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(20_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let bpp = match DI::KIND {
+            InterfaceKind::Serial4Line | InterfaceKind::Serial3Line => BitsPerPixel::Eighteen,
+            InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit => BitsPerPixel::Sixteen,
+            _ => {
+                return Err(ModelInitError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedInterface(SUPPORTED_RGB565),
+                ))
+            }
+        };
+
+        let madctl = init_common(di, delay, options, PixelFormat::with_all(bpp))?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+
+    fn send_pixels<DI>(
+        &mut self,
+        di: &mut DI,
+        pixels: impl IntoIterator<Item = Self::ColorFormat>,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        if expands_colors(DI::KIND) {
+            Rgb565::send_pixels_as_rgb666(di, pixels)
+        } else {
+            Rgb565::send_pixels(di, pixels, endianness)
+        }
+    }
+
+    fn send_repeated_pixel<DI>(
+        &mut self,
+        di: &mut DI,
+        pixel: Self::ColorFormat,
+        count: u32,
+        endianness: Endianness,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        if expands_colors(DI::KIND) {
+            Rgb565::send_repeated_pixel_as_rgb666(di, pixel, count)
+        } else {
+            Rgb565::send_repeated_pixel(di, pixel, count, endianness)
+        }
+    }
+
+    fn send_pixels_from_buffer<DI>(
+        &mut self,
+        di: &mut DI,
+        data: &[DI::Word],
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        Self::ColorFormat: InterfacePixelFormat<DI::Word>,
+    {
+        if expands_colors(DI::KIND) {
+            Rgb565::send_pixels_from_buffer_as_rgb666(di, data)
+        } else {
+            Rgb565::send_pixels_from_buffer(di, data)
+        }
+    }
+
+    fn raw_pixel_data_len(&self, pixels: u32, kind: InterfaceKind) -> usize {
+        if expands_colors(kind) {
+            pixels as usize * 3
+        } else {
+            pixels as usize * 2
+        }
+    }
+}
+
+impl Model for ILI9488Rgb666 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 50 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(20_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+impl Model for ILI9488Rgb111 {
+    type ColorFormat = Rgb111;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 50 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(20_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+/// Supported combinations of interface kind and color depth of [`ILI9488Rgb565`].
+const SUPPORTED_RGB565: &[(InterfaceKind, BitsPerPixel)] = &[
+    (InterfaceKind::Serial4Line, BitsPerPixel::Eighteen),
+    (InterfaceKind::Serial3Line, BitsPerPixel::Eighteen),
+    (InterfaceKind::Parallel8Bit, BitsPerPixel::Sixteen),
+    (InterfaceKind::Parallel16Bit, BitsPerPixel::Sixteen),
+];
+
+// Serial interfaces don't support 16-bit pixels, the colors are expanded to 18 bits.
+fn expands_colors(kind: InterfaceKind) -> bool {
+    matches!(
+        kind,
+        InterfaceKind::Serial4Line | InterfaceKind::Serial3Line
+    )
+}
+
+// common init for all color format models
+fn init_common<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_us(120_000);
+
+    di.write_raw(
+        0xE0,
+        &[
+            0x00, 0x03, 0x09, 0x08, 0x16, 0x0A, 0x3F, 0x78, 0x4C, 0x09, 0x0A, 0x08, 0x16, 0x1A,
+            0x0F,
+        ],
+    )?; // positive gamma control
+    di.write_raw(
+        0xE1,
+        &[
+            0x00, 0x16, 0x19, 0x03, 0x0F, 0x05, 0x32, 0x45, 0x46, 0x04, 0x0E, 0x0D, 0x35, 0x37,
+            0x0F,
+        ],
+    )?; // negative gamma control
+    di.write_raw(0xC0, &[0x17, 0x15])?; // power control 1
+    di.write_raw(0xC1, &[0x41])?; // power control 2
+    di.write_raw(0xC5, &[0x00, 0x12, 0x80])?; // VCOM control
+    di.write_command(madctl)?; // memory access control
+    di.write_command(SetPixelFormat::new(pixel_format))?; // pixel format
+    di.write_raw(0xB0, &[0x00])?; // interface mode control, SDO is used
+    di.write_raw(0xB1, &[0xA0])?; // frame rate control, 60 Hz
+    di.write_raw(0xB4, &[0x02])?; // display inversion control, 2 dot inversion
+    di.write_raw(0xB6, &[0x02, 0x02, 0x3B])?; // display function control
+    di.write_raw(0xB7, &[0xC6])?; // entry mode set
+    di.write_raw(0xF7, &[0xA9, 0x51, 0x2C, 0x82])?; // adjust control 3, DSI write DCS command
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+    di.write_command(ExitSleepMode)?; // turn off sleep
+    delay.delay_us(120_000);
+    di.write_command(EnterNormalMode)?; // turn to normal mode
+
+    Ok(madctl)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use embedded_graphics_core::{pixelcolor::RgbColor, prelude::*, primitives::Rectangle};
+
+    #[cfg(feature = "std")]
+    use crate::interface::CaptureToImage;
+    use crate::{
+        _mock::MockDelay,
+        _test::{NullInterface, Parallel18Bit, Parallel8Bit},
+        interface::TracingInterface,
+        Builder, InitError,
+    };
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rgb565_colors_are_expanded() {
+        let mut display = Builder::new(ILI9488Rgb565, CaptureToImage::new(320, 480))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.set_pixel(10, 20, Rgb565::GREEN).unwrap();
+        let area = Rectangle::new(Point::new(0, 0), Size::new(2, 1));
+        display.fill_solid(&area, Rgb565::BLUE).unwrap();
+        display
+            .set_pixels_from_buffer(5, 5, 5, 5, &[0xFC, 0, 0])
+            .unwrap();
+        display.write_raw_window(6, 5, 6, 5, &[0, 0, 0xFC]).unwrap();
+
+        let (di, _, _, _) = display.release();
+        assert_eq!(di.pixel(10, 20), Some([0, 255, 0]));
+        assert_eq!(di.pixel(1, 0), Some([0, 0, 255]));
+        assert_eq!(di.pixel(5, 5), Some([255, 0, 0]));
+        assert_eq!(di.pixel(6, 5), Some([0, 0, 255]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn expanded_colors_ignore_endianness() {
+        let mut display = Builder::new(ILI9488Rgb565, CaptureToImage::new(320, 480))
+            .endianness(Endianness::Little)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.set_pixel(10, 20, Rgb565::GREEN).unwrap();
+        let area = Rectangle::new(Point::new(0, 0), Size::new(2, 1));
+        display.fill_solid(&area, Rgb565::BLUE).unwrap();
+
+        let (di, _, _, _) = display.release();
+        assert_eq!(di.pixel(10, 20), Some([0, 255, 0]));
+        assert_eq!(di.pixel(1, 0), Some([0, 0, 255]));
+    }
+
+    #[test]
+    fn rgb565_uses_16_bit_pixels_on_parallel_interfaces() {
+        let mut pixel_format = 0;
+        let di =
+            TracingInterface::new(NullInterface::<u8, Parallel8Bit>::new(), |command, args| {
+                if command == 0x3A {
+                    pixel_format = args[0];
+                }
+            });
+
+        let mut display = Builder::new(ILI9488Rgb565, di)
+            .init(&mut MockDelay)
+            .unwrap();
+        display.write_raw_window(0, 0, 1, 0, &[0; 4]).unwrap();
+        assert!(display.write_raw_window(0, 0, 1, 0, &[0; 6]).is_err());
+
+        let _ = display.release();
+        assert_eq!(pixel_format, 0x55);
+    }
+
+    #[test]
+    fn rgb565_is_rejected_on_18_bit_interfaces() {
+        let result = Builder::new(ILI9488Rgb565, NullInterface::<u16, Parallel18Bit>::new())
+            .init(&mut MockDelay);
+        assert!(matches!(
+            result,
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface(SUPPORTED_RGB565)
+            ))
+        ));
+    }
+}
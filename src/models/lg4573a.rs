@@ -0,0 +1,119 @@
+use embedded_graphics_core::pixelcolor::Rgb666;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
+    },
+    interface::Interface,
+    models::{turn_on_display, Model, ModelInitError},
+    options::{ColorOrder, ModelOptions},
+};
+
+/// LG4573A display in Rgb666 color mode.
+///
+/// Used by 4" 480x800 SPI modules. The panels of these modules are wired in BGR order,
+/// which is why the default color order of this model is [`ColorOrder::Bgr`].
+pub struct LG4573A;
+
+// Display mode settings: RGB interface (B1h), resolution (B2h), timings (B3h - B7h)
+const DISPLAY_MODE_SETTINGS: &[(u8, &[u8])] = &[
+    (0xB1, &[0x08, 0x3B, 0x0F]),
+    (0xB2, &[0x00, 0xC8]),
+    (0xB3, &[0x00]),
+    (0xB4, &[0x00]),
+    (0xB5, &[0x42, 0x10, 0x10, 0x00, 0x20]),
+    (0xB6, &[0x0B, 0x0F, 0x3C, 0x13, 0x13, 0xE8]),
+    (0xB7, &[0x46, 0x06, 0x0C, 0x00, 0x00]),
+];
+
+const POWER_SETTINGS: &[(u8, &[u8])] = &[
+    (0xC0, &[0x01, 0x11]),
+    (0xC3, &[0x07, 0x03, 0x04, 0x04, 0x04]),
+    (0xC4, &[0x12, 0x24, 0x18, 0x18, 0x02, 0x49]),
+    (0xC5, &[0x6F]),
+    (0xC6, &[0x41, 0x63]),
+];
+
+// The same gamma curve is used for the positive and negative polarity of all channels.
+const GAMMA: &[u8] = &[0x03, 0x07, 0x73, 0x35, 0x00, 0x01, 0x20, 0x00, 0x03];
+const GAMMA_SETTINGS: &[(u8, &[u8])] = &[
+    (0xD0, GAMMA),
+    (0xD1, GAMMA),
+    (0xD2, GAMMA),
+    (0xD3, GAMMA),
+    (0xD4, GAMMA),
+    (0xD5, GAMMA),
+];
+
+impl Model for LG4573A {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (480, 800);
+    const DEFAULT_COLOR_ORDER: ColorOrder = ColorOrder::Bgr;
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+
+        delay.delay_us(120_000);
+
+        di.write_command(madctl)?;
+        di.write_command(SetInvertMode::new(options.invert_colors))?;
+        di.write_command(SetPixelFormat::new(pf))?;
+
+        for &(command, params) in DISPLAY_MODE_SETTINGS
+            .iter()
+            .chain(POWER_SETTINGS)
+            .chain(GAMMA_SETTINGS)
+        {
+            di.write_raw(command, params)?;
+        }
+
+        di.write_command(ExitSleepMode)?;
+        delay.delay_us(120_000);
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{_mock::MockDelay, _test::RecordingInterface, Builder};
+
+    use super::*;
+
+    #[test]
+    fn init_sequence() {
+        let display = Builder::new(LG4573A, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap();
+        let (di, _, _, _) = display.release();
+
+        // BGR order by default, inversion off and 18 bits per pixel, followed by the display
+        // mode settings
+        assert_eq!(
+            di.commands[1..6],
+            [
+                (0x36, 0x08),
+                (0x20, 0),
+                (0x3A, 0x66),
+                (0xB1, 0x08),
+                (0xB2, 0x00)
+            ]
+        );
+        assert_eq!(di.commands[8..10], [(0xB5, 0x42), (0xB6, 0x0B)]);
+        assert_eq!(di.commands[di.commands.len() - 2..], [(0x11, 0), (0x29, 0)]);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{dcs::BitsPerPixel, interface::InterfaceKind};
+
+use super::*;
+
+/// Metadata of a display model.
+///
+/// See [`ALL`] for the metadata of all builtin models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ModelInfo {
+    /// Name of the model type
+    pub name: &'static str,
+    /// Framebuffer size, see [`Model::FRAMEBUFFER_SIZE`]
+    pub framebuffer_size: (u16, u16),
+    /// Color depth of the color format, see [`Model::ColorFormat`]
+    pub color_format: BitsPerPixel,
+    /// Interface kinds which the model can be used with
+    pub interfaces: &'static [InterfaceKind],
+    /// Maximum serial clock frequency, see [`Model::MAX_SERIAL_CLOCK_HZ`]
+    pub max_serial_clock_hz: Option<u32>,
+}
+
+// Interface kinds of models which send 1 or 2 bytes or a single 16-bit word per pixel.
+const SERIAL_AND_PARALLEL: &[InterfaceKind] = &[
+    InterfaceKind::Serial4Line,
+    InterfaceKind::Serial3Line,
+    InterfaceKind::Parallel8Bit,
+    InterfaceKind::Parallel16Bit,
+];
+
+macro_rules! model_info {
+    ($model:ident) => {
+        model_info!($model, SERIAL_AND_PARALLEL)
+    };
+    ($model:ident, $interfaces:expr) => {
+        ModelInfo {
+            name: stringify!($model),
+            framebuffer_size: <$model as Model>::FRAMEBUFFER_SIZE,
+            color_format: BitsPerPixel::from_rgb_color::<<$model as Model>::ColorFormat>(),
+            interfaces: $interfaces,
+            max_serial_clock_hz: <$model as Model>::MAX_SERIAL_CLOCK_HZ,
+        }
+    };
+}
+
+/// Metadata of all builtin models.
+///
+/// Allows tools, like board configuration generators, to list the supported hardware.
+/// Wrappers like [`Dual`] aren't included.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::{dcs::BitsPerPixel, interface::InterfaceKind, models};
+///
+/// let spi_rgb666 = models::ALL.iter().filter(|model| {
+///     model.color_format == BitsPerPixel::Eighteen
+///         && model.interfaces.contains(&InterfaceKind::Serial4Line)
+/// });
+/// assert!(spi_rgb666.count() > 0);
+/// ```
+pub const ALL: &[ModelInfo] = &[
+    model_info!(GC9107),
+    model_info!(GC9307),
+    model_info!(GC9A01),
+    model_info!(GC9A01A),
+    model_info!(HX8347),
+    model_info!(ILI9327Rgb565),
+    model_info!(ILI9327Rgb666),
+    model_info!(ILI9341Rgb565),
+    model_info!(ILI9341Rgb666),
+    model_info!(ILI9341Rgb666Parallel18, &[InterfaceKind::Parallel18Bit]),
+    model_info!(ILI9342CRgb565),
+    model_info!(ILI9342CRgb666),
+    model_info!(ILI9486Rgb565),
+    model_info!(ILI9486Rgb666),
+    model_info!(ILI9488Rgb565),
+    model_info!(ILI9488Rgb666),
+    model_info!(
+        ILI9488Rgb111,
+        &[
+            InterfaceKind::Serial4Line,
+            InterfaceKind::Serial3Line,
+            InterfaceKind::Parallel8Bit,
+        ]
+    ),
+    model_info!(LG4573A),
+    model_info!(RM67162),
+    model_info!(
+        ST7365PRgb565,
+        &[InterfaceKind::Parallel8Bit, InterfaceKind::Parallel16Bit]
+    ),
+    model_info!(ST7365PRgb666),
+    model_info!(ST7735B),
+    model_info!(ST7735R),
+    model_info!(ST7735s),
+    model_info!(ST7789),
+    model_info!(ST7796),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_entries() {
+        for (i, model) in ALL.iter().enumerate() {
+            assert!(
+                ALL[i + 1..].iter().all(|other| other.name != model.name),
+                "duplicate model {}",
+                model.name
+            );
+        }
+
+        let model = ALL.iter().find(|model| model.name == "ST7789").unwrap();
+        assert_eq!(model.framebuffer_size, (240, 320));
+        assert_eq!(model.color_format, BitsPerPixel::Sixteen);
+    }
+}
@@ -3,10 +3,11 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
     },
     interface::Interface,
+    models::{turn_on_display, write_commands, DisplayBrightness, ModelInitError},
     options::ModelOptions,
 };
 
@@ -21,6 +22,9 @@ use super::Model;
 /// This driver was developed for the Lilygo T-Display-S3 AMOLED display (v2).
 /// The initialization sequence is based on Lilygo's Arduino example code.
 ///
+/// A vendor brightness and gamma table can be loaded with
+/// [`Builder::brightness_table`](crate::Builder::brightness_table).
+///
 /// Currently only tested with 240x536 resolution displays.
 /// While it may work with other display sizes, this is untested and could lead to unexpected behavior.
 /// If you encounter issues with different display sizes, please report them.
@@ -36,13 +40,16 @@ impl Model for RM67162 {
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
     {
         let madctl = SetAddressMode::from(options);
 
+        // SLPOUT must not be sent earlier than 120ms after a reset
+        delay.delay_us(120_000);
+
         di.write_raw(0xFE, &[0x04])?;
         di.write_raw(0x6A, &[0x00])?;
         di.write_raw(0xFE, &[0x05])?;
@@ -53,6 +60,9 @@ impl Model for RM67162 {
         di.write_raw(0x2B, &[0x73])?;
         di.write_raw(0xFE, &[0x0A])?;
         di.write_raw(0x29, &[0x10])?;
+        if let Some(table) = options.brightness_table {
+            write_commands(di, table.commands())?;
+        }
         di.write_raw(0xFE, &[0x00])?;
         di.write_raw(0x51, &[0xaf])?; // Set brightness
         di.write_raw(0x53, &[0x20])?;
@@ -70,8 +80,43 @@ impl Model for RM67162 {
         di.write_command(ExitSleepMode)?;
         delay.delay_us(120_000);
 
-        di.write_command(SetDisplayOn)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
         Ok(madctl)
     }
 }
+
+impl DisplayBrightness for RM67162 {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{_mock::MockDelay, _test::RecordingInterface, options::BrightnessTable, Builder};
+
+    use super::*;
+
+    #[test]
+    fn brightness_table_is_sent_before_user_commands() {
+        const TABLE: BrightnessTable =
+            BrightnessTable::new(&[(0xFE, &[0x05]), (0xC7, &[0x01, 0x02]), (0xB0, &[0x03])]);
+
+        let display = Builder::new(RM67162, RecordingInterface::default())
+            .brightness_table(TABLE)
+            .init(&mut MockDelay)
+            .unwrap();
+        let (di, _, _, _) = display.release();
+
+        // The table follows the soft reset and the vendor setup and is followed by the switch to the user
+        // command page.
+        assert_eq!(
+            di.commands[10..16],
+            [
+                (0x29, 0x10),
+                (0xFE, 0x05),
+                (0xC7, 0x01),
+                (0xB0, 0x03),
+                (0xFE, 0x00),
+                (0x51, 0xAF)
+            ]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
+    },
+    interface::{Interface, InterfaceKind},
+    models::{
+        turn_on_display, write_commands, Commands, ConfigurationError, Model, ModelInitError,
+    },
+    options::ModelOptions,
+};
+
+// Commands after the address mode and pixel format.
+const COMMANDS: Commands = &[
+    // display inversion control, 1 dot inversion
+    (0xB4, &[0x01]),
+    // display function control
+    (0xB6, &[0x80, 0x02, 0x3B]),
+    // display output control adjust
+    (0xE8, &[0x40, 0x8A, 0x00, 0x00, 0x29, 0x19, 0xA5, 0x33]),
+    // power control 2 and 3
+    (0xC1, &[0x06]),
+    (0xC2, &[0xA7]),
+    // VCOM control
+    (0xC5, &[0x18]),
+    // positive and negative gamma control
+    (
+        0xE0,
+        &[
+            0xF0, 0x09, 0x0B, 0x06, 0x04, 0x15, 0x2F, 0x54, 0x42, 0x3C, 0x17, 0x14, 0x18, 0x1B,
+        ],
+    ),
+    (
+        0xE1,
+        &[
+            0xE0, 0x09, 0x0B, 0x06, 0x04, 0x03, 0x2B, 0x43, 0x42, 0x3B, 0x16, 0x14, 0x17, 0x1B,
+        ],
+    ),
+    // disable the extended command set
+    (0xF0, &[0x3C]),
+    (0xF0, &[0x69]),
+];
+
+/// Supported combinations of interface kind and color depth.
+const SUPPORTED: &[(InterfaceKind, BitsPerPixel)] = &[
+    (InterfaceKind::Serial4Line, BitsPerPixel::Eighteen),
+    (InterfaceKind::Serial3Line, BitsPerPixel::Eighteen),
+    (InterfaceKind::Parallel8Bit, BitsPerPixel::Sixteen),
+    (InterfaceKind::Parallel8Bit, BitsPerPixel::Eighteen),
+    (InterfaceKind::Parallel16Bit, BitsPerPixel::Sixteen),
+    (InterfaceKind::Parallel16Bit, BitsPerPixel::Eighteen),
+];
+
+/// ST7365P display in Rgb565 color mode.
+///
+/// Like the ILI9488, the ST7365P doesn't support 16-bit pixels on serial interfaces, use
+/// [`ST7365PRgb666`] for SPI displays.
+pub struct ST7365PRgb565;
+
+/// ST7365P display in Rgb666 color mode.
+pub struct ST7365PRgb666;
+
+impl Model for ST7365PRgb565 {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if matches!(
+            DI::KIND,
+            InterfaceKind::Serial4Line | InterfaceKind::Serial3Line
+        ) {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface(SUPPORTED),
+            ));
+        }
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+impl Model for ST7365PRgb666 {
+    type ColorFormat = Rgb666;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        if DI::KIND == InterfaceKind::Parallel18Bit {
+            return Err(ModelInitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface(SUPPORTED),
+            ));
+        }
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        let madctl = init_common(di, delay, options, pf)?;
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
+
+// common init for all color format models
+fn init_common<DELAY, DI>(
+    di: &mut DI,
+    delay: &mut DELAY,
+    options: &ModelOptions,
+    pixel_format: PixelFormat,
+) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+where
+    DELAY: DelayNs,
+    DI: Interface,
+{
+    let madctl = SetAddressMode::from(options);
+
+    delay.delay_ms(120);
+
+    // enable the extended command set
+    di.write_raw(0xF0, &[0xC3])?;
+    di.write_raw(0xF0, &[0x96])?;
+    di.write_command(madctl)?;
+    di.write_command(SetPixelFormat::new(pixel_format))?;
+
+    write_commands(di, COMMANDS)?;
+
+    di.write_command(SetInvertMode::new(options.invert_colors))?;
+
+    di.write_command(ExitSleepMode)?; // turn off sleep
+    delay.delay_ms(120);
+
+    Ok(madctl)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{_mock::MockDelay, _test::NullInterface, Builder, InitError};
+
+    use super::*;
+
+    #[test]
+    fn rgb565_is_rejected_on_serial_interfaces() {
+        let result = Builder::new(ST7365PRgb565, NullInterface::<u8>::new()).init(&mut MockDelay);
+        assert!(matches!(
+            result,
+            Err(InitError::InvalidConfiguration(
+                ConfigurationError::UnsupportedInterface(SUPPORTED)
+            ))
+        ));
+
+        Builder::new(ST7365PRgb666, NullInterface::<u8>::new())
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+}
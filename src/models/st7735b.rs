@@ -0,0 +1,84 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetInvertMode, SetPixelFormat,
+    },
+    interface::Interface,
+    models::{turn_on_display, Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// ST7735B display in Rgb565 color mode.
+///
+/// The ST7735B is used in older 1.8" 128x160 panels, which are configured with
+/// `display_size(128, 160)` and `display_offset(2, 1)`.
+pub struct ST7735B;
+
+impl Model for ST7735B {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_command(ExitSleepMode)?; // turn off sleep
+        delay.delay_us(120_000);
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
+        delay.delay_us(10_000);
+
+        di.write_raw(0xB1, &[0x00, 0x06, 0x03])?; // set frame rate, fastest refresh
+        delay.delay_us(10_000);
+        di.write_command(madctl)?; // set memory data access control
+        di.write_raw(0xB6, &[0x15, 0x02])?; // set display function, non-overlap timing
+        di.write_raw(0xB4, &[0x00])?; // set inversion control, line inversion
+        di.write_raw(0xC0, &[0x02, 0x70])?; // set power control 1, GVDD 4.7 V
+        delay.delay_us(10_000);
+        di.write_raw(0xC1, &[0x05])?; // set power control 2, VGH and VGL
+        di.write_raw(0xC2, &[0x01, 0x02])?; // set power control 3
+        di.write_raw(0xC5, &[0x3C, 0x38])?; // set VCOM control 1
+        delay.delay_us(10_000);
+        di.write_raw(0xFC, &[0x11, 0x15])?; // set power control 6
+
+        di.write_raw(
+            0xE0,
+            &[
+                0x09, 0x16, 0x09, 0x20, 0x21, 0x1B, 0x13, 0x19, 0x17, 0x15, 0x1E, 0x2B, 0x04, 0x05,
+                0x02, 0x0E,
+            ],
+        )?; // set GAMMA +Polarity characteristics
+        di.write_raw(
+            0xE1,
+            &[
+                0x0B, 0x14, 0x08, 0x1E, 0x22, 0x1D, 0x18, 0x1E, 0x1B, 0x1A, 0x24, 0x2B, 0x06, 0x06,
+                0x02, 0x0F,
+            ],
+        )?; // set GAMMA -Polarity characteristics
+        delay.delay_us(10_000);
+
+        di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
+        di.write_command(EnterNormalMode)?; // turn to normal mode
+        delay.delay_us(10_000);
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
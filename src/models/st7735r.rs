@@ -0,0 +1,85 @@
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    dcs::{
+        BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
+        SetInvertMode, SetPixelFormat,
+    },
+    interface::Interface,
+    models::{turn_on_display, Model, ModelInitError},
+    options::ModelOptions,
+};
+
+/// ST7735R display in Rgb565 color mode.
+///
+/// The ST7735R is used in the common 1.8" 128x160 "red tab" and "green tab" panels and in
+/// 1.44" 128x128 panels. The panel size is selected with the builder, e.g.
+/// `display_size(128, 160)` for red tab panels, `display_size(128, 160)` and
+/// `display_offset(2, 1)` for green tab panels and `display_size(128, 128)` and
+/// `display_offset(2, 3)` for 128x128 panels.
+pub struct ST7735R;
+
+impl Model for ST7735R {
+    type ColorFormat = Rgb565;
+    const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+
+    fn init<DELAY, DI>(
+        &mut self,
+        di: &mut DI,
+        delay: &mut DELAY,
+        options: &ModelOptions,
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
+    where
+        DELAY: DelayNs,
+        DI: Interface,
+    {
+        let madctl = SetAddressMode::from(options);
+
+        delay.delay_us(120_000);
+
+        di.write_command(ExitSleepMode)?; // turn off sleep
+        delay.delay_us(120_000);
+
+        di.write_raw(0xB1, &[0x01, 0x2C, 0x2D])?; // set frame rate, normal mode
+        di.write_raw(0xB2, &[0x01, 0x2C, 0x2D])?; // set frame rate, idle mode
+        di.write_raw(0xB3, &[0x01, 0x2C, 0x2D, 0x01, 0x2C, 0x2D])?; // set frame rate, partial mode
+        di.write_raw(0xB4, &[0x07])?; // set inversion control, no inversion
+        di.write_raw(0xC0, &[0xA2, 0x02, 0x84])?; // set power control 1
+        di.write_raw(0xC1, &[0xC5])?; // set power control 2
+        di.write_raw(0xC2, &[0x0A, 0x00])?; // set power control 3
+        di.write_raw(0xC3, &[0x8A, 0x2A])?; // set power control 4
+        di.write_raw(0xC4, &[0x8A, 0xEE])?; // set power control 5
+        di.write_raw(0xC5, &[0x0E])?; // set VCOM control 1
+
+        di.write_command(SetInvertMode::new(options.invert_colors))?; // set color inversion
+        di.write_command(madctl)?; // set memory data access control
+
+        let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
+        di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
+
+        di.write_raw(
+            0xE0,
+            &[
+                0x02, 0x1C, 0x07, 0x12, 0x37, 0x32, 0x29, 0x2D, 0x29, 0x25, 0x2B, 0x39, 0x00, 0x01,
+                0x03, 0x10,
+            ],
+        )?; // set GAMMA +Polarity characteristics
+        di.write_raw(
+            0xE1,
+            &[
+                0x03, 0x1D, 0x07, 0x06, 0x2E, 0x2C, 0x29, 0x2D, 0x2E, 0x2E, 0x37, 0x3F, 0x00, 0x00,
+                0x02, 0x10,
+            ],
+        )?; // set GAMMA -Polarity characteristics
+
+        di.write_command(EnterNormalMode)?; // turn to normal mode
+        delay.delay_us(10_000);
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
+
+        Ok(madctl)
+    }
+}
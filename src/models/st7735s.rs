@@ -3,11 +3,11 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{
     dcs::{
-        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetInvertMode,
+        SetPixelFormat,
     },
     interface::Interface,
-    models::Model,
+    models::{turn_on_display, Model, ModelInitError},
     options::ModelOptions,
 };
 
@@ -17,13 +17,15 @@ pub struct ST7735s;
 impl Model for ST7735s {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -65,7 +67,8 @@ impl Model for ST7735s {
         di.write_command(SetPixelFormat::new(pf))?; // set interface pixel format, 16bit pixel into frame memory
 
         di.write_command(madctl)?; // set memory data access control, Top -> Bottom, RGB, Left -> Right
-        di.write_command(SetDisplayOn)?; // turn on display
+
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
         Ok(madctl)
     }
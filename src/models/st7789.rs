@@ -4,10 +4,10 @@ use embedded_hal::delay::DelayNs;
 use crate::{
     dcs::{
         BitsPerPixel, EnterNormalMode, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode,
-        SetDisplayOn, SetInvertMode, SetPixelFormat,
+        SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
-    models::Model,
+    models::{turn_on_display, DisplayBrightness, Model, ModelInitError},
     options::ModelOptions,
 };
 
@@ -17,13 +17,17 @@ pub struct ST7789;
 impl Model for ST7789 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    // 16 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(62_500_000);
+    // DISPON requires some time otherwise we risk SPI data issues
+    const DISPLAY_ON_DELAY_US: u32 = 120_000;
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -45,11 +49,11 @@ impl Model for ST7789 {
         delay.delay_us(10_000);
         di.write_command(EnterNormalMode)?;
         delay.delay_us(10_000);
-        di.write_command(SetDisplayOn)?;
 
-        // DISPON requires some time otherwise we risk SPI data issues
-        delay.delay_us(120_000);
+        turn_on_display(di, delay, options, Self::DISPLAY_ON_DELAY_US)?;
 
         Ok(madctl)
     }
 }
+
+impl DisplayBrightness for ST7789 {}
@@ -1,7 +1,12 @@
 use embedded_graphics_core::pixelcolor::Rgb565;
 use embedded_hal::delay::DelayNs;
 
-use crate::{dcs::SetAddressMode, interface::Interface, models::Model, options::ModelOptions};
+use crate::{
+    dcs::SetAddressMode,
+    interface::Interface,
+    models::{DisplayBrightness, Model, ModelInitError},
+    options::ModelOptions,
+};
 
 /// ST7796 display in Rgb565 color mode.
 pub struct ST7796;
@@ -9,13 +14,17 @@ pub struct ST7796;
 impl Model for ST7796 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    // 66 ns minimum serial write cycle
+    const MAX_SERIAL_CLOCK_HZ: Option<u32> = Some(15_000_000);
+    // DISPON requires some time otherwise we risk SPI data issues
+    const DISPLAY_ON_DELAY_US: u32 = 120_000;
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -23,3 +32,5 @@ impl Model for ST7796 {
         super::ST7789.init(di, delay, options)
     }
 }
+
+impl DisplayBrightness for ST7796 {}
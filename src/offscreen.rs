@@ -0,0 +1,418 @@
+//! Off-screen controller RAM.
+//!
+//! Many controllers have more RAM than the connected panel can show, e.g. the
+//! ST7789 has RAM for 240x320 pixels, but is often used with 240x240 panels.
+//! [`OffscreenRegion`] is a draw target for the RAM lines which aren't visible
+//! with the current scroll offset. Content can be prepared in this region and
+//! revealed later with [`Display::reveal_offscreen`], which uses the vertical
+//! scrolling feature of the controller and doesn't require any pixels to be
+//! redrawn.
+//!
+//! The off-screen region always follows the visible area along the scrolling
+//! axis, which is the vertical axis of the display in the default orientation.
+//! It takes the display offset and the current scroll offset into account and
+//! therefore never overlaps the visible area. The whole RAM must be used as the
+//! scroll region, by calling
+//! [`set_vertical_scroll_region(0, 0)`](Display::set_vertical_scroll_region).
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+//! use mipidsi::{models::ST7789, Builder};
+//!
+//! # let di = mipidsi::_mock::MockDisplayInterface;
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! let mut display = Builder::new(ST7789, di)
+//!     .display_size(240, 240)
+//!     .init(&mut delay)
+//!     .unwrap();
+//! display.set_vertical_scroll_region(0, 0).unwrap();
+//!
+//! // The 80 lines after the visible area are off-screen.
+//! let mut region = display.offscreen_region();
+//! assert_eq!(region.size(), Size::new(240, 80));
+//! region
+//!     .fill_solid(&Rectangle::new(Point::zero(), Size::new(240, 20)), Rgb565::RED)
+//!     .unwrap();
+//!
+//! // Scroll the red lines into view.
+//! display.reveal_offscreen(20).unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    add_lines,
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::MemoryMapping,
+    Display,
+};
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Returns a draw target for the RAM lines which aren't visible.
+    ///
+    /// See the [module documentation](crate::offscreen) for more information.
+    pub fn offscreen_region(&mut self) -> OffscreenRegion<'_, DI, M, RST, BL> {
+        OffscreenRegion { display: self }
+    }
+
+    /// Scrolls the display to reveal the given number of lines of the off-screen region.
+    ///
+    /// The same number of lines at the start of the visible area is moved off-screen
+    /// and becomes the end of the off-screen region.
+    pub fn reveal_offscreen(&mut self, lines: u16) -> Result<(), DI::Error> {
+        let rows = M::FRAMEBUFFER_SIZE.1;
+        let lines = lines % rows;
+
+        // The scroll offset counts RAM lines, which are in reverse order if the
+        // orientation reverses the rows.
+        let offset = if MemoryMapping::from(self.options.hardware_orientation()).reverse_rows {
+            add_lines(self.scroll_offset, rows - lines, rows)
+        } else {
+            add_lines(self.scroll_offset, lines, rows)
+        };
+
+        self.set_vertical_scroll_offset(offset)
+    }
+
+    /// Returns `true` if the scrolling axis is the x axis in the current orientation.
+    pub(crate) fn scrolls_horizontally(&self) -> bool {
+        MemoryMapping::from(self.options.hardware_orientation()).swap_rows_and_columns
+    }
+
+    /// Returns the number of RAM lines along the scrolling axis and the number of
+    /// pixels per line.
+    pub(crate) fn ram_lines(&self) -> (u16, u16) {
+        (M::FRAMEBUFFER_SIZE.1, self.options.display_size.0)
+    }
+
+    /// Draws a single pixel, whose coordinate along the scrolling axis is relative to
+    /// the first visible line.
+    pub(crate) fn draw_ram_pixel(
+        &mut self,
+        point: Point,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error> {
+        if let [Some((x, y, _, _)), _] = self.ram_windows(&Rectangle::new(point, Size::new(1, 1))) {
+            self.write_pixels_raw(x, y, x, y, core::iter::once(color))?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills a rectangle, whose coordinate along the scrolling axis is relative to the
+    /// first visible line.
+    pub(crate) fn fill_ram(
+        &mut self,
+        area: &Rectangle,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error> {
+        for (sx, sy, ex, ey) in self.ram_windows(area).into_iter().flatten() {
+            self.fill_raw(sx, sy, ex, ey, color)?;
+        }
+
+        Ok(())
+    }
+
+    // Converts a rectangle relative to the first visible line into up to two
+    // rectangles in controller address coordinates, because the RAM wraps around
+    // when it is scrolled.
+    fn ram_windows(&self, area: &Rectangle) -> [Option<(u16, u16, u16, u16)>; 2] {
+        let (rows, width) = self.ram_lines();
+        let horizontal = self.scrolls_horizontally();
+
+        // Swap the coordinates to make the scrolling axis the y axis.
+        let (mut tl, mut size) = (area.top_left, area.size);
+        if horizontal {
+            tl = Point::new(tl.y, tl.x);
+            size = Size::new(size.height, size.width);
+        }
+        let ram = Rectangle::new(Point::zero(), Size::new(width.into(), rows.into()));
+        let area = Rectangle::new(tl, size).intersection(&ram);
+        if area.is_zero_sized() {
+            return [None, None];
+        }
+
+        let (mut ox, mut oy) = self.address_offset();
+        if horizontal {
+            (ox, oy) = (oy, ox);
+        }
+        let first_visible = if MemoryMapping::from(self.options.hardware_orientation()).reverse_rows
+        {
+            (rows - self.scroll_offset % rows) % rows
+        } else {
+            self.scroll_offset
+        };
+
+        let start = add_lines(
+            add_lines(area.top_left.y as u16, first_visible, rows),
+            oy,
+            rows,
+        );
+        let len = area.size.height as u16;
+        let sx = area.top_left.x as u16 + ox;
+        let ex = sx + area.size.width as u16 - 1;
+
+        let window = |sy: u16, ey: u16| {
+            if horizontal {
+                (sy, sx, ey, ex)
+            } else {
+                (sx, sy, ex, ey)
+            }
+        };
+
+        if u32::from(start) + u32::from(len) <= u32::from(rows) {
+            [Some(window(start, start + len - 1)), None]
+        } else {
+            [
+                Some(window(start, rows - 1)),
+                Some(window(0, len - (rows - start) - 1)),
+            ]
+        }
+    }
+}
+
+/// Draw target for the RAM lines which aren't visible.
+///
+/// The first line of the region directly follows the last visible line. See the
+/// [module documentation](self) for more information.
+pub struct OffscreenRegion<'a, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut Display<DI, M, RST, BL>,
+}
+
+impl<DI, M, RST, BL> OffscreenRegion<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    // Converts a rectangle in region coordinates into the coordinates used by
+    // `Display::fill_ram`.
+    fn to_ram(&self, area: &Rectangle) -> Rectangle {
+        let area = area.intersection(&self.bounding_box());
+        let visible = i32::from(self.display.options.display_size.1);
+
+        if self.display.scrolls_horizontally() {
+            Rectangle::new(area.top_left + Point::new(visible, 0), area.size)
+        } else {
+            Rectangle::new(area.top_left + Point::new(0, visible), area.size)
+        }
+    }
+}
+
+impl<DI, M, RST, BL> DrawTarget for OffscreenRegion<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let area = self.to_ram(&Rectangle::new(point, Size::new(1, 1)));
+            if !area.is_zero_sized() {
+                self.display.draw_ram_pixel(area.top_left, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.to_ram(area);
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.display.fill_ram(&area, color)
+    }
+}
+
+impl<DI, M, RST, BL> OriginDimensions for OffscreenRegion<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    fn size(&self) -> Size {
+        let (rows, width) = self.display.ram_lines();
+        let lines = rows.saturating_sub(self.display.options.display_size.1);
+
+        if self.display.scrolls_horizontally() {
+            Size::new(lines.into(), width.into())
+        } else {
+            Size::new(width.into(), lines.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _mock::MockDelay,
+        _test::{display, RecordingInterface},
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn region_follows_visible_area() {
+        let mut display = display(Orientation::new());
+        display.set_vertical_scroll_region(0, 0).unwrap();
+        assert_eq!(display.offscreen_region().size(), Size::new(240, 80));
+
+        let area = Rectangle::new(Point::new(0, 0), Size::new(240, 10));
+        display
+            .offscreen_region()
+            .fill_solid(&area, Rgb565::RED)
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 0, 0, 239]);
+        assert_eq!(di.pages, [0, 240, 0, 249]);
+
+        // After revealing the filled lines the region starts at the next line and
+        // the lines which were scrolled off the screen are at the end.
+        display.reveal_offscreen(10).unwrap();
+        assert_eq!(display.vertical_scroll_offset(), 10);
+        display
+            .offscreen_region()
+            .fill_solid(
+                &Rectangle::new(Point::new(0, 70), Size::new(240, 20)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pages, [0, 0, 0, 9]);
+    }
+
+    #[test]
+    fn region_wraps_around() {
+        let mut display = display(Orientation::new());
+        display.reveal_offscreen(50).unwrap();
+
+        unsafe { display.dcs() }.windows = 0;
+        display
+            .offscreen_region()
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 0), Size::new(20, 80)),
+                Rgb565::RED,
+            )
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, 2);
+        assert_eq!(di.columns, [0, 10, 0, 29]);
+        assert_eq!(di.pages, [0, 0, 0, 49]);
+    }
+
+    #[test]
+    fn region_with_swapped_axes() {
+        let mut display = display(Orientation::new().rotate(Rotation::Deg90));
+        assert_eq!(display.offscreen_region().size(), Size::new(80, 240));
+
+        display
+            .offscreen_region()
+            .draw_iter([
+                Pixel(Point::new(5, 7), Rgb565::RED),
+                Pixel(Point::new(80, 0), Rgb565::RED),
+            ])
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 245, 0, 245]);
+        assert_eq!(di.pages, [0, 7, 0, 7]);
+    }
+
+    /// Model with more RAM lines than can be added with 16-bit integers.
+    struct TallModel;
+
+    impl Model for TallModel {
+        type ColorFormat = Rgb565;
+        const FRAMEBUFFER_SIZE: (u16, u16) = (8, 60000);
+
+        fn init<DELAY, DI>(
+            &mut self,
+            _di: &mut DI,
+            _delay: &mut DELAY,
+            options: &crate::options::ModelOptions,
+        ) -> Result<crate::dcs::SetAddressMode, crate::models::ModelInitError<DI::Error>>
+        where
+            DELAY: embedded_hal::delay::DelayNs,
+            DI: Interface,
+        {
+            Ok(crate::dcs::SetAddressMode::from(options))
+        }
+    }
+
+    #[test]
+    fn large_framebuffer_does_not_overflow() {
+        let mut display = Builder::new(TallModel, RecordingInterface::default())
+            .display_size(8, 40000)
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_vertical_scroll_region(0, 0).unwrap();
+        display.set_vertical_scroll_offset(50000).unwrap();
+        display.reveal_offscreen(5000).unwrap();
+        assert_eq!(display.vertical_scroll_offset(), 55000);
+
+        let area = Rectangle::new(Point::new(0, 15000), Size::new(8, 10));
+        display
+            .offscreen_region()
+            .fill_solid(&area, Rgb565::RED)
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 0, 0, 7]);
+        assert_eq!(di.pages, [0xC3, 0x50, 0xC3, 0x59]); // 50000 - 50009
+
+        // The area wraps around at the end of the RAM
+        display.set_vertical_scroll_offset(15000).unwrap();
+        let windows = unsafe { display.dcs() }.windows;
+        let area = Rectangle::new(Point::new(0, 4995), Size::new(8, 10));
+        display
+            .offscreen_region()
+            .fill_solid(&area, Rgb565::RED)
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, windows + 2);
+        assert_eq!(di.pages, [0, 0, 0, 4]);
+
+        // Fixed areas which don't fit into the RAM don't overflow
+        display.set_vertical_scroll_region(40000, 40000).unwrap();
+    }
+}
@@ -23,18 +23,35 @@ pub struct ModelOptions {
     pub display_size: (u16, u16),
     /// Display offset (x, y) for given display.
     pub display_offset: (u16, u16),
+    /// Vendor brightness and gamma table, which is sent by models that support it.
+    pub brightness_table: Option<BrightnessTable>,
+    /// Mounting of the panel, which is applied in addition to the orientation.
+    pub mounting: Mounting,
+    /// Byte order of 16-bit pixel data.
+    pub endianness: Endianness,
+    /// Whether the display is turned on at the end of [`Model::init`].
+    ///
+    /// The builder clears this option to run the [`init_hook`](crate::Builder::init_hook),
+    /// to draw a splash screen or to draw the first frame before the display is turned on.
+    pub display_on: bool,
 }
 
 impl ModelOptions {
     /// Creates model options for the entire framebuffer.
+    ///
+    /// The color order and color inversion are set to the model defaults.
     pub fn full_size<M: Model>() -> Self {
         Self {
-            color_order: ColorOrder::default(),
+            color_order: M::DEFAULT_COLOR_ORDER,
             orientation: Orientation::default(),
-            invert_colors: ColorInversion::default(),
+            invert_colors: M::DEFAULT_COLOR_INVERSION,
             refresh_order: RefreshOrder::default(),
             display_size: M::FRAMEBUFFER_SIZE,
             display_offset: (0, 0),
+            brightness_table: None,
+            mounting: Mounting::new(),
+            endianness: Endianness::default(),
+            display_on: true,
         }
     }
 
@@ -47,14 +64,60 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size,
             display_offset,
+            brightness_table: None,
+            mounting: Mounting::new(),
+            endianness: Endianness::default(),
+            display_on: true,
+        }
+    }
+
+    /// Returns the orientation which is used by the controller.
+    ///
+    /// This is the orientation combined with the orientation of the mounting.
+    pub(crate) fn hardware_orientation(&self) -> Orientation {
+        self.mounting.orientation.then(self.orientation)
+    }
+
+    /// Returns the offset between display coordinates and controller address coordinates.
+    ///
+    /// Returns `None` if the display area, the display offset and the mounting correction
+    /// result in addresses outside of the controller address space, which would otherwise
+    /// make the address window wrap around.
+    pub(crate) fn address_offset(&self, framebuffer_size: (u16, u16)) -> Option<(u16, u16)> {
+        let mut offset = self.display_offset;
+        let mapping = MemoryMapping::from(self.hardware_orientation());
+
+        let size = self.display_size;
+        if mapping.reverse_columns {
+            offset.0 = framebuffer_size
+                .0
+                .checked_sub(size.0.checked_add(offset.0)?)?;
         }
+        if mapping.reverse_rows {
+            offset.1 = framebuffer_size
+                .1
+                .checked_sub(size.1.checked_add(offset.1)?)?;
+        }
+        let correction = self.mounting.offset;
+        offset.0 = offset.0.checked_add_signed(correction.0)?;
+        offset.1 = offset.1.checked_add_signed(correction.1)?;
+
+        // The last pixel of the display must be addressable too
+        offset.0.checked_add(size.0.saturating_sub(1))?;
+        offset.1.checked_add(size.1.saturating_sub(1))?;
+
+        if mapping.swap_rows_and_columns {
+            offset = (offset.1, offset.0);
+        }
+
+        Some(offset)
     }
 
     /// Returns the display size based on current orientation and display options.
     ///
     /// Used by models.
     pub(crate) fn display_size(&self) -> (u16, u16) {
-        if self.orientation.rotation.is_horizontal() {
+        if self.hardware_orientation().rotation.is_horizontal() {
             self.display_size
         } else {
             (self.display_size.1, self.display_size.0)
@@ -62,36 +125,99 @@ impl ModelOptions {
     }
 }
 
+/// Mounting of the panel.
+///
+/// Describes how a panel is mounted in a device, if the default orientation of the
+/// controller doesn't match the orientation of the device. The mounting is applied in
+/// addition to the [`Orientation`], which is then relative to the device instead of the
+/// panel. This hides the corrections for the mounting from the application code, e.g. when
+/// the orientation is changed at runtime.
+///
+/// The offset corrects the address window for panels whose visible area isn't at the
+/// position which is calculated from the display offset, e.g. if the panel is only offset
+/// in one orientation. It is added to the controller RAM addresses of the columns and rows
+/// after the orientation was applied.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::options::{Mounting, Orientation, Rotation};
+///
+/// // Panel which is mounted upside down and is shifted by two columns.
+/// let mounting = Mounting::new()
+///     .orientation(Orientation::new().rotate(Rotation::Deg180))
+///     .offset(2, 0);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mounting {
+    /// Orientation of the device relative to the default orientation of the panel.
+    pub orientation: Orientation,
+    /// Correction of the controller RAM addresses as columns and rows.
+    pub offset: (i16, i16),
+}
+
+impl Mounting {
+    /// Creates a mounting without corrections.
+    pub const fn new() -> Self {
+        Self {
+            orientation: Orientation::new(),
+            offset: (0, 0),
+        }
+    }
+
+    /// Sets the orientation of the device relative to the panel.
+    #[must_use]
+    pub const fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the correction of the column and row addresses.
+    #[must_use]
+    pub const fn offset(mut self, columns: i16, rows: i16) -> Self {
+        self.offset = (columns, rows);
+        self
+    }
+}
+
 /// Color inversion.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum ColorInversion {
     /// Normal colors.
+    #[default]
     Normal,
     /// Inverted colors.
     Inverted,
 }
 
-impl Default for ColorInversion {
-    fn default() -> Self {
-        Self::Normal
-    }
+/// Byte order of 16-bit pixel data.
+///
+/// Controllers expect the high byte of an Rgb565 pixel first, but some boards, e.g. with
+/// certain ST7789 clones or 16-bit parallel buses with swapped data lines, receive the pixel
+/// data in the opposite order, which swaps the colors. [`Little`](Self::Little) sends the
+/// low byte of every 16-bit pixel first. Models which don't send 16-bit pixels, e.g.
+/// [`ILI9488Rgb565`](crate::models::ILI9488Rgb565) which expands the colors to 18 bits on
+/// serial interfaces, aren't affected. Raw pixel data for
+/// [`Display::set_pixels_from_buffer`](crate::Display::set_pixels_from_buffer) isn't changed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// High byte first.
+    #[default]
+    Big,
+    /// Low byte first.
+    Little,
 }
 
 /// Vertical refresh order.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum VerticalRefreshOrder {
     /// Refresh from top to bottom.
+    #[default]
     TopToBottom,
     /// Refresh from bottom to top.
     BottomToTop,
 }
 
-impl Default for VerticalRefreshOrder {
-    fn default() -> Self {
-        Self::TopToBottom
-    }
-}
-
 impl VerticalRefreshOrder {
     /// Returns the opposite refresh order.
     #[must_use]
@@ -104,20 +230,15 @@ impl VerticalRefreshOrder {
 }
 
 /// Horizontal refresh order.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum HorizontalRefreshOrder {
     /// Refresh from left to right.
+    #[default]
     LeftToRight,
     /// Refresh from right to left.
     RightToLeft,
 }
 
-impl Default for HorizontalRefreshOrder {
-    fn default() -> Self {
-        Self::LeftToRight
-    }
-}
-
 impl HorizontalRefreshOrder {
     /// Returns the opposite refresh order.
     #[must_use]
@@ -168,6 +289,43 @@ impl RefreshOrder {
     }
 }
 
+/// Vendor specific brightness and gamma table.
+///
+/// Some AMOLED controllers, like the [`RM67162`](crate::models::RM67162), default to a
+/// brightness curve which causes visible banding at high brightness levels. Panel vendors
+/// provide tables of register writes, which configure the brightness and gamma curve for a
+/// specific panel. The commands are sent unchanged during the init sequence, including the
+/// page select commands (e.g. `0xFE`) which are required to reach the vendor registers.
+///
+/// # Examples
+///
+/// ```
+/// use mipidsi::options::BrightnessTable;
+///
+/// // Register writes provided by the panel vendor
+/// const TABLE: BrightnessTable = BrightnessTable::new(&[
+///     (0xFE, &[0x05]),
+///     (0xC7, &[0x00, 0x01]),
+///     (0xB0, &[0x02]),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrightnessTable {
+    commands: &'static [(u8, &'static [u8])],
+}
+
+impl BrightnessTable {
+    /// Creates a table from a list of commands and their parameters.
+    pub const fn new(commands: &'static [(u8, &'static [u8])]) -> Self {
+        Self { commands }
+    }
+
+    /// Returns the commands and their parameters.
+    pub const fn commands(&self) -> &'static [(u8, &'static [u8])] {
+        self.commands
+    }
+}
+
 /// Tearing effect output setting.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum TearingEffect {
@@ -179,17 +337,29 @@ pub enum TearingEffect {
     HorizontalAndVertical,
 }
 
+/// Brightness control setting.
+///
+/// Configures the brightness control of controllers which implement
+/// [`DisplayBrightness`](crate::models::DisplayBrightness).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BrightnessControl {
+    /// Enables the brightness control block (BCTRL).
+    ///
+    /// The brightness set with [`Display::set_brightness`](crate::Display::set_brightness)
+    /// is only applied while the brightness control block is enabled.
+    pub brightness_control: bool,
+    /// Enables a smooth transition when the brightness is changed (DD).
+    pub dimming: bool,
+    /// Enables the backlight control output of the controller (BL).
+    pub backlight: bool,
+}
+
 /// Subpixel order.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ColorOrder {
     /// RGB subpixel order.
+    #[default]
     Rgb,
     /// BGR subpixel order.
     Bgr,
 }
-
-impl Default for ColorOrder {
-    fn default() -> Self {
-        Self::Rgb
-    }
-}
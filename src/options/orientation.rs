@@ -157,6 +157,21 @@ impl Orientation {
             self.flip_vertical_absolute()
         }
     }
+
+    /// Applies another orientation relative to this orientation.
+    ///
+    /// The result is the same as first mirroring this orientation with
+    /// [`flip_horizontal`](Self::flip_horizontal), if `other` is mirrored, and then
+    /// rotating it by the rotation of `other`.
+    #[must_use]
+    pub const fn then(self, other: Orientation) -> Self {
+        let orientation = if other.mirrored {
+            self.flip_horizontal()
+        } else {
+            self
+        };
+        orientation.rotate(other.rotation)
+    }
 }
 
 impl Default for Orientation {
@@ -242,6 +257,28 @@ mod tests {
         Orientation { rotation, mirrored }
     }
 
+    #[test]
+    fn then() {
+        use Rotation::*;
+
+        for rotation in [Deg0, Deg90, Deg180, Deg270] {
+            for mirrored in [false, true] {
+                let o = orientation(rotation, mirrored);
+                assert_eq!(Orientation::new().then(o), o);
+                assert_eq!(o.then(Orientation::new()), o);
+            }
+        }
+
+        assert_eq!(
+            orientation(Deg90, false).then(orientation(Deg180, false)),
+            orientation(Deg270, false)
+        );
+        assert_eq!(
+            orientation(Deg90, false).then(orientation(Deg0, true)),
+            Orientation::new().rotate(Deg90).flip_horizontal()
+        );
+    }
+
     #[test]
     fn flip_horizontal() {
         use Rotation::*;
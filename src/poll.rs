@@ -0,0 +1,290 @@
+//! Non-blocking drawing operations.
+//!
+//! Filling a large area takes a long time on slow interfaces, during which a firmware with
+//! a super loop can't do anything else. [`Display::start_fill`] starts a fill operation,
+//! which is then continued in small steps by calling [`PendingFill::poll`] until it returns
+//! [`Poll::Ready`]. Other work, like input handling, can be done between the calls.
+//!
+//! [`Display::clear_with_progress`] and [`Display::fill_solid_with_progress`] use the same
+//! mechanism to report the progress of a blocking fill, e.g. to update a progress bar or to
+//! feed a watchdog during the initial clear of a large display.
+//!
+//! This API is experimental and might change in future versions.
+//!
+//! # Examples
+//!
+//! ```
+//! use core::task::Poll;
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! let area = Rectangle::new(Point::zero(), Size::new(240, 320));
+//! let mut fill = display.start_fill(&area, Rgb565::BLUE, 1024).unwrap();
+//!
+//! while fill.poll().unwrap() == Poll::Pending {
+//!     // handle input
+//! }
+//! ```
+
+use core::task::Poll;
+
+use embedded_graphics_core::{geometry::Dimensions, primitives::Rectangle};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Event, Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Starts filling an area with a single color.
+    ///
+    /// The area is clipped like in [`fill_solid`](embedded_graphics_core::draw_target::DrawTarget::fill_solid).
+    /// This function only sets the address window, the pixels are sent by calling
+    /// [`PendingFill::poll`], which sends at most `pixels_per_poll` pixels per call.
+    ///
+    /// See the [module documentation](crate::poll) for more information.
+    pub fn start_fill(
+        &mut self,
+        area: &Rectangle,
+        color: M::ColorFormat,
+        pixels_per_poll: u32,
+    ) -> Result<PendingFill<'_, DI, M, RST, BL>, DI::Error> {
+        let area = area.intersection(&self.drawable_area());
+        let remaining = area.size.width * area.size.height;
+
+        let window = area.bottom_right().and_then(|bottom_right| {
+            self.offset_window(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                bottom_right.x as u16,
+                bottom_right.y as u16,
+            )
+        });
+        if let Some((sx, sy, ex, ey)) = window {
+            self.di.handle_event(Event::BeginTransaction)?;
+            let result = self
+                .set_address_window_raw(sx, sy, ex, ey)
+                .and_then(|()| self.model.write_memory_start(&mut self.di));
+            if let Err(e) = result {
+                let _ = self.di.handle_event(Event::EndTransaction);
+                return Err(e);
+            }
+        }
+
+        Ok(PendingFill {
+            display: self,
+            color,
+            remaining,
+            pixels_per_poll: pixels_per_poll.max(1),
+        })
+    }
+
+    /// Fills an area with a single color and reports the progress.
+    ///
+    /// The area is filled in 100 steps. `progress` is called with `0.0` before the first step
+    /// and after each step with the completed fraction of the area, which is `1.0` after the
+    /// last step.
+    pub fn fill_solid_with_progress(
+        &mut self,
+        area: &Rectangle,
+        color: M::ColorFormat,
+        mut progress: impl FnMut(f32),
+    ) -> Result<(), DI::Error> {
+        let area = area.intersection(&self.drawable_area());
+        let total = area.size.width * area.size.height;
+
+        let mut fill = self.start_fill(&area, color, total.div_ceil(PROGRESS_STEPS))?;
+        progress(0.0);
+        while fill.poll()?.is_pending() {
+            progress(1.0 - fill.remaining() as f32 / total as f32);
+        }
+        progress(1.0);
+
+        Ok(())
+    }
+
+    /// Clears the display with a single color and reports the progress.
+    ///
+    /// See [`fill_solid_with_progress`](Self::fill_solid_with_progress) for more information.
+    pub fn clear_with_progress(
+        &mut self,
+        color: M::ColorFormat,
+        progress: impl FnMut(f32),
+    ) -> Result<(), DI::Error> {
+        self.fill_solid_with_progress(&self.bounding_box(), color, progress)
+    }
+}
+
+// Number of steps of fills with progress reports.
+const PROGRESS_STEPS: u32 = 100;
+
+/// Fill operation started by [`Display::start_fill`].
+///
+/// If the fill is dropped before it is completed, the remaining pixels aren't drawn.
+pub struct PendingFill<'a, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut Display<DI, M, RST, BL>,
+    color: M::ColorFormat,
+    remaining: u32,
+    pixels_per_poll: u32,
+}
+
+impl<DI, M, RST, BL> PendingFill<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Sends the next pixels.
+    ///
+    /// Returns [`Poll::Ready`] after the last pixels were sent and [`Poll::Pending`] if
+    /// there are pixels left.
+    pub fn poll(&mut self) -> Result<Poll<()>, DI::Error> {
+        if self.remaining == 0 {
+            return Ok(Poll::Ready(()));
+        }
+
+        let count = self.remaining.min(self.pixels_per_poll);
+        let result = self.display.model.send_repeated_pixel(
+            &mut self.display.di,
+            self.color,
+            count,
+            self.display.options.endianness,
+        );
+        if let Err(e) = result {
+            // The fill can't be continued after an error
+            self.remaining = 0;
+            let _ = self.display.di.handle_event(Event::EndTransaction);
+            return Err(e);
+        }
+        self.remaining -= count;
+
+        if self.remaining > 0 {
+            return Ok(Poll::Pending);
+        }
+
+        self.display.di.handle_event(Event::EndTransaction)?;
+        Ok(Poll::Ready(()))
+    }
+
+    /// Returns the number of pixels which haven't been sent yet.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+}
+
+impl<DI, M, RST, BL> Drop for PendingFill<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    fn drop(&mut self) {
+        if self.remaining > 0 {
+            // Errors can't be reported here, the next drawing operation will most likely
+            // return the same error.
+            let _ = self.display.di.handle_event(Event::EndTransaction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+
+    use crate::{_mock::MockDelay, _test::RecordingInterface, models::ST7789, Builder};
+
+    use super::*;
+
+    fn display() -> Display<RecordingInterface, ST7789, crate::NoResetPin> {
+        Builder::new(ST7789, RecordingInterface::default())
+            .init(&mut MockDelay)
+            .unwrap()
+    }
+
+    #[test]
+    fn fill_is_sent_in_steps() {
+        let mut display = display();
+        let area = Rectangle::new(Point::new(-5, 0), Size::new(15, 10));
+
+        let mut fill = display.start_fill(&area, Rgb565::RED, 40).unwrap();
+        assert_eq!(fill.remaining(), 100);
+        assert_eq!(fill.poll().unwrap(), Poll::Pending);
+        assert_eq!(fill.poll().unwrap(), Poll::Pending);
+        assert_eq!(fill.poll().unwrap(), Poll::Ready(()));
+        assert_eq!(fill.poll().unwrap(), Poll::Ready(()));
+        drop(fill);
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pixels, 100);
+        assert_eq!(di.transactions, 0);
+    }
+
+    #[test]
+    fn dropped_fill_ends_transaction() {
+        let mut display = display();
+        let area = Rectangle::new(Point::zero(), Size::new(10, 10));
+
+        let mut fill = display.start_fill(&area, Rgb565::RED, 40).unwrap();
+        assert_eq!(fill.poll().unwrap(), Poll::Pending);
+        drop(fill);
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pixels, 40);
+        assert_eq!(di.transactions, 0);
+    }
+
+    #[test]
+    fn empty_fill_is_ready() {
+        let mut display = display();
+        let area = Rectangle::new(Point::new(-20, 0), Size::new(10, 10));
+
+        let mut fill = display.start_fill(&area, Rgb565::RED, 40).unwrap();
+        assert_eq!(fill.poll().unwrap(), Poll::Ready(()));
+        drop(fill);
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.transactions, 0);
+    }
+
+    #[test]
+    fn clear_reports_progress() {
+        let mut display = display();
+
+        let mut calls = 0;
+        let mut last = -1.0;
+        display
+            .clear_with_progress(Rgb565::BLACK, |progress| {
+                assert!(progress > last);
+                last = progress;
+                calls += 1;
+            })
+            .unwrap();
+        assert_eq!(calls, 101);
+        assert_eq!(last, 1.0);
+
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.pixels, 240 * 320);
+        assert_eq!(di.transactions, 0);
+    }
+}
@@ -0,0 +1,122 @@
+//! Flush requests for interrupt driven firmware.
+//!
+//! A common pattern in [RTIC](https://rtic.rs) applications is to draw the next frame into a
+//! framebuffer in the idle task and to send it to the display when the tearing effect (TE)
+//! output of the controller signals the start of the vertical blanking period. The TE
+//! interrupt handler should be as short as possible, so it only posts a [`FlushRequest`],
+//! which is taken by the task that owns the display.
+//!
+//! [`FlushRequest`] doesn't depend on RTIC and only uses atomic loads and stores, which makes
+//! it usable from any interrupt handler, also on targets without atomic read-modify-write
+//! instructions like ARMv6-M. It can be stored in a `static` or in a shared resource.
+//!
+//! Requires the `rtic` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi::rtic::FlushRequest;
+//!
+//! static FLUSH: FlushRequest = FlushRequest::new();
+//!
+//! // TE interrupt handler
+//! fn on_tearing_effect() {
+//!     FLUSH.request();
+//! }
+//!
+//! // display task
+//! fn display_task() -> bool {
+//!     if FLUSH.take() {
+//!         // send the framebuffer to the display, e.g. with `display.fill_contiguous`
+//!         return true;
+//!     }
+//!     false
+//! }
+//!
+//! on_tearing_effect();
+//! assert!(display_task());
+//! assert!(!display_task());
+//! ```
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Lock-free mailbox for flush requests.
+///
+/// [`request`](Self::request) must only be called from one context, e.g. the TE interrupt
+/// handler, and [`take`](Self::take) must only be called from one other context, e.g. the
+/// display task. The requests and the taken requests are stored in two counters, which
+/// each have a single writer. Multiple requests before a take are merged into one.
+#[derive(Debug, Default)]
+pub struct FlushRequest {
+    requested: AtomicUsize,
+    taken: AtomicUsize,
+}
+
+impl FlushRequest {
+    /// Creates a new mailbox without a pending request.
+    pub const fn new() -> Self {
+        Self {
+            requested: AtomicUsize::new(0),
+            taken: AtomicUsize::new(0),
+        }
+    }
+
+    /// Requests a flush.
+    pub fn request(&self) {
+        let requested = self.requested.load(Ordering::Relaxed);
+        self.requested
+            .store(requested.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Returns `true` if a flush was requested and hasn't been taken yet.
+    pub fn is_pending(&self) -> bool {
+        self.requested.load(Ordering::Acquire) != self.taken.load(Ordering::Relaxed)
+    }
+
+    /// Takes the pending request.
+    ///
+    /// Returns `true` if a flush was requested since the last call.
+    pub fn take(&self) -> bool {
+        let requested = self.requested.load(Ordering::Acquire);
+        if requested == self.taken.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        self.taken.store(requested, Ordering::Relaxed);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_merged() {
+        let flush = FlushRequest::new();
+        assert!(!flush.is_pending());
+        assert!(!flush.take());
+
+        flush.request();
+        flush.request();
+        assert!(flush.is_pending());
+        assert!(flush.take());
+        assert!(!flush.is_pending());
+        assert!(!flush.take());
+
+        flush.request();
+        assert!(flush.take());
+    }
+
+    #[test]
+    fn counter_wraps_around() {
+        let flush = FlushRequest {
+            requested: AtomicUsize::new(usize::MAX),
+            taken: AtomicUsize::new(usize::MAX),
+        };
+
+        flush.request();
+        assert!(flush.take());
+        assert!(!flush.take());
+    }
+}
@@ -0,0 +1,595 @@
+//! Orientation aware hardware scrolling.
+//!
+//! [`Display::set_vertical_scroll_region`] and [`Display::set_vertical_scroll_offset`]
+//! use the raw controller coordinates, which don't take the orientation, the display
+//! offset or the size of the framebuffer into account. [`Scroller`] uses the same
+//! hardware feature, but the fixed areas and the scroll direction are specified in the
+//! coordinates of the current orientation. The scroll area only contains the visible
+//! lines, so that lines which are scrolled out of view at the start of the scroll area
+//! reappear at the end of it.
+//!
+//! Drawing to the display itself isn't affected by the scroll position, i.e. the content
+//! appears shifted by the scrolled lines. [`Scroller::canvas`] returns a draw target
+//! which uses the coordinates of the visible content instead, which makes it possible to
+//! implement terminal-style scrolling by drawing new lines at the end of the scroll area
+//! after each scroll.
+//!
+//! [`Display::scroll_overlay`] draws the boundaries of the scroll area and the current
+//! scroll offset, which helps to debug the raw scroll settings on panels that only show a
+//! part of the controller RAM.
+//!
+//! The scrolling axis is the vertical axis of the display in the default orientation.
+//! In orientations with a 90° or 270° rotation the content scrolls horizontally, from
+//! right to left for positive lines.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+//! use mipidsi::{models::ST7789, Builder};
+//!
+//! # let di = mipidsi::_mock::MockDisplayInterface;
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! let mut display = Builder::new(ST7789, di)
+//!     .display_size(240, 240)
+//!     .init(&mut delay)
+//!     .unwrap();
+//!
+//! // Keep a 20 line status bar at the top of the display.
+//! let mut scroller = display.scroller(20, 0).unwrap();
+//!
+//! // Scroll one line of text and clear the line which appears at the bottom.
+//! scroller.scroll_by(&mut display, 10).unwrap();
+//! scroller
+//!     .canvas(&mut display)
+//!     .fill_solid(
+//!         &Rectangle::new(Point::new(0, 230), Size::new(240, 10)),
+//!         Rgb565::BLACK,
+//!     )
+//!     .unwrap();
+//! ```
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Drawable, Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::MemoryMapping,
+    Display,
+};
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Creates a [`Scroller`] with the given fixed areas.
+    ///
+    /// See [`Scroller::new`] and the [module documentation](crate::scroller) for more
+    /// information.
+    pub fn scroller(
+        &mut self,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<Scroller, DI::Error> {
+        Scroller::new(self, top_fixed_area, bottom_fixed_area)
+    }
+
+    /// Returns an overlay which shows the current hardware scroll state.
+    ///
+    /// See [`ScrollOverlay`] for more information.
+    pub fn scroll_overlay(
+        &self,
+        boundary_color: M::ColorFormat,
+        offset_color: M::ColorFormat,
+    ) -> ScrollOverlay<M::ColorFormat> {
+        let rows = M::FRAMEBUFFER_SIZE.1;
+        let (top_fixed_area, lines) = self.scroll_area;
+        let reverse_rows = MemoryMapping::from(self.options.hardware_orientation()).reverse_rows;
+        let first_line = i32::from(self.first_line());
+        let height = i32::from(self.options.display_size.1);
+
+        // Converts a RAM line into a display line, if it is visible.
+        let to_display = |ram_line: u16| {
+            let address = if reverse_rows {
+                rows.saturating_sub(ram_line + 1)
+            } else {
+                ram_line
+            };
+            let line = i32::from(address) - first_line;
+            (0..height).contains(&line).then_some(line)
+        };
+
+        // The RAM line which is shown at the given position of the scroll area.
+        let shown = |position: i32| {
+            let start = (i32::from(self.scroll_offset) - i32::from(top_fixed_area))
+                .rem_euclid(i32::from(lines));
+            top_fixed_area + ((start + position) % i32::from(lines)) as u16
+        };
+
+        let (boundaries, offset) = if lines == 0 {
+            ([None, None], None)
+        } else {
+            (
+                [
+                    to_display(shown(0)),
+                    to_display(shown(i32::from(lines) - 1)),
+                ],
+                to_display(top_fixed_area),
+            )
+        };
+
+        ScrollOverlay {
+            boundaries,
+            offset,
+            horizontal: self.scrolls_horizontally(),
+            length: self.options.display_size.0.into(),
+            boundary_color,
+            offset_color,
+        }
+    }
+
+    // Returns the address line of the first display line along the scrolling axis.
+    fn first_line(&self) -> u16 {
+        let (ox, oy) = self.address_offset();
+        if self.scrolls_horizontally() {
+            ox
+        } else {
+            oy
+        }
+    }
+}
+
+/// Orientation aware hardware scrolling.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct Scroller {
+    top_fixed_area: u16,
+    lines: u16,
+    position: u16,
+}
+
+impl Scroller {
+    /// Creates a new scroller.
+    ///
+    /// The fixed areas are the number of lines at the start and the end of the scrolling
+    /// axis in the current orientation, which aren't scrolled. They are clamped to make
+    /// sure that at least one line is scrolled. The scroll region of the controller is
+    /// set to the remaining visible lines and the scroll position is reset.
+    ///
+    /// The scroller must be recreated after the orientation was changed.
+    pub fn new<DI, M, RST, BL>(
+        display: &mut Display<DI, M, RST, BL>,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<Self, DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let height = display.options.display_size.1.max(1);
+        let top_fixed_area = top_fixed_area.min(height - 1);
+        let bottom_fixed_area = bottom_fixed_area.min(height - 1 - top_fixed_area);
+
+        let scroller = Self {
+            top_fixed_area,
+            lines: height - top_fixed_area - bottom_fixed_area,
+            position: 0,
+        };
+
+        // The fixed areas of the controller also contain the lines outside of the display.
+        let rows = M::FRAMEBUFFER_SIZE.1;
+        let first_line = display.first_line();
+        let top = first_line.saturating_add(top_fixed_area);
+        let bottom = rows
+            .saturating_sub(first_line.saturating_add(height))
+            .saturating_add(bottom_fixed_area);
+
+        // The fixed areas are swapped if the orientation reverses the rows.
+        if MemoryMapping::from(display.options.hardware_orientation()).reverse_rows {
+            display.set_vertical_scroll_region(bottom, top)?;
+        } else {
+            display.set_vertical_scroll_region(top, bottom)?;
+        }
+        scroller.update_scroll_offset(display)?;
+
+        Ok(scroller)
+    }
+
+    /// Returns the number of lines the content of the scroll area is scrolled by.
+    ///
+    /// The position is always less than the number of lines in the scroll area.
+    pub fn position(&self) -> u16 {
+        self.position
+    }
+
+    /// Scrolls the content of the scroll area by the given number of lines.
+    ///
+    /// Positive values move the content towards the start of the scroll area and
+    /// negative values towards the end. Lines which are moved out of the scroll area
+    /// reappear on the other side.
+    pub fn scroll_by<DI, M, RST, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL>,
+        lines: i32,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let count = i32::from(self.lines);
+        self.position =
+            (i32::from(self.position) + lines.rem_euclid(count)).rem_euclid(count) as u16;
+
+        self.update_scroll_offset(display)
+    }
+
+    /// Returns a draw target which uses the coordinates of the visible content.
+    ///
+    /// Drawing operations in the scroll area are moved by the current
+    /// [`position`](Self::position), so that they appear at the given coordinates on the
+    /// display. The fixed areas are drawn without any changes.
+    pub fn canvas<'a, DI, M, RST, BL>(
+        &self,
+        display: &'a mut Display<DI, M, RST, BL>,
+    ) -> ScrollerCanvas<'a, DI, M, RST, BL>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        ScrollerCanvas {
+            display,
+            scroller: self.clone(),
+        }
+    }
+
+    fn update_scroll_offset<DI, M, RST, BL>(
+        &self,
+        display: &mut Display<DI, M, RST, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let rows = M::FRAMEBUFFER_SIZE.1;
+        let height = display.options.display_size.1;
+        let first_line = display.first_line();
+
+        // The scroll offset counts RAM lines, which are in reverse order if the
+        // orientation reverses the rows.
+        let offset = if MemoryMapping::from(display.options.hardware_orientation()).reverse_rows {
+            let bottom_fixed_area = height - self.top_fixed_area - self.lines;
+            let top = rows
+                .saturating_sub(first_line.saturating_add(height))
+                .saturating_add(bottom_fixed_area);
+            top + (self.lines - self.position) % self.lines
+        } else {
+            first_line.saturating_add(self.top_fixed_area) + self.position
+        };
+
+        display.set_vertical_scroll_offset(offset)
+    }
+
+    // Converts a line in visible coordinates into the display line it is drawn to.
+    fn map_line(&self, line: i32) -> i32 {
+        let start = i32::from(self.top_fixed_area);
+        let lines = i32::from(self.lines);
+
+        if (start..start + lines).contains(&line) {
+            start + (line - start + i32::from(self.position)) % lines
+        } else {
+            line
+        }
+    }
+
+    // Splits a range of lines in visible coordinates into up to four ranges of display
+    // lines, because the scroll area wraps around.
+    fn map_lines(&self, start: i32, len: u32) -> [Option<(i32, u32)>; 4] {
+        let end = start.saturating_add_unsigned(len);
+        let scroll_start = i32::from(self.top_fixed_area);
+        let scroll_end = scroll_start + i32::from(self.lines);
+
+        let range = |start: i32, end: i32| (start < end).then(|| (start, (end - start) as u32));
+
+        let mut ranges = [
+            range(start, end.min(scroll_start)),
+            None,
+            None,
+            range(start.max(scroll_end), end),
+        ];
+
+        if let Some((start, len)) = range(start.max(scroll_start), end.min(scroll_end)) {
+            let mapped = self.map_line(start);
+            let wrapped = mapped.saturating_add_unsigned(len) - scroll_end;
+            if wrapped > 0 {
+                ranges[1] = Some((mapped, len - wrapped as u32));
+                ranges[2] = Some((scroll_start, wrapped as u32));
+            } else {
+                ranges[1] = Some((mapped, len));
+            }
+        }
+
+        ranges
+    }
+}
+
+/// Overlay which shows the current hardware scroll state.
+///
+/// The effects of the raw [`Display::set_vertical_scroll_region`] and
+/// [`Display::set_vertical_scroll_offset`] calls are hard to follow on panels which only
+/// show a part of the controller RAM. The overlay is created by
+/// [`Display::scroll_overlay`] and draws lines across the display, which stay at their
+/// position on the panel with the current scroll settings:
+///
+/// * the first and the last line of the scroll area in the boundary color,
+/// * the line which shows the start of the scroll area in RAM, i.e. the line where the
+///   scrolled content wraps around, in the offset color.
+///
+/// Lines which aren't visible on the display are skipped. The overlay overwrites the
+/// display content and must be recreated after the scroll settings were changed.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// display.set_vertical_scroll_region(20, 0).unwrap();
+/// display.set_vertical_scroll_offset(50).unwrap();
+///
+/// display
+///     .scroll_overlay(Rgb565::GREEN, Rgb565::RED)
+///     .draw(&mut display)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollOverlay<C> {
+    boundaries: [Option<i32>; 2],
+    offset: Option<i32>,
+    horizontal: bool,
+    length: u32,
+    boundary_color: C,
+    offset_color: C,
+}
+
+impl<C: PixelColor> Drawable for ScrollOverlay<C> {
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let lines = self
+            .boundaries
+            .iter()
+            .map(|line| (line, self.boundary_color))
+            .chain([(&self.offset, self.offset_color)]);
+
+        for (line, color) in lines {
+            if let Some(line) = *line {
+                let area = if self.horizontal {
+                    Rectangle::new(Point::new(line, 0), Size::new(1, self.length))
+                } else {
+                    Rectangle::new(Point::new(0, line), Size::new(self.length, 1))
+                };
+                target.fill_solid(&area, color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw target for the visible content of a [`Scroller`].
+pub struct ScrollerCanvas<'a, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    display: &'a mut Display<DI, M, RST, BL>,
+    scroller: Scroller,
+}
+
+impl<DI, M, RST, BL> DrawTarget for ScrollerCanvas<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let scroller = &self.scroller;
+        let horizontal = self.display.scrolls_horizontally();
+
+        self.display
+            .draw_iter(pixels.into_iter().map(|Pixel(point, color)| {
+                let point = if horizontal {
+                    Point::new(scroller.map_line(point.x), point.y)
+                } else {
+                    Point::new(point.x, scroller.map_line(point.y))
+                };
+                Pixel(point, color)
+            }))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        if self.display.scrolls_horizontally() {
+            let lines = self.scroller.map_lines(area.top_left.x, area.size.width);
+            for (x, width) in lines.into_iter().flatten() {
+                let area = Rectangle::new(
+                    Point::new(x, area.top_left.y),
+                    Size::new(width, area.size.height),
+                );
+                self.display.fill_solid(&area, color)?;
+            }
+        } else {
+            let lines = self.scroller.map_lines(area.top_left.y, area.size.height);
+            for (y, height) in lines.into_iter().flatten() {
+                let area = Rectangle::new(
+                    Point::new(area.top_left.x, y),
+                    Size::new(area.size.width, height),
+                );
+                self.display.fill_solid(&area, color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, BL> Dimensions for ScrollerCanvas<'_, DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{
+        _test::display,
+        options::{Orientation, Rotation},
+    };
+
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    fn fixed_areas_include_offscreen_lines() {
+        let mut display = display(Orientation::new());
+        let mut scroller = display.scroller(10, 20).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.scroll_area, [0, 10, 0, 210, 0, 100]);
+        assert_eq!(di.scroll_offset, [0, 10]);
+
+        scroller.scroll_by(&mut display, 5).unwrap();
+        assert_eq!(scroller.position(), 5);
+        assert_eq!(unsafe { display.dcs() }.scroll_offset, [0, 15]);
+
+        // Lines in the fixed areas aren't moved
+        let mut canvas = scroller.canvas(&mut display);
+        canvas
+            .fill_solid(&rect(0, 235, 240, 1), Rgb565::RED)
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.pages, [0, 235, 0, 235]);
+
+        let mut canvas = scroller.canvas(&mut display);
+        canvas
+            .fill_solid(&rect(0, 10, 240, 1), Rgb565::RED)
+            .unwrap();
+        assert_eq!(unsafe { display.dcs() }.pages, [0, 15, 0, 15]);
+    }
+
+    #[test]
+    fn scroll_area_wraps_around() {
+        let mut display = display(Orientation::new());
+        let mut scroller = display.scroller(10, 20).unwrap();
+        scroller.scroll_by(&mut display, -5).unwrap();
+        assert_eq!(scroller.position(), 205);
+        assert_eq!(unsafe { display.dcs() }.scroll_offset, [0, 215]);
+
+        unsafe { display.dcs() }.windows = 0;
+        scroller
+            .canvas(&mut display)
+            .fill_solid(&rect(0, 10, 240, 10), Rgb565::RED)
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.windows, 2);
+        assert_eq!(di.pages, [0, 10, 0, 14]);
+
+        scroller.scroll_by(&mut display, 215).unwrap();
+        assert_eq!(scroller.position(), 0);
+    }
+
+    #[test]
+    fn overlay_follows_scroll_state() {
+        let mut display = display(Orientation::new());
+        let overlay = display.scroll_overlay(Rgb565::GREEN, Rgb565::RED);
+        // The last line of the default scroll area is off-screen
+        assert_eq!(overlay.boundaries, [Some(0), None]);
+        assert_eq!(overlay.offset, Some(0));
+
+        display.set_vertical_scroll_region(10, 20).unwrap();
+        display.set_vertical_scroll_offset(15).unwrap();
+        let overlay = display.scroll_overlay(Rgb565::GREEN, Rgb565::RED);
+        assert_eq!(overlay.boundaries, [Some(15), Some(14)]);
+        assert_eq!(overlay.offset, Some(10));
+
+        overlay.draw(&mut display).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.columns, [0, 0, 0, 239]);
+        assert_eq!(di.pages, [0, 10, 0, 10]);
+    }
+
+    #[test]
+    fn reversed_rows_scroll_backwards() {
+        let mut display = display(Orientation::new().rotate(Rotation::Deg270));
+        let mut scroller = display.scroller(0, 0).unwrap();
+        let di = unsafe { display.dcs() };
+        assert_eq!(di.scroll_area, [0, 0, 0, 240, 0, 80]);
+
+        scroller.scroll_by(&mut display, 10).unwrap();
+        assert_eq!(unsafe { display.dcs() }.scroll_offset, [0, 230]);
+
+        // The content scrolls horizontally in this orientation.
+        scroller
+            .canvas(&mut display)
+            .draw_iter([Pixel(Point::new(0, 5), Rgb565::RED)])
+            .unwrap();
+        let di = unsafe { display.dcs() };
+        // With the `batch` feature the address window extends to the right edge of the display.
+        assert_eq!(di.columns[..2], [0, 90]);
+        assert_eq!(di.pages, [0, 5, 0, 5]);
+    }
+}
@@ -0,0 +1,307 @@
+//! Self-test routine for assembled devices.
+//!
+//! [`Display::run_self_test`] draws a fixed sequence of test patterns, which makes missing
+//! color channels, dead pixels, wrong offsets and banding visible at a glance. Each pattern
+//! is shown for a configurable dwell time. This is useful for production-line tests, where
+//! an operator or a camera checks the display of every assembled device.
+//!
+//! The patterns are shown in the order of [`Pattern::ALL`]:
+//!
+//! 1. solid red, green and blue,
+//! 2. the [`TestImage`],
+//! 3. horizontal gradients for the red, green, blue and all channels,
+//! 4. a one pixel white border on a black background.
+//!
+//! If a clock is set with [`SelfTest::clock`], the time which was needed to draw each
+//! pattern is returned in the [`SelfTestReport`].
+//!
+//! # Examples
+//!
+//! ```
+//! use mipidsi::self_test::SelfTest;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! # let mut delay = mipidsi::_mock::MockDelay;
+//! # fn micros() -> u32 { 0 }
+//! let report = SelfTest::new()
+//!     .dwell_ms(2000)
+//!     .clock(micros)
+//!     .run(&mut display, &mut delay)
+//!     .unwrap();
+//!
+//! let slowest = report.max_draw_time_us();
+//! ```
+
+use embedded_graphics_core::{
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::*,
+};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, TestImage,
+};
+
+/// Test pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Solid red.
+    Red,
+    /// Solid green.
+    Green,
+    /// Solid blue.
+    Blue,
+    /// The [`TestImage`].
+    TestImage,
+    /// Horizontal gradients from black to full intensity.
+    ///
+    /// The display is divided into four horizontal bands, for the red, green and blue
+    /// channel and for all channels.
+    Gradient,
+    /// One pixel white border on a black background.
+    Border,
+}
+
+impl Pattern {
+    /// All patterns in the order they are shown during the self-test.
+    pub const ALL: [Pattern; 6] = [
+        Pattern::Red,
+        Pattern::Green,
+        Pattern::Blue,
+        Pattern::TestImage,
+        Pattern::Gradient,
+        Pattern::Border,
+    ];
+
+    fn draw<D>(self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        D::Color: RgbColor + From<Rgb888>,
+    {
+        match self {
+            Pattern::Red => target.clear(D::Color::RED),
+            Pattern::Green => target.clear(D::Color::GREEN),
+            Pattern::Blue => target.clear(D::Color::BLUE),
+            Pattern::TestImage => TestImage::new().draw(target),
+            Pattern::Gradient => draw_gradient(target),
+            Pattern::Border => draw_border(target),
+        }
+    }
+}
+
+/// Draws horizontal gradients for the red, green, blue and all channels.
+fn draw_gradient<D>(target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    D::Color: From<Rgb888>,
+{
+    let area = target.bounding_box();
+    let width = area.size.width.max(2) - 1;
+    let band_height = (area.size.height / 4).max(1);
+
+    target.fill_contiguous(
+        &area,
+        area.points().map(|p| {
+            let value = ((p.x - area.top_left.x) as u32 * 255 / width) as u8;
+            let color = match (p.y - area.top_left.y) as u32 / band_height {
+                0 => Rgb888::new(value, 0, 0),
+                1 => Rgb888::new(0, value, 0),
+                2 => Rgb888::new(0, 0, value),
+                _ => Rgb888::new(value, value, value),
+            };
+            color.into()
+        }),
+    )
+}
+
+/// Draws a one pixel white border on a black background.
+fn draw_border<D>(target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    D::Color: RgbColor,
+{
+    let area = target.bounding_box();
+    let inner = area.offset(-1);
+
+    target.fill_contiguous(
+        &area,
+        area.points().map(|p| {
+            if inner.contains(p) {
+                D::Color::BLACK
+            } else {
+                D::Color::WHITE
+            }
+        }),
+    )
+}
+
+/// Self-test configuration.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct SelfTest {
+    dwell_ms: u32,
+    clock: Option<fn() -> u32>,
+}
+
+impl SelfTest {
+    /// Creates a new self-test configuration.
+    ///
+    /// Each pattern is shown for one second by default.
+    pub const fn new() -> Self {
+        Self {
+            dwell_ms: 1000,
+            clock: None,
+        }
+    }
+
+    /// Sets the time in milliseconds which every pattern is shown for.
+    #[must_use]
+    pub const fn dwell_ms(mut self, dwell_ms: u32) -> Self {
+        self.dwell_ms = dwell_ms;
+        self
+    }
+
+    /// Sets the clock which is used to measure the draw times.
+    ///
+    /// `clock` must return a timestamp in microseconds, which is allowed to wrap around.
+    #[must_use]
+    pub const fn clock(mut self, clock: fn() -> u32) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Runs the self-test.
+    ///
+    /// Draws all patterns and waits for the dwell time after each pattern. The last
+    /// pattern stays on the display after this function has returned.
+    pub fn run<DI, M, RST, BL>(
+        &self,
+        display: &mut Display<DI, M, RST, BL>,
+        delay: &mut impl DelayNs,
+    ) -> Result<SelfTestReport, DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb888>,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let mut report = SelfTestReport {
+            draw_times_us: [None; Pattern::ALL.len()],
+        };
+
+        for (pattern, draw_time) in Pattern::ALL.iter().zip(&mut report.draw_times_us) {
+            let start = self.clock.map(|clock| clock());
+            pattern.draw(display)?;
+            *draw_time = self
+                .clock
+                .zip(start)
+                .map(|(clock, start)| clock().wrapping_sub(start));
+
+            delay.delay_ms(self.dwell_ms);
+        }
+
+        Ok(report)
+    }
+}
+
+impl Default for SelfTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Timing statistics of a self-test.
+///
+/// The draw times only include the time which was needed to draw the patterns, but not
+/// the dwell time. All times are `None` if no clock was set with [`SelfTest::clock`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestReport {
+    draw_times_us: [Option<u32>; Pattern::ALL.len()],
+}
+
+impl SelfTestReport {
+    /// Returns the time in microseconds which was needed to draw a pattern.
+    pub fn draw_time_us(&self, pattern: Pattern) -> Option<u32> {
+        Pattern::ALL
+            .iter()
+            .position(|p| *p == pattern)
+            .and_then(|index| self.draw_times_us[index])
+    }
+
+    /// Returns the time in microseconds which was needed to draw all patterns.
+    pub fn total_draw_time_us(&self) -> Option<u32> {
+        self.draw_times_us
+            .iter()
+            .try_fold(0u32, |total, time| Some(total.saturating_add((*time)?)))
+    }
+
+    /// Returns the longest time in microseconds which was needed to draw a single pattern.
+    pub fn max_draw_time_us(&self) -> Option<u32> {
+        self.draw_times_us.iter().copied().max().flatten()
+    }
+}
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb888>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Runs the self-test with the default configuration.
+    ///
+    /// Every pattern is shown for one second. Use [`SelfTest`] to change the dwell time
+    /// or to measure the draw times. See the [module documentation](crate::self_test) for
+    /// more information.
+    pub fn run_self_test(&mut self, delay: &mut impl DelayNs) -> Result<SelfTestReport, DI::Error> {
+        SelfTest::new().run(self, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::_mock::{new_mock_display, MockDelay};
+
+    use super::*;
+
+    // Clock which advances by 100 us on every call.
+    fn clock() -> u32 {
+        static TIME: AtomicU32 = AtomicU32::new(u32::MAX - 250);
+        TIME.fetch_add(100, Ordering::Relaxed)
+    }
+
+    #[test]
+    fn draw_times_are_measured() {
+        let mut display = new_mock_display();
+        let report = SelfTest::new()
+            .dwell_ms(0)
+            .clock(clock)
+            .run(&mut display, &mut MockDelay)
+            .unwrap();
+
+        // The clock wraps around during the test
+        for pattern in Pattern::ALL {
+            assert_eq!(report.draw_time_us(pattern), Some(100));
+        }
+        assert_eq!(report.total_draw_time_us(), Some(600));
+        assert_eq!(report.max_draw_time_us(), Some(100));
+    }
+
+    #[test]
+    fn no_times_without_clock() {
+        let mut display = new_mock_display();
+        let report = display.run_self_test(&mut MockDelay).unwrap();
+
+        assert_eq!(report.draw_time_us(Pattern::Gradient), None);
+        assert_eq!(report.total_draw_time_us(), None);
+        assert_eq!(report.max_draw_time_us(), None);
+    }
+}
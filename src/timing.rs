@@ -0,0 +1,227 @@
+//! Checks for the delays that are required between some commands.
+//!
+//! The model init sequence is run with an interface and delay wrapper, which
+//! track the requested delays since the last reset or sleep mode change. In debug
+//! builds a missing delay causes a panic, which makes timing errors in init sequences
+//! visible even if the display works by chance on the hardware it was tested with.
+
+use core::cell::Cell;
+
+use embedded_hal::delay::DelayNs;
+
+use crate::interface::{Event, Interface, InterfaceKind};
+
+const SOFT_RESET: u8 = 0x01;
+const ENTER_SLEEP_MODE: u8 = 0x10;
+const EXIT_SLEEP_MODE: u8 = 0x11;
+
+// Returns the minimal delay in microseconds between the `previous` command and the
+// `next` command.
+//
+// The values are taken from the MIPI DCS specification and the datasheets of the
+// supported controllers. A hardware reset is treated like a software reset.
+const fn required_delay_us(previous: u8, next: u8) -> u32 {
+    match (previous, next) {
+        (SOFT_RESET, EXIT_SLEEP_MODE) => 120_000,
+        (ENTER_SLEEP_MODE, EXIT_SLEEP_MODE) | (EXIT_SLEEP_MODE, ENTER_SLEEP_MODE) => 120_000,
+        (SOFT_RESET | ENTER_SLEEP_MODE | EXIT_SLEEP_MODE, _) => 5_000,
+        _ => 0,
+    }
+}
+
+/// Tracks the delays since the last command with timing requirements.
+#[derive(Default)]
+pub(crate) struct TimingChecker {
+    // Last command with timing requirements and the delay since it was sent
+    last: Cell<Option<(u8, u32)>>,
+}
+
+impl TimingChecker {
+    /// Records a hardware reset.
+    pub fn reset(&self) {
+        self.last.set(Some((SOFT_RESET, 0)));
+    }
+
+    fn delay_us(&self, us: u32) {
+        if let Some((command, delay)) = self.last.get() {
+            self.last.set(Some((command, delay.saturating_add(us))));
+        }
+    }
+
+    fn command(&self, command: u8) {
+        if let Some((previous, delay)) = self.last.get() {
+            let required = required_delay_us(previous, command);
+            debug_assert!(
+                delay >= required,
+                "command 0x{command:02X} was sent {delay} us after command 0x{previous:02X}, \
+                 but a delay of at least {required} us is required"
+            );
+        }
+
+        if required_delay_us(command, 0) > 0 {
+            self.last.set(Some((command, 0)));
+        }
+    }
+}
+
+/// Interface wrapper which reports all commands to a [`TimingChecker`].
+pub(crate) struct CheckedInterface<'a, DI> {
+    di: &'a mut DI,
+    checker: &'a TimingChecker,
+}
+
+impl<'a, DI> CheckedInterface<'a, DI> {
+    pub fn new(di: &'a mut DI, checker: &'a TimingChecker) -> Self {
+        Self { di, checker }
+    }
+}
+
+impl<DI: Interface> Interface for CheckedInterface<'_, DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    const KIND: InterfaceKind = DI::KIND;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.checker.command(command);
+        self.di.send_command(command, args)
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels(pixels)
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        self.di.send_repeated_pixel(pixel, count)
+    }
+
+    fn send_pixels_from_buffer<const N: usize>(
+        &mut self,
+        data: &[Self::Word],
+    ) -> Result<(), Self::Error> {
+        self.di.send_pixels_from_buffer::<N>(data)
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Self::Error> {
+        self.di.handle_event(event)
+    }
+}
+
+/// Delay wrapper which reports all delays to a [`TimingChecker`].
+pub(crate) struct CheckedDelay<'a, D> {
+    delay: &'a mut D,
+    checker: &'a TimingChecker,
+}
+
+impl<'a, D> CheckedDelay<'a, D> {
+    pub fn new(delay: &'a mut D, checker: &'a TimingChecker) -> Self {
+        Self { delay, checker }
+    }
+}
+
+impl<D: DelayNs> DelayNs for CheckedDelay<'_, D> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.checker.delay_us(ns / 1000);
+        self.delay.delay_ns(ns);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.checker.delay_us(us);
+        self.delay.delay_us(us);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.checker.delay_us(ms.saturating_mul(1000));
+        self.delay.delay_ms(ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface, MockOutputPin},
+        dcs::InterfaceExt,
+        models::*,
+        Builder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn delays_are_accumulated() {
+        let checker = TimingChecker::default();
+        let mut di = MockDisplayInterface;
+        let mut delay = MockDelay;
+        let mut di = CheckedInterface::new(&mut di, &checker);
+        let mut delay = CheckedDelay::new(&mut delay, &checker);
+
+        checker.reset();
+        delay.delay_ms(5);
+        di.write_raw(0x36, &[0]).unwrap();
+        delay.delay_us(115_000);
+        di.write_raw(EXIT_SLEEP_MODE, &[]).unwrap();
+        delay.delay_ns(5_000_000);
+        di.write_raw(0x29, &[]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "command 0x11 was sent 100000 us after command 0x01")]
+    fn missing_delay_panics() {
+        let checker = TimingChecker::default();
+        let mut di = MockDisplayInterface;
+        let mut delay = MockDelay;
+        let mut di = CheckedInterface::new(&mut di, &checker);
+        let mut delay = CheckedDelay::new(&mut delay, &checker);
+
+        di.write_raw(SOFT_RESET, &[]).unwrap();
+        delay.delay_ms(100);
+        di.write_raw(EXIT_SLEEP_MODE, &[]).unwrap();
+    }
+
+    fn init<M: Model>(model: M)
+    where
+        M::ColorFormat: crate::interface::InterfacePixelFormat<u8>,
+    {
+        // with hardware reset
+        Builder::new(model, MockDisplayInterface)
+            .reset_pin(MockOutputPin)
+            .init(&mut MockDelay)
+            .unwrap();
+    }
+
+    #[test]
+    fn model_init_sequences() {
+        init(GC9107);
+        init(GC9307);
+        init(GC9A01);
+        init(GC9A01A);
+        init(HX8347);
+        init(ILI9327Rgb565);
+        init(ILI9327Rgb666);
+        init(ILI9341Rgb565);
+        init(ILI9341Rgb666);
+        init(ILI9342CRgb565);
+        init(ILI9342CRgb666);
+        init(ILI9486Rgb565);
+        init(ILI9486Rgb666);
+        init(ILI9488Rgb565);
+        init(ILI9488Rgb666);
+        init(ILI9488Rgb111);
+        init(LG4573A);
+        init(RM67162);
+        init(ST7365PRgb565);
+        init(ST7365PRgb666);
+        init(ST7735B);
+        init(ST7735R);
+        init(ST7735s);
+        init(ST7789);
+        init(ST7796);
+    }
+}
@@ -0,0 +1,79 @@
+//! Trace records for the `trace` feature.
+//!
+//! Every command which is sent with [`InterfaceExt`](crate::dcs::InterfaceExt) and the steps
+//! of the display initialization are recorded with `log` and/or `defmt`, depending on which
+//! of these optional dependencies is enabled. Without the `trace` feature the functions
+//! are empty and are removed by the compiler.
+
+/// Records a command with its parameters.
+#[inline]
+pub(crate) fn command(instruction: u8, params: &[u8]) {
+    #[cfg(all(feature = "trace", feature = "log"))]
+    log::trace!("command {instruction:02X} {params:02X?}");
+    #[cfg(all(feature = "trace", feature = "defmt"))]
+    defmt::trace!("command {=u8:02X} {=[u8]:02X}", instruction, params);
+
+    let _ = (instruction, params);
+}
+
+/// Records a step of the display initialization.
+#[inline]
+pub(crate) fn init(step: &'static str) {
+    #[cfg(all(feature = "trace", feature = "log"))]
+    log::trace!("init: {step}");
+    #[cfg(all(feature = "trace", feature = "defmt"))]
+    defmt::trace!("init: {=str}", step);
+
+    let _ = step;
+}
+
+#[cfg(all(test, feature = "trace", feature = "log"))]
+mod tests {
+    use core::{
+        fmt::Write,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface},
+        models::ST7789,
+        Builder,
+    };
+
+    static EXIT_SLEEP_MODE: AtomicBool = AtomicBool::new(false);
+    static MODEL_INIT: AtomicBool = AtomicBool::new(false);
+
+    /// Logger which looks for the records of the ST7789 init sequence.
+    struct Logger;
+
+    impl log::Log for Logger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let mut message = heapless::String::<64>::new();
+            let _ = write!(message, "{}", record.args());
+            match message.as_str() {
+                "command 11 []" => EXIT_SLEEP_MODE.store(true, Ordering::Relaxed),
+                "init: model init sequence" => MODEL_INIT.store(true, Ordering::Relaxed),
+                _ => {}
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn commands_and_init_steps_are_recorded() {
+        log::set_logger(&Logger).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        Builder::new(ST7789, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert!(EXIT_SLEEP_MODE.load(Ordering::Relaxed));
+        assert!(MODEL_INIT.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,214 @@
+//! Buffer size tuning.
+//!
+//! The best size for the buffer of an interface depends on the MCU, the bus and the
+//! interface implementation: small buffers add the overhead of a transfer for every few
+//! pixels, while large buffers waste RAM without being any faster. [`Display::tune_buffer_size`]
+//! measures the throughput for several transfer sizes and recommends the best size.
+//!
+//! The buffers of the SPI interfaces are borrowed, so the recommended size is applied by
+//! creating the interface with a buffer of that size. To measure a size, the buffer of the
+//! interface must be at least as large, so the tuning should be done with the largest
+//! buffer which fits into the RAM.
+//!
+//! # Examples
+//!
+//! ```
+//! use embedded_graphics::pixelcolor::Rgb565;
+//! use embedded_graphics::prelude::*;
+//!
+//! # let mut display = mipidsi::_mock::new_mock_display();
+//! # let mut time = 0;
+//! # let mut now_us = || { time += 1000; time };
+//! let tuning = display
+//!     .tune_buffer_size(&[64, 256, 512, 1024, 4096], Rgb565::BLACK, now_us)
+//!     .unwrap()
+//!     .unwrap();
+//! // tuning.size is one of the measured sizes
+//! # assert!([64, 256, 512, 1024, 4096].contains(&tuning.size));
+//! ```
+
+use embedded_graphics_core::geometry::Dimensions;
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Event, Interface, InterfacePixelFormat, TransferLimitInterface},
+    models::Model,
+    Display,
+};
+
+/// Result of [`Display::tune_buffer_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTuning {
+    /// Transfer size in bytes with the highest throughput
+    pub size: usize,
+    /// Measured throughput with this size in pixels per second
+    pub pixels_per_second: u32,
+}
+
+impl<DI, M, RST, BL> Display<DI, M, RST, BL>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Measures the throughput for several transfer sizes and returns the best size.
+    ///
+    /// For every size in `sizes`, which are given in bytes, the whole display is filled with
+    /// `color` in transfers of at most that size. `now_us` must return a timestamp in
+    /// microseconds, which is allowed to wrap around. Returns `None` if `sizes` is empty.
+    ///
+    /// If multiple sizes reach the same throughput, the smallest one is recommended.
+    /// See the [module documentation](crate::tune) for more information.
+    pub fn tune_buffer_size(
+        &mut self,
+        sizes: &[usize],
+        color: M::ColorFormat,
+        mut now_us: impl FnMut() -> u32,
+    ) -> Result<Option<BufferTuning>, DI::Error> {
+        let area = self.bounding_box();
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(None);
+        };
+        let Some((sx, sy, ex, ey)) = self.offset_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        ) else {
+            return Ok(None);
+        };
+        let count = area.size.width * area.size.height;
+
+        let mut best: Option<BufferTuning> = None;
+        for &size in sizes {
+            let start = now_us();
+
+            self.di.handle_event(Event::BeginTransaction)?;
+            let endianness = self.options.endianness;
+            let result = self
+                .set_address_window_raw(sx, sy, ex, ey)
+                .and_then(|()| self.model.write_memory_start(&mut self.di))
+                .and_then(|()| {
+                    self.model.send_pixels(
+                        &mut TransferLimitInterface::new(&mut self.di, size),
+                        (0..count).map(|_| color),
+                        endianness,
+                    )
+                });
+            // The transaction is ended even if an error occurs
+            let end = self.di.handle_event(Event::EndTransaction);
+            result?;
+            end?;
+
+            let elapsed = now_us().wrapping_sub(start).max(1);
+            let pixels_per_second =
+                u32::try_from(u64::from(count) * 1_000_000 / u64::from(elapsed))
+                    .unwrap_or(u32::MAX);
+
+            let faster = best.map_or(true, |best| {
+                pixels_per_second > best.pixels_per_second
+                    || (pixels_per_second == best.pixels_per_second && size < best.size)
+            });
+            if faster {
+                best = Some(BufferTuning {
+                    size,
+                    pixels_per_second,
+                });
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, convert::Infallible};
+
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use crate::{_mock::MockDelay, interface::InterfaceKind, models::ST7789, Builder};
+
+    use super::*;
+
+    /// Interface with a simulated clock, which needs 100 us per transfer and 1 us per byte.
+    ///
+    /// Pixel data which is larger than `max_transfer_size` is split into multiple transfers.
+    struct TimedInterface<'a> {
+        time: &'a Cell<u32>,
+        max_transfer_size: Option<usize>,
+    }
+
+    impl Interface for TimedInterface<'_> {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        fn send_command(&mut self, _command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            let bytes = pixels.into_iter().count() * N;
+            let transfers = self.max_transfer_size.map_or(1, |max| bytes.div_ceil(max));
+            self.time
+                .set(self.time.get() + 100 * transfers as u32 + bytes as u32);
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            _pixel: [Self::Word; N],
+            _count: u32,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn tune(max_transfer_size: Option<usize>, sizes: &[usize]) -> Option<BufferTuning> {
+        let time = Cell::new(0);
+        let di = TimedInterface {
+            time: &time,
+            max_transfer_size,
+        };
+        let mut display = Builder::new(ST7789, di)
+            .display_size(100, 100)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display
+            .tune_buffer_size(sizes, Rgb565::BLACK, || time.get())
+            .unwrap()
+    }
+
+    #[test]
+    fn largest_size_is_fastest() {
+        // 20000 bytes in 10 transfers of 2000 bytes take 21000 us
+        assert_eq!(
+            tune(None, &[2000, 200, 20]),
+            Some(BufferTuning {
+                size: 2000,
+                pixels_per_second: 476_190,
+            })
+        );
+    }
+
+    #[test]
+    fn interface_limit_is_respected() {
+        // Sizes above the limit of the interface are as fast as the limit.
+        let tuning = tune(Some(200), &[2000, 200, 20]).unwrap();
+        assert_eq!(tuning.size, 200);
+    }
+
+    #[test]
+    fn no_sizes() {
+        assert_eq!(tune(None, &[]), None);
+    }
+}
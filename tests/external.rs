@@ -6,7 +6,7 @@ use mipidsi::{
         SetDisplayOn, SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
-    models::Model,
+    models::{Model, ModelInitError},
     options::ModelOptions,
 };
 
@@ -17,13 +17,14 @@ pub struct ExternalST7789;
 impl Model for ExternalST7789 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const DISPLAY_ON_DELAY_US: u32 = 120_000;
 
     fn init<DELAY, DI>(
         &mut self,
         di: &mut DI,
         delay: &mut DELAY,
         options: &ModelOptions,
-    ) -> Result<SetAddressMode, DI::Error>
+    ) -> Result<SetAddressMode, ModelInitError<DI::Error>>
     where
         DELAY: DelayNs,
         DI: Interface,
@@ -45,10 +46,11 @@ impl Model for ExternalST7789 {
         delay.delay_us(10_000);
         di.write_command(EnterNormalMode)?;
         delay.delay_us(10_000);
-        di.write_command(SetDisplayOn)?;
 
-        // DISPON requires some time otherwise we risk SPI data issues
-        delay.delay_us(120_000);
+        if options.display_on {
+            di.write_command(SetDisplayOn)?;
+            delay.delay_us(Self::DISPLAY_ON_DELAY_US);
+        }
 
         Ok(madctl)
     }